@@ -0,0 +1,4 @@
+// sqlx::migrate!로 포함된 마이그레이션이 변경되면 다시 빌드
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,8 @@
+-- 0001_initial_schema 되돌리기 (트리거/인덱스는 테이블과 함께 삭제됨)
+DROP TABLE IF EXISTS user_type_menu;
+DROP TABLE IF EXISTS user_type_permission;
+DROP TABLE IF EXISTS menu_item;
+DROP TABLE IF EXISTS permission;
+DROP TABLE IF EXISTS admin_user;
+DROP TABLE IF EXISTS user_type;
+DROP FUNCTION IF EXISTS set_updated_at();
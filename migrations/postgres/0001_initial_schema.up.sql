@@ -0,0 +1,121 @@
+-- 202504190000001_initial_schema.sql (PostgreSQL)
+-- 타임스탬프는 UTC 기준 TIMESTAMP로 저장 (연결 시 세션 시간대를 UTC로 설정)
+
+-- 사용자 종류 (역할) 테이블
+CREATE TABLE IF NOT EXISTS user_type
+(
+    id          BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name        TEXT      NOT NULL UNIQUE, -- 예: SuperAdmin, ContentEditor, Viewer
+    description TEXT,
+    created_at  TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at  TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 관리자 사용자 테이블
+CREATE TABLE IF NOT EXISTS admin_user
+(
+    id            BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    username      TEXT      NOT NULL UNIQUE,
+    password_hash TEXT      NOT NULL,
+    user_type_id  BIGINT    NOT NULL REFERENCES user_type (id) ON DELETE RESTRICT, -- 사용자 종류 삭제 시 해당 유저가 있으면 삭제 방지
+    is_active     BOOLEAN   NOT NULL DEFAULT TRUE,
+    last_login_at TIMESTAMP,
+    created_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_user_user_type_id ON admin_user (user_type_id);
+
+-- 권한 테이블
+CREATE TABLE IF NOT EXISTS permission
+(
+    id          BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    code        VARCHAR(255) NOT NULL UNIQUE, -- 예: user:read, user:create, user_type:read, menu:update
+    description TEXT,
+    created_at  TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at  TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 메뉴 항목 테이블
+CREATE TABLE IF NOT EXISTS menu_item
+(
+    id            BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name          TEXT      NOT NULL,                                     -- 메뉴 표시 이름 (예: "사용자 관리")
+    path          TEXT      NOT NULL UNIQUE,                              -- 프론트엔드 라우팅 경로 (예: "/users")
+    icon          TEXT,                                                   -- 아이콘 클래스 또는 URL (선택 사항)
+    parent_id     BIGINT    REFERENCES menu_item (id) ON DELETE SET NULL, -- 부모 메뉴 ID (null이면 최상위)
+    display_order BIGINT    NOT NULL DEFAULT 0,
+    is_visible    BOOLEAN   NOT NULL DEFAULT TRUE,
+    created_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_menu_item_parent_id ON menu_item (parent_id);
+
+-- 사용자 종류 - 권한 매핑 테이블 (다대다)
+CREATE TABLE IF NOT EXISTS user_type_permission
+(
+    user_type_id  BIGINT    NOT NULL REFERENCES user_type (id) ON DELETE CASCADE,
+    permission_id BIGINT    NOT NULL REFERENCES permission (id) ON DELETE CASCADE,
+    created_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_type_id, permission_id)
+);
+
+-- 사용자 종류 - 메뉴 매핑 테이블 (다대다)
+CREATE TABLE IF NOT EXISTS user_type_menu
+(
+    user_type_id BIGINT NOT NULL REFERENCES user_type (id) ON DELETE CASCADE,
+    menu_item_id BIGINT NOT NULL REFERENCES menu_item (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_type_id, menu_item_id)
+);
+
+
+-- 업데이트 시 updated_at 자동 갱신 트리거 (PostgreSQL)
+CREATE OR REPLACE FUNCTION set_updated_at() RETURNS TRIGGER AS
+$$
+BEGIN
+    NEW.updated_at = CURRENT_TIMESTAMP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER user_type_updated_at
+    BEFORE UPDATE
+    ON user_type
+    FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
+
+CREATE TRIGGER admin_user_updated_at
+    BEFORE UPDATE
+    ON admin_user
+    FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
+
+CREATE TRIGGER permission_updated_at
+    BEFORE UPDATE
+    ON permission
+    FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
+
+CREATE TRIGGER menu_item_updated_at
+    BEFORE UPDATE
+    ON menu_item
+    FOR EACH ROW
+EXECUTE FUNCTION set_updated_at();
+
+-- 초기 데이터 (선택 사항)
+-- 기본 user_type 데이터 추가
+INSERT INTO user_type (name, description)
+VALUES ('SuperAdmin', '시스템 전체 관리자'),
+       ('Admin', '일반 관리자'),
+       ('User', '일반 사용자');
+INSERT INTO permission (code, description)
+VALUES ('*', 'All permissions');
+INSERT INTO menu_item (name, path, display_order)
+VALUES ('Dashboard', '/', 0);
+
+-- SuperAdmin에게 모든 권한과 기본 메뉴 부여
+INSERT INTO user_type_permission (user_type_id, permission_id)
+VALUES (1, 1);
+INSERT INTO user_type_menu (user_type_id, menu_item_id)
+VALUES (1, 1);
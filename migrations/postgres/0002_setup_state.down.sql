@@ -0,0 +1,2 @@
+-- 0002_setup_state 되돌리기
+DROP TABLE IF EXISTS setup_state;
@@ -0,0 +1,20 @@
+-- 최초 실행 설정(/setup) 완료 여부 (단일 행)
+CREATE TABLE IF NOT EXISTS setup_state
+(
+    id           INTEGER PRIMARY KEY CHECK (id = 1),
+    completed_by BIGINT REFERENCES admin_user (id) ON DELETE SET NULL,
+    completed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 이미 사용자가 있는 기존 설치본은 설정 완료로 간주 (0001이 넣은 기본 admin 계정은 제외)
+INSERT INTO setup_state (id, completed_by)
+SELECT 1, MIN(id)
+FROM admin_user
+WHERE password_hash <> '$2b$12$5ONiULm9P4oefANo/NYCguK4W4NdKYgoAbKFl9X5iqaMZeS7IfWqy'
+HAVING COUNT(1) > 0;
+
+-- 공개된 기본 비밀번호('password123')를 그대로 쓰는 기본 admin 계정 삭제
+DELETE
+FROM admin_user
+WHERE username = 'admin'
+  AND password_hash = '$2b$12$5ONiULm9P4oefANo/NYCguK4W4NdKYgoAbKFl9X5iqaMZeS7IfWqy';
@@ -0,0 +1,2 @@
+-- 0003_system_setting 되돌리기
+DROP TABLE IF EXISTS system_setting;
@@ -0,0 +1,24 @@
+-- 예약 작업 실행 이력
+CREATE TABLE IF NOT EXISTS job_run
+(
+    id           BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    job_name     VARCHAR(100) NOT NULL,
+    trigger_type VARCHAR(20)  NOT NULL, -- schedule / manual
+    status       VARCHAR(20)  NOT NULL, -- running / succeeded / failed
+    message      TEXT,
+    started_by   BIGINT REFERENCES admin_user (id) ON DELETE SET NULL, -- 수동 실행한 사용자
+    instance_id  VARCHAR(100) NOT NULL,
+    started_at   TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_at  TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_run_job_name_started_at ON job_run (job_name, started_at);
+
+-- 작업별 실행 잠금 (여러 인스턴스 중 하나만 실행, locked_until이 지나면 만료)
+CREATE TABLE IF NOT EXISTS job_lock
+(
+    job_name     VARCHAR(100) PRIMARY KEY,
+    locked_by    VARCHAR(100) NOT NULL,
+    locked_until TIMESTAMP    NOT NULL,
+    last_slot    TIMESTAMP    -- 예약 실행이 처리한 마지막 예정 시각 (같은 시각을 다른 인스턴스가 다시 실행하지 않도록)
+);
@@ -0,0 +1,5 @@
+-- 0005_account_dormancy 되돌리기
+ALTER TABLE admin_user DROP COLUMN reactivated_at;
+ALTER TABLE admin_user DROP COLUMN deactivated_reason;
+ALTER TABLE admin_user DROP COLUMN deactivated_at;
+ALTER TABLE admin_user DROP COLUMN dormancy_warned_at;
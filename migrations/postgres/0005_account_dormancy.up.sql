@@ -0,0 +1,5 @@
+-- 장기 미사용 계정 비활성화 정책 (경고 시각, 비활성화 사유, 재활성화 시각)
+ALTER TABLE admin_user ADD COLUMN dormancy_warned_at TIMESTAMP;
+ALTER TABLE admin_user ADD COLUMN deactivated_at TIMESTAMP;
+ALTER TABLE admin_user ADD COLUMN deactivated_reason TEXT;
+ALTER TABLE admin_user ADD COLUMN reactivated_at TIMESTAMP; -- 미사용 기간 계산 기준에 포함
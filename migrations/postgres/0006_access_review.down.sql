@@ -0,0 +1,3 @@
+-- 0006_access_review 되돌리기
+DROP TABLE IF EXISTS access_review_item;
+DROP TABLE IF EXISTS access_review;
@@ -0,0 +1,4 @@
+-- 0007_user_permission 되돌리기
+DROP INDEX IF EXISTS idx_user_permission_audit_user_id_changed_at;
+DROP TABLE IF EXISTS user_permission_audit;
+DROP TABLE IF EXISTS user_permission;
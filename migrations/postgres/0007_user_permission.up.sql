@@ -0,0 +1,31 @@
+-- 사용자별 권한 예외 (사용자 종류로 받은 권한에 추가 허용 또는 거부)
+CREATE TABLE IF NOT EXISTS user_permission
+(
+    id            BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id       BIGINT       NOT NULL REFERENCES admin_user (id) ON DELETE CASCADE,
+    permission_id BIGINT       NOT NULL REFERENCES permission (id) ON DELETE CASCADE,
+    effect        VARCHAR(10)  NOT NULL, -- allow / deny
+    reason        TEXT,
+    expires_at    TIMESTAMP,             -- NULL이면 만료 없음
+    granted_by    VARCHAR(100) NOT NULL,
+    granted_at    TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, permission_id)
+);
+
+-- 사용자별 권한 예외 변경 이력 (사용자/권한이 삭제되어도 유지되도록 외래 키 없음)
+CREATE TABLE IF NOT EXISTS user_permission_audit
+(
+    id              BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    user_id         BIGINT       NOT NULL,
+    username        TEXT         NOT NULL,
+    permission_code TEXT         NOT NULL,
+    action          VARCHAR(20)  NOT NULL, -- set / remove
+    effect          VARCHAR(10),           -- 변경 후(set) 또는 삭제 전(remove) 값
+    expires_at      TIMESTAMP,
+    reason          TEXT,
+    changed_by      VARCHAR(100) NOT NULL,
+    changed_at      TIMESTAMP    NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_permission_audit_user_id_changed_at
+    ON user_permission_audit (user_id, changed_at);
@@ -118,6 +118,3 @@ INSERT INTO user_type_permission (user_type_id, permission_id)
 VALUES (1, 1);
 INSERT INTO user_type_menu (user_type_id, menu_item_id)
 VALUES (1, 1);
-
--- 'password123' 해시 예시
-INSERT INTO admin_user (username, password_hash, user_type_id) VALUES ('admin', '$2b$12$5ONiULm9P4oefANo/NYCguK4W4NdKYgoAbKFl9X5iqaMZeS7IfWqy', 1);
\ No newline at end of file
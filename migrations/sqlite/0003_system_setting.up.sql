@@ -0,0 +1,8 @@
+-- 재시작 없이 변경 가능한 런타임 설정 (값은 JSON으로 저장, 행이 없으면 설정 파일의 기본값 사용)
+CREATE TABLE IF NOT EXISTS system_setting
+(
+    key        VARCHAR(100) PRIMARY KEY,
+    value      TEXT NOT NULL,
+    updated_by INTEGER REFERENCES admin_user (id) ON DELETE SET NULL,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
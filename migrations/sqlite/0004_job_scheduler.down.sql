@@ -0,0 +1,4 @@
+-- 0004_job_scheduler 되돌리기
+DROP TABLE IF EXISTS job_lock;
+DROP INDEX IF EXISTS idx_job_run_job_name_started_at;
+DROP TABLE IF EXISTS job_run;
@@ -0,0 +1,32 @@
+-- 접근 권한 재인증(access review) 캠페인
+-- 증적 보존을 위해 검토자는 ID가 아닌 사용자 이름으로 저장 (사용자가 삭제되어도 유지)
+CREATE TABLE IF NOT EXISTS access_review
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    name       VARCHAR(200)                       NOT NULL,
+    status     VARCHAR(20)                        NOT NULL DEFAULT 'open', -- open / closed
+    user_types TEXT                               NOT NULL,                -- 대상 사용자 종류 이름 (JSON 배열)
+    created_by VARCHAR(100)                       NOT NULL,
+    due_at     DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    closed_by  VARCHAR(100),
+    closed_at  DATETIME
+);
+
+-- 캠페인 시작 시점의 사용자별 사용자 종류/유효 권한 스냅샷과 검토 결과
+CREATE TABLE IF NOT EXISTS access_review_item
+(
+    id             INTEGER PRIMARY KEY AUTOINCREMENT,
+    review_id      INTEGER      NOT NULL REFERENCES access_review (id) ON DELETE CASCADE,
+    user_id        INTEGER REFERENCES admin_user (id) ON DELETE SET NULL,
+    username       TEXT         NOT NULL,
+    user_type_id   INTEGER      NOT NULL, -- 스냅샷이므로 외래 키 없음
+    user_type_name TEXT         NOT NULL,
+    permissions    TEXT         NOT NULL, -- 권한 코드 (JSON 배열)
+    decision       VARCHAR(20)  NOT NULL DEFAULT 'pending', -- pending / keep / revoke
+    comment        TEXT,
+    decided_by     VARCHAR(100),
+    decided_at     DATETIME,
+    applied_at     DATETIME, -- 종료 시 회수가 적용된 시각
+    UNIQUE (review_id, user_id)
+);
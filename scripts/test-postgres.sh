@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+# 임시 PostgreSQL 인스턴스를 띄워 postgres 백엔드로 테스트 실행
+# 사용법: scripts/test-postgres.sh [cargo test 추가 인자...]
+# 이미 실행 중인 DB를 쓰려면 TEST_DATABASE_URL을 지정 (public 스키마가 초기화됨)
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+run_tests() {
+    cargo test --no-default-features --features postgres "$@"
+}
+
+if [[ -n "${TEST_DATABASE_URL:-}" ]]; then
+    run_tests "$@"
+    exit
+fi
+
+PORT="${PGPORT:-54329}"
+DATA_DIR="$(mktemp -d)"
+trap 'pg_ctl -D "$DATA_DIR" -m immediate stop >/dev/null 2>&1 || true; rm -rf "$DATA_DIR"' EXIT
+
+initdb -D "$DATA_DIR" -U postgres --auth=trust >/dev/null
+pg_ctl -D "$DATA_DIR" -o "-p $PORT -k $DATA_DIR -c listen_addresses=localhost" -l "$DATA_DIR/postgres.log" -w start >/dev/null
+createdb -h localhost -p "$PORT" -U postgres admin_server_test
+
+TEST_DATABASE_URL="postgres://postgres@localhost:$PORT/admin_server_test" run_tests "$@"
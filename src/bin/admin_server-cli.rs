@@ -0,0 +1,344 @@
+use std::io::{self, BufRead, Write};
+
+use admin_server::{
+    config::{
+        db::{self, DbPool},
+        env::{self, ConfigArgs, Env},
+    },
+    dto::user::{CreateUserRequest, ResetPasswordRequest},
+    repositories::SqlxRepository,
+    services::{self, user_type::SUPER_ADMIN_USER_TYPE},
+};
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::{Parser, Subcommand};
+use rand::RngCore;
+
+/// Maintenance commands operating directly on the admin_server database.
+#[derive(Parser)]
+#[command(name = "admin_server-cli", version)]
+struct Cli {
+    #[command(flatten)]
+    config: ConfigArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a user with the SuperAdmin user type
+    CreateSuperuser {
+        #[arg(long)]
+        username: String,
+        /// Read from stdin when omitted
+        #[arg(long, env = "ADMIN_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Reset the password of an existing user
+    ResetPassword {
+        #[arg(long)]
+        username: String,
+        /// Read from stdin when omitted
+        #[arg(long, env = "ADMIN_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// List or assign user types (roles)
+    #[command(subcommand)]
+    Roles(RolesCommand),
+    /// Run or roll back database migrations
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// Check applied migrations, checksums and required tables
+    VerifySchema,
+    /// Inspect the effective configuration
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Generate a new JWT secret and store it in JWT_SECRET_FILE or the active env file
+    RotateJwtSecret {
+        /// Defaults to the file selected by PROFILE (.env, .env.dev, .env.prod)
+        #[arg(long)]
+        env_file: Option<String>,
+        /// Only print the new secret without modifying any file
+        #[arg(long)]
+        print_only: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RolesCommand {
+    /// List all user types
+    List,
+    /// Assign a user type to a user
+    Assign {
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        role: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Validate the configuration and print it with secrets redacted
+    Check,
+}
+
+#[derive(Subcommand)]
+enum MigrateCommand {
+    /// Apply all pending migrations
+    Run,
+    /// Show applied and pending migrations and checksum mismatches
+    Status,
+    /// Revert applied migrations down to the target version (default: previous version)
+    Revert {
+        #[arg(long)]
+        target: Option<i64>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt().with_writer(io::stderr).init();
+
+    let cli = Cli::parse();
+    if let Command::RotateJwtSecret {
+        env_file,
+        print_only,
+    } = &cli.command
+    {
+        env::load_env_file(&cli.config.profile);
+        return rotate_jwt_secret(env_file.as_deref(), &cli.config.profile, *print_only);
+    }
+
+    let env = Env::load(&cli.config)?;
+    if let Command::Config(ConfigCommand::Check) = &cli.command {
+        return config_check(&env);
+    }
+    env.validate()?;
+
+    let pool = db::create_pool(&env.database.url, &env.database.pool).await?;
+    let repo = SqlxRepository::new(db::DbPools::single(pool.clone()));
+
+    match cli.command {
+        Command::CreateSuperuser { username, password } => {
+            let password = password_or_prompt(password)?;
+            let user_type =
+                services::user_type::get_user_type_by_name(&repo, SUPER_ADMIN_USER_TYPE).await?;
+            let req = CreateUserRequest {
+                username: username.clone(),
+                password,
+                user_type_id: user_type.id,
+                is_active: Some(true),
+            };
+            // 사용자 생성과 웹 최초 설정(/setup) 비활성화를 한 트랜잭션으로 처리
+            let user = services::setup::create_super_admin(&pool, req).await?;
+            println!("Created superuser `{}` (id: {})", username, user.id);
+        }
+        Command::ResetPassword { username, password } => {
+            let password = password_or_prompt(password)?;
+            services::user::reset_password(&repo, &username, ResetPasswordRequest { password })
+                .await?;
+            println!("Password for `{}` has been reset", username);
+        }
+        Command::Roles(RolesCommand::List) => {
+            for user_type in services::user_type::list_user_types(&repo).await? {
+                println!(
+                    "{:>4}  {:<20}  {}",
+                    user_type.id, user_type.name, user_type.description
+                );
+            }
+        }
+        Command::Roles(RolesCommand::Assign { username, role }) => {
+            let user_type = services::user_type::get_user_type_by_name(&repo, &role).await?;
+            services::user::assign_user_type(&repo, &username, user_type.id).await?;
+            println!("Assigned user type `{}` to `{}`", role, username);
+        }
+        Command::Migrate(MigrateCommand::Run) => {
+            db::migrate_db(&pool, &env.database.migration_dir).await?;
+            println!("Migrations applied");
+        }
+        Command::Migrate(MigrateCommand::Status) => {
+            let report = db::verify_schema(&pool, &env.database.migration_dir).await?;
+            print_schema_report(&report);
+        }
+        Command::Migrate(MigrateCommand::Revert { target }) => {
+            let target = match target {
+                Some(target) => target,
+                None => previous_version(&pool, &env.database.migration_dir).await?,
+            };
+            db::revert_migrations(&pool, &env.database.migration_dir, target).await?;
+            println!("Reverted migrations down to version {}", target);
+        }
+        Command::VerifySchema => {
+            let report = db::verify_schema(&pool, &env.database.migration_dir).await?;
+            print_schema_report(&report);
+            if !report.is_ok() {
+                bail!("Database schema does not match the migrations");
+            }
+            println!("Schema OK");
+        }
+        Command::RotateJwtSecret { .. } | Command::Config(_) => {
+            unreachable!("handled before connecting")
+        }
+    }
+
+    pool.close().await;
+    Ok(())
+}
+
+fn print_schema_report(report: &db::SchemaReport) {
+    println!("source:              {:?}", report.source);
+    println!("applied:             {:?}", report.applied);
+    println!("pending:             {:?}", report.pending);
+    println!("checksum mismatches: {:?}", report.checksum_mismatches);
+    println!("unknown versions:    {:?}", report.unknown);
+    println!("dirty version:       {:?}", report.dirty);
+    println!("missing tables:      {:?}", report.missing_tables);
+}
+
+// 직전에 적용된 마이그레이션 버전 (적용된 것이 하나뿐이면 0)
+async fn previous_version(pool: &DbPool, migration_dir: &str) -> Result<i64> {
+    let report = db::verify_schema(pool, migration_dir).await?;
+    let mut applied = report.applied;
+    applied.sort_unstable();
+    if applied.pop().is_none() {
+        bail!("No applied migrations to revert");
+    }
+    Ok(applied.pop().unwrap_or(0))
+}
+
+fn password_or_prompt(password: Option<String>) -> Result<String> {
+    if let Some(password) = password {
+        return Ok(password);
+    }
+
+    eprint!("Password: ");
+    io::stderr().flush()?;
+    let echo = EchoGuard::disable()?;
+    let mut line = String::new();
+    let read = io::stdin().lock().read_line(&mut line);
+    drop(echo);
+    read.context("Failed to read password from stdin")?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+// 터미널에서 입력하는 동안 비밀번호가 화면에 표시되지 않도록 에코를 끄고, drop 시 원래 설정으로 복원
+// (파이프 등 터미널이 아닌 입력은 그대로 읽음)
+#[cfg(unix)]
+struct EchoGuard(Option<libc::termios>);
+
+#[cfg(unix)]
+impl EchoGuard {
+    fn disable() -> Result<Self> {
+        let fd = libc::STDIN_FILENO;
+        // SAFETY: termios는 tcgetattr가 채우는 일반 C 구조체이며 fd는 표준 입력
+        unsafe {
+            if libc::isatty(fd) != 1 {
+                return Ok(Self(None));
+            }
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(fd, &mut original) != 0 {
+                bail!(
+                    "Failed to read terminal settings: {}",
+                    io::Error::last_os_error()
+                );
+            }
+            let mut silent = original;
+            // 입력은 숨기고 Enter의 줄바꿈만 표시
+            silent.c_lflag &= !libc::ECHO;
+            silent.c_lflag |= libc::ECHONL;
+            if libc::tcsetattr(fd, libc::TCSANOW, &silent) != 0 {
+                bail!(
+                    "Failed to disable terminal echo: {}",
+                    io::Error::last_os_error()
+                );
+            }
+            Ok(Self(Some(original)))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoGuard {
+    fn drop(&mut self) {
+        if let Some(original) = &self.0 {
+            // SAFETY: disable()에서 읽어 둔 설정을 같은 fd에 되돌림
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct EchoGuard;
+
+#[cfg(not(unix))]
+impl EchoGuard {
+    fn disable() -> Result<Self> {
+        use std::io::IsTerminal;
+        if io::stdin().is_terminal() {
+            bail!("Interactive password input is not supported on this platform; use --password or ADMIN_PASSWORD");
+        }
+        Ok(Self)
+    }
+}
+
+fn config_check(env: &Env) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&env.redacted())?);
+    env.validate()?;
+    eprintln!("Configuration OK (profile: {})", env.profile);
+    Ok(())
+}
+
+fn rotate_jwt_secret(env_file: Option<&str>, profile: &str, print_only: bool) -> Result<()> {
+    let mut bytes = [0u8; 48];
+    rand::rng().fill_bytes(&mut bytes);
+    let secret = STANDARD.encode(bytes);
+
+    if print_only {
+        println!("{}", secret);
+        return Ok(());
+    }
+
+    // JWT_SECRET_FILE로 읽는 경우 env 파일에 JWT_SECRET을 쓰면 두 설정이 충돌하므로 시크릿 파일을 갱신
+    if let Some((name, path)) = env::file_source("auth.jwt_secret", std::env::vars()) {
+        if env_file.is_some() {
+            bail!(
+                "The JWT secret is read from `{}` ({}); --env-file cannot be used",
+                path,
+                name
+            );
+        }
+        std::fs::write(&path, format!("{}\n", secret))
+            .with_context(|| format!("Failed to write `{}`", path))?;
+        println!("Wrote a new JWT secret to `{}` ({})", path, name);
+    } else {
+        let path = env_file.unwrap_or_else(|| env::env_file_path(profile));
+        write_env_secret(path, &secret)?;
+        println!("Wrote a new JWT_SECRET to `{}`", path);
+    }
+    eprintln!("Restart the server to apply it; all previously issued tokens are now invalid.");
+    Ok(())
+}
+
+fn write_env_secret(path: &str, secret: &str) -> Result<()> {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("JWT_SECRET=") {
+                replaced = true;
+                format!("JWT_SECRET=\"{}\"", secret)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(format!("JWT_SECRET=\"{}\"", secret));
+    }
+    std::fs::write(path, lines.join("\n") + "\n")
+        .with_context(|| format!("Failed to write `{}`", path))
+}
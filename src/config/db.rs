@@ -1,23 +1,435 @@
 use anyhow::Context;
-use sqlx::{migrate::Migrator, sqlite::SqlitePoolOptions, SqlitePool};
-use std::{error::Error, path::Path, result::Result};
+use serde::{Deserialize, Serialize};
+use sqlx::migrate::{Migrate, Migration, Migrator};
+use std::{ops::Deref, path::Path, result::Result};
 
-pub async fn create_pool(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
+#[cfg(all(feature = "sqlite", feature = "postgres"))]
+compile_error!("features `sqlite` and `postgres` are mutually exclusive");
+#[cfg(not(any(feature = "sqlite", feature = "postgres")))]
+compile_error!("either the `sqlite` or the `postgres` feature must be enabled");
+
+// 빌드 시 선택된 데이터베이스 백엔드 (cargo feature `sqlite` / `postgres`)
+#[cfg(feature = "sqlite")]
+pub type Db = sqlx::Sqlite;
+#[cfg(feature = "postgres")]
+pub type Db = sqlx::Postgres;
+
+pub type DbPool = sqlx::Pool<Db>;
+pub type DbConnection = <Db as sqlx::Database>::Connection;
+pub type DbTransaction<'c> = sqlx::Transaction<'c, Db>;
+pub type DbArguments = <Db as sqlx::Database>::Arguments<'static>;
+pub type DbRow = <Db as sqlx::Database>::Row;
+
+// 백엔드별 마이그레이션 디렉터리 (MIGRATION_DIR 미설정 시 사용)
+#[cfg(feature = "sqlite")]
+pub const DEFAULT_MIGRATION_DIR: &str = "./migrations/sqlite";
+#[cfg(feature = "postgres")]
+pub const DEFAULT_MIGRATION_DIR: &str = "./migrations/postgres";
+
+// 바이너리에 포함된 마이그레이션 (마이그레이션 디렉터리가 없는 배포 환경용)
+#[cfg(feature = "sqlite")]
+static EMBEDDED_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+#[cfg(feature = "postgres")]
+static EMBEDDED_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+// 기존 설치본에 적용된 뒤 마지막 구문이 제거된 마이그레이션 (버전, 제거된 구문)
+// 0001: 공개된 기본 admin 계정('password123') 시드. 이 계정은 0002가 삭제함
+const SUPERSEDED_MIGRATIONS: &[(i64, &str)] = &[(
+    1,
+    "\n-- 'password123' 해시 예시\nINSERT INTO admin_user (username, password_hash, user_type_id) VALUES ('admin', '$2b$12$5ONiULm9P4oefANo/NYCguK4W4NdKYgoAbKFl9X5iqaMZeS7IfWqy', 1);",
+)];
+
+// 기록된 체크섬이 "현재 SQL + 제거된 구문"을 그대로 적용한 결과일 때만 이전 버전으로 인정
+fn is_superseded_revision(migration: &Migration, applied_checksum: &[u8]) -> bool {
+    SUPERSEDED_MIGRATIONS
+        .iter()
+        .filter(|(version, _)| *version == migration.version)
+        .any(|(_, removed)| {
+            let sql = format!("{}{}", migration.sql, removed);
+            let earlier = Migration::new(
+                migration.version,
+                migration.description.clone(),
+                migration.migration_type,
+                sql.into(),
+                migration.no_tx,
+            );
+            earlier.checksum.as_ref() == applied_checksum
+        })
+}
+
+/// Connection settings, the `database.pool` configuration section (`DB_*` variables).
+///
+/// `journal_mode`, `synchronous`, `busy_timeout_ms` and `foreign_keys` only apply to SQLite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DbOptions {
+    pub max_connections: u32, // SQLite는 읽기 풀 크기 (쓰기 풀은 항상 1)
+    pub busy_timeout_ms: u64,
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub foreign_keys: bool,
+    pub statement_cache_capacity: usize,
+}
+
+impl Default for DbOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            busy_timeout_ms: 5000,
+            journal_mode: "wal".to_string(),
+            synchronous: "normal".to_string(),
+            foreign_keys: true,
+            statement_cache_capacity: 100,
+        }
+    }
+}
+
+/// Read and write pools. With SQLite all writes go through a single connection so
+/// concurrent writers queue in the pool instead of failing with `database is locked`;
+/// with PostgreSQL both point at the same pool.
+#[derive(Clone)]
+pub struct DbPools {
+    pub read: DbPool,
+    pub write: DbPool,
+}
+
+impl DbPools {
+    // 읽기/쓰기에 같은 풀 사용 (CLI, 테스트 등)
+    pub fn single(pool: DbPool) -> Self {
+        Self {
+            read: pool.clone(),
+            write: pool,
+        }
+    }
+}
+
+/// Closes both pools at shutdown, waiting for checked-out connections to be returned. With
+/// SQLite the WAL is checkpointed first so the database file is complete on its own.
+pub async fn close_pools(pools: &DbPools) {
+    pools.read.close().await;
+    // 읽기 연결이 모두 닫힌 뒤여야 WAL 파일을 비울 수 있음
+    #[cfg(feature = "sqlite")]
+    match sqlx::query_as::<_, (i64, i64, i64)>("PRAGMA wal_checkpoint(TRUNCATE)")
+        .fetch_one(&pools.write)
+        .await
+    {
+        Ok((0, _, _)) => tracing::info!("Checkpointed the SQLite WAL"),
+        Ok(_) => tracing::warn!("SQLite WAL checkpoint was blocked by another connection"),
+        Err(e) => tracing::warn!("Failed to checkpoint the SQLite WAL: {}", e),
+    }
+    pools.write.close().await;
+    tracing::info!("Closed database connections");
+}
+
+#[cfg(feature = "sqlite")]
+fn sqlite_connect_options(
+    database_url: &str,
+    options: &DbOptions,
+) -> Result<sqlx::sqlite::SqliteConnectOptions, sqlx::Error> {
+    use sqlx::sqlite::SqliteConnectOptions;
+    use std::{str::FromStr, time::Duration};
+
+    Ok(SqliteConnectOptions::from_str(database_url)?
+        .busy_timeout(Duration::from_millis(options.busy_timeout_ms))
+        .synchronous(options.synchronous.parse()?)
+        .foreign_keys(options.foreign_keys)
+        .statement_cache_capacity(options.statement_cache_capacity))
+}
+
+// 읽기/쓰기 겸용 단일 풀 (마이그레이션, CLI 등)
+#[cfg(feature = "sqlite")]
+pub async fn create_pool(database_url: &str, options: &DbOptions) -> Result<DbPool, sqlx::Error> {
     tracing::info!("Connecting to database at `{}`", database_url);
 
-    SqlitePoolOptions::new()
-        .max_connections(10)
-        .connect(database_url)
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(options.max_connections)
+        .connect_with(
+            sqlite_connect_options(database_url, options)?
+                .journal_mode(options.journal_mode.parse()?),
+        )
         .await
 }
 
-pub async fn migrate_db(pool: &SqlitePool, migration_dir: &str) -> Result<(), Box<dyn Error>> {
-    tracing::info!("Running database migrations from `{}`", migration_dir);
+#[cfg(feature = "sqlite")]
+pub async fn create_pools(database_url: &str, options: &DbOptions) -> Result<DbPools, sqlx::Error> {
+    tracing::info!(
+        "Connecting to database at `{}` (journal_mode={}, synchronous={}, read connections={})",
+        database_url,
+        options.journal_mode,
+        options.synchronous,
+        options.max_connections
+    );
+
+    // journal_mode(WAL)는 파일에 영구 저장되므로 쓰기 풀에서만 설정
+    let write = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(
+            sqlite_connect_options(database_url, options)?
+                .journal_mode(options.journal_mode.parse()?),
+        )
+        .await?;
+    let read = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(options.max_connections)
+        .connect_with(sqlite_connect_options(database_url, options)?.pragma("query_only", "ON"))
+        .await?;
+
+    Ok(DbPools { read, write })
+}
+
+#[cfg(feature = "postgres")]
+pub async fn create_pool(database_url: &str, options: &DbOptions) -> Result<DbPool, sqlx::Error> {
+    use sqlx::postgres::PgConnectOptions;
+    use std::str::FromStr;
+
+    // SQLite와 동일하게 CURRENT_TIMESTAMP가 UTC로 저장되도록 세션 시간대 고정
+    let connect_options = PgConnectOptions::from_str(database_url)?
+        .options([("timezone", "UTC")])
+        .statement_cache_capacity(options.statement_cache_capacity);
+    // 접속 URL에 비밀번호가 포함될 수 있으므로 호스트/DB 이름만 기록
+    tracing::info!(
+        "Connecting to database `{}` at `{}`",
+        connect_options.get_database().unwrap_or_default(),
+        connect_options.get_host()
+    );
 
-    Migrator::new(Path::new(&migration_dir))
+    sqlx::postgres::PgPoolOptions::new()
+        .max_connections(options.max_connections)
+        .connect_with(connect_options)
         .await
-        .context("Failed to initialize database migrator")?
+}
+
+#[cfg(feature = "postgres")]
+pub async fn create_pools(database_url: &str, options: &DbOptions) -> Result<DbPools, sqlx::Error> {
+    Ok(DbPools::single(create_pool(database_url, options).await?))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationSource {
+    Directory,
+    Embedded,
+}
+
+pub enum LoadedMigrator {
+    Directory(Migrator),
+    Embedded,
+}
+
+impl LoadedMigrator {
+    pub fn source(&self) -> MigrationSource {
+        match self {
+            LoadedMigrator::Directory(_) => MigrationSource::Directory,
+            LoadedMigrator::Embedded => MigrationSource::Embedded,
+        }
+    }
+}
+
+impl Deref for LoadedMigrator {
+    type Target = Migrator;
+
+    fn deref(&self) -> &Migrator {
+        match self {
+            LoadedMigrator::Directory(migrator) => migrator,
+            LoadedMigrator::Embedded => &EMBEDDED_MIGRATOR,
+        }
+    }
+}
+
+/// Loads the migrations from `migration_dir`, falling back to the migrations embedded
+/// at build time when the directory does not exist.
+pub async fn load_migrator(migration_dir: &str) -> anyhow::Result<LoadedMigrator> {
+    if !Path::new(migration_dir).is_dir() {
+        tracing::warn!(
+            "Migration directory `{}` not found, using the migrations embedded in the binary",
+            migration_dir
+        );
+        return Ok(LoadedMigrator::Embedded);
+    }
+
+    let migrator = Migrator::new(Path::new(migration_dir))
+        .await
+        .with_context(|| format!("Failed to load migrations from `{}`", migration_dir))?;
+    Ok(LoadedMigrator::Directory(migrator))
+}
+
+pub async fn migrate_db(pool: &DbPool, migration_dir: &str) -> anyhow::Result<()> {
+    let migrator = load_migrator(migration_dir).await?;
+    tracing::info!(
+        "Running database migrations from `{}` ({:?})",
+        migration_dir,
+        migrator.source()
+    );
+
+    replace_superseded_checksums(pool, &migrator).await?;
+    migrator
         .run(pool)
         .await
-        .map_err(|e| Box::new(e) as Box<dyn Error>)
+        .context("Failed to run database migrations")
+}
+
+/// Records the current checksum for applied migrations that were verifiably applied from an
+/// earlier revision listed in `SUPERSEDED_MIGRATIONS`, so that `Migrator::run` accepts them.
+/// Any other mismatch is left for `Migrator::run` to reject.
+async fn replace_superseded_checksums(pool: &DbPool, migrator: &Migrator) -> anyhow::Result<()> {
+    let mut conn = pool.acquire().await?;
+    let has_migrations_table: bool = sqlx::query_scalar(TABLE_EXISTS_QUERY)
+        .bind("_sqlx_migrations")
+        .fetch_one(&mut *conn)
+        .await?;
+    if !has_migrations_table {
+        return Ok(());
+    }
+
+    for applied in conn.list_applied_migrations().await? {
+        let Some(migration) = migrator
+            .iter()
+            .find(|m| m.version == applied.version && !m.migration_type.is_down_migration())
+        else {
+            continue;
+        };
+        if applied.checksum == migration.checksum
+            || !is_superseded_revision(migration, &applied.checksum)
+        {
+            continue;
+        }
+        tracing::warn!(
+            "Migration {} ({}) was applied from an earlier revision; recording the checksum of the current revision",
+            migration.version,
+            migration.description
+        );
+        sqlx::query(
+            "UPDATE _sqlx_migrations SET checksum = $1 WHERE version = $2 AND checksum = $3",
+        )
+        .bind(migration.checksum.as_ref())
+        .bind(applied.version)
+        .bind(applied.checksum.as_ref())
+        .execute(&mut *conn)
+        .await
+        .context("Failed to update migration checksum")?;
+    }
+    Ok(())
+}
+
+pub async fn revert_migrations(
+    pool: &DbPool,
+    migration_dir: &str,
+    target: i64,
+) -> anyhow::Result<()> {
+    tracing::info!(
+        "Reverting database migrations from `{}` down to version {}",
+        migration_dir,
+        target
+    );
+
+    load_migrator(migration_dir)
+        .await?
+        .undo(pool, target)
+        .await
+        .context("Failed to revert migrations")
+}
+
+// 애플리케이션이 동작하는 데 필요한 테이블 목록
+pub const REQUIRED_TABLES: &[&str] = &[
+    "user_type",
+    "admin_user",
+    "permission",
+    "menu_item",
+    "user_type_permission",
+    "user_type_menu",
+    "system_setting",
+    "job_run",
+    "job_lock",
+    "access_review",
+    "access_review_item",
+    "user_permission",
+    "user_permission_audit",
+];
+
+#[cfg(feature = "sqlite")]
+const TABLE_EXISTS_QUERY: &str =
+    "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = $1)";
+#[cfg(feature = "postgres")]
+const TABLE_EXISTS_QUERY: &str = "SELECT EXISTS (SELECT 1 FROM information_schema.tables WHERE table_schema = current_schema() AND table_name = $1)";
+
+#[derive(Debug)]
+pub struct SchemaReport {
+    pub source: MigrationSource,
+    pub applied: Vec<i64>,
+    pub pending: Vec<(i64, String)>,
+    pub checksum_mismatches: Vec<i64>,
+    pub unknown: Vec<i64>, // DB에는 적용되었으나 마이그레이션 디렉터리에 없는 버전
+    pub dirty: Option<i64>,
+    pub missing_tables: Vec<String>,
+}
+
+impl SchemaReport {
+    pub fn is_ok(&self) -> bool {
+        self.pending.is_empty()
+            && self.checksum_mismatches.is_empty()
+            && self.unknown.is_empty()
+            && self.dirty.is_none()
+            && self.missing_tables.is_empty()
+    }
+}
+
+/// Compares the migrations in `migration_dir` (or the embedded ones) with the ones recorded
+/// in the database and checks that every table the application relies on exists.
+pub async fn verify_schema(pool: &DbPool, migration_dir: &str) -> anyhow::Result<SchemaReport> {
+    let migrator = load_migrator(migration_dir).await?;
+    let mut conn = pool.acquire().await?;
+    let mut report = SchemaReport {
+        source: migrator.source(),
+        applied: Vec::new(),
+        pending: Vec::new(),
+        checksum_mismatches: Vec::new(),
+        unknown: Vec::new(),
+        dirty: None,
+        missing_tables: Vec::new(),
+    };
+
+    let has_migrations_table: bool = sqlx::query_scalar(TABLE_EXISTS_QUERY)
+        .bind("_sqlx_migrations")
+        .fetch_one(&mut *conn)
+        .await?;
+    let applied = if has_migrations_table {
+        report.dirty = conn.dirty_version().await?;
+        conn.list_applied_migrations().await?
+    } else {
+        Vec::new()
+    };
+
+    for migration in migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+    {
+        match applied.iter().find(|a| a.version == migration.version) {
+            Some(a)
+                if a.checksum != migration.checksum
+                    && !is_superseded_revision(migration, &a.checksum) =>
+            {
+                report.checksum_mismatches.push(migration.version)
+            }
+            Some(_) => report.applied.push(migration.version),
+            None => report
+                .pending
+                .push((migration.version, migration.description.to_string())),
+        }
+    }
+    report.unknown = applied
+        .iter()
+        .filter(|a| !migrator.version_exists(a.version))
+        .map(|a| a.version)
+        .collect();
+
+    for table in REQUIRED_TABLES {
+        let exists: bool = sqlx::query_scalar(TABLE_EXISTS_QUERY)
+            .bind(table)
+            .fetch_one(&mut *conn)
+            .await?;
+        if !exists {
+            report.missing_tables.push(table.to_string());
+        }
+    }
+
+    Ok(report)
 }
@@ -0,0 +1,156 @@
+use crate::models::{AccessReview, AccessReviewItem};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAccessReviewRequest {
+    #[schema(example = "2026 Q4 access recertification")]
+    #[validate(length(min = 1, max = 200, message = "Name must be 1-200 characters long"))]
+    pub name: String,
+    #[schema(example = json!([2, 3]))]
+    #[validate(length(min = 1, message = "At least one user type must be selected"))]
+    pub user_type_ids: Vec<i64>,
+    pub due_at: Option<DateTime<Utc>>, // 안내용 (기한이 지나도 자동 종료하지 않음)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessReviewDecision {
+    Keep,   // 현재 접근 권한 유지
+    Revoke, // 종료 시 계정 비활성화
+}
+
+impl AccessReviewDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Revoke => "revoke",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DecideAccessReviewItemRequest {
+    pub decision: AccessReviewDecision,
+    #[schema(example = "Still on the support rota")]
+    #[validate(length(max = 1000, message = "Comment must be at most 1000 characters long"))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessReviewResponse {
+    #[schema(example = 1)]
+    pub id: i64,
+    #[schema(example = "2026 Q4 access recertification")]
+    pub name: String,
+    #[schema(example = "open")]
+    pub status: String,
+    #[schema(example = json!(["Operator", "Auditor"]))]
+    pub user_types: Vec<String>,
+    #[schema(example = "admin")]
+    pub created_by: String,
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub closed_by: Option<String>,
+    pub closed_at: Option<DateTime<Utc>>,
+    #[schema(example = 12)]
+    pub total: usize,
+    #[schema(example = 3)]
+    pub pending: usize,
+    #[schema(example = 8)]
+    pub kept: usize,
+    #[schema(example = 1)]
+    pub revoked: usize,
+}
+
+impl AccessReviewResponse {
+    pub fn new(review: AccessReview, items: &[AccessReviewItem]) -> Self {
+        let count = |decision: &str| items.iter().filter(|i| i.decision == decision).count();
+        Self {
+            id: review.id,
+            name: review.name,
+            status: review.status,
+            user_types: serde_json::from_str(&review.user_types).unwrap_or_default(),
+            created_by: review.created_by,
+            due_at: review.due_at.map(|t| Utc.from_utc_datetime(&t)),
+            created_at: Utc.from_utc_datetime(&review.created_at),
+            closed_by: review.closed_by,
+            closed_at: review.closed_at.map(|t| Utc.from_utc_datetime(&t)),
+            total: items.len(),
+            pending: count("pending"),
+            kept: count("keep"),
+            revoked: count("revoke"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessReviewItemResponse {
+    #[schema(example = 5)]
+    pub id: i64,
+    #[schema(example = 7)]
+    pub user_id: Option<i64>, // 사용자가 삭제되면 null
+    #[schema(example = "jane")]
+    pub username: String,
+    #[schema(example = 2)]
+    pub user_type_id: i64,
+    #[schema(example = "Operator")]
+    pub user_type_name: String,
+    #[schema(example = json!(["menu:read", "user:read"]))]
+    pub permissions: Vec<String>, // 캠페인 시작 시점의 유효 권한
+    #[schema(example = "pending")]
+    pub decision: String,
+    pub comment: Option<String>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+impl From<AccessReviewItem> for AccessReviewItemResponse {
+    fn from(item: AccessReviewItem) -> Self {
+        Self {
+            id: item.id,
+            user_id: item.user_id,
+            username: item.username,
+            user_type_id: item.user_type_id,
+            user_type_name: item.user_type_name,
+            permissions: serde_json::from_str(&item.permissions).unwrap_or_default(),
+            decision: item.decision,
+            comment: item.comment,
+            decided_by: item.decided_by,
+            decided_at: item.decided_at.map(|t| Utc.from_utc_datetime(&t)),
+            applied_at: item.applied_at.map(|t| Utc.from_utc_datetime(&t)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessReviewDetailResponse {
+    pub review: AccessReviewResponse,
+    pub items: Vec<AccessReviewItemResponse>,
+}
+
+// 증적 내보내기 행 (캠페인 정보와 항목을 한 줄로)
+#[derive(Debug, Serialize, FromRow)]
+pub struct AccessReviewEvidence {
+    pub review_id: i64,
+    pub review_name: String,
+    pub review_status: String,
+    pub review_created_by: String,
+    pub review_created_at: NaiveDateTime,
+    pub review_closed_by: Option<String>,
+    pub review_closed_at: Option<NaiveDateTime>,
+    pub item_id: i64,
+    pub user_id: Option<i64>,
+    pub username: String,
+    pub user_type_name: String,
+    pub permissions: String, // JSON 배열
+    pub decision: String,
+    pub comment: Option<String>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<NaiveDateTime>,
+    pub applied_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,48 @@
+use crate::config::db::{MigrationSource, SchemaReport};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PendingMigration {
+    #[schema(example = 3)]
+    pub version: i64,
+    #[schema(example = "add audit log")]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MigrationStatusResponse {
+    #[schema(example = true)]
+    pub ok: bool,
+    #[schema(value_type = String, example = "directory")]
+    pub source: MigrationSource, // "directory" 또는 "embedded"
+    #[schema(example = json!([1, 2]))]
+    pub applied: Vec<i64>,
+    pub pending: Vec<PendingMigration>,
+    pub checksum_mismatches: Vec<i64>,
+    pub unknown: Vec<i64>, // DB에는 적용되었으나 마이그레이션 목록에 없는 버전
+    pub dirty: Option<i64>,
+    pub missing_tables: Vec<String>,
+}
+
+impl From<SchemaReport> for MigrationStatusResponse {
+    fn from(report: SchemaReport) -> Self {
+        MigrationStatusResponse {
+            ok: report.is_ok(),
+            source: report.source,
+            applied: report.applied,
+            pending: report
+                .pending
+                .into_iter()
+                .map(|(version, description)| PendingMigration {
+                    version,
+                    description,
+                })
+                .collect(),
+            checksum_mismatches: report.checksum_mismatches,
+            unknown: report.unknown,
+            dirty: report.dirty,
+            missing_tables: report.missing_tables,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantSource {
+    Role,     // 사용자 종류(user_type_permission)로 부여
+    Wildcard, // "*"에 포함
+    Direct,   // 사용자별 예외(user_permission)
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PermissionGrant {
+    pub source: GrantSource,
+    #[schema(example = "Granted to user type `Editor`")]
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EffectivePermission {
+    #[schema(example = "menu:write")]
+    pub code: String,
+    pub grants: Vec<PermissionGrant>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EffectivePermissionsResponse {
+    #[schema(example = 7)]
+    pub user_id: i64,
+    #[schema(example = "jane")]
+    pub username: String,
+    #[schema(example = 2)]
+    pub user_type_id: i64,
+    #[schema(example = "Editor")]
+    pub user_type_name: String,
+    #[schema(example = true)]
+    pub is_active: bool,
+    pub permissions: Vec<EffectivePermission>, // code 순 정렬
+    pub denied: Vec<EffectivePermission>,      // 거부 예외로 제외된 권한
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AuthzCheckRequest {
+    #[schema(example = 7)]
+    #[validate(range(min = 1, message = "Invalid user ID"))]
+    pub user_id: i64,
+    #[schema(example = "menu:write")]
+    #[validate(length(min = 1, message = "Permission code is required"))]
+    pub permission: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzDecision {
+    Allow,
+    Deny,
+}
+
+// 결정을 내린 규칙 (평가 순서대로)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzRule {
+    InactiveAccount,
+    DirectDeny,
+    Role,
+    DirectGrant,
+    Wildcard,
+    NoGrant,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthzCheckResponse {
+    #[schema(example = 7)]
+    pub user_id: i64,
+    #[schema(example = "jane")]
+    pub username: String,
+    #[schema(example = "menu:write")]
+    pub permission: String,
+    pub decision: AuthzDecision,
+    pub rule: AuthzRule,
+    #[schema(example = "Granted to user type `Editor`")]
+    pub explanation: String,
+}
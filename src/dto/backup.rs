@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackupInfo {
+    #[schema(example = "backup-20250101T030000123Z.db.gz")]
+    pub name: String,
+    #[schema(example = 40960)]
+    pub size_bytes: u64,
+    #[schema(example = true)]
+    pub compressed: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoreResponse {
+    pub restored: String,          // 복원에 사용된 백업 이름
+    pub safety_backup: BackupInfo, // 복원 직전 상태의 백업
+}
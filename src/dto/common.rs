@@ -25,15 +25,21 @@ impl ListQueryParams {
         (page - 1) * self.get_limit()
     }
 
-    // 정렬 문자열 생성 (SQL Injection 주의 - 컬럼명 화이트리스트 방식 권장)
-    pub fn get_order_by(&self, allowed_columns: &[&str]) -> String {
+    // 정렬 컬럼과 내림차순 여부 (허용되지 않은 값이면 id 오름차순)
+    pub fn get_sort<'a>(&'a self, allowed_columns: &[&str]) -> (&'a str, bool) {
         let sort_col = self.sort_by.as_deref().unwrap_or("id"); // 기본 정렬 컬럼
         let order_dir = self.order.as_deref().unwrap_or("asc");
 
         if allowed_columns.contains(&sort_col) && (order_dir == "asc" || order_dir == "desc") {
-            format!("{} {}", sort_col, order_dir.to_uppercase())
+            (sort_col, order_dir == "desc")
         } else {
-            "id ASC".to_string() // 기본값 또는 안전한 값
+            ("id", false) // 기본값 또는 안전한 값
         }
     }
+
+    // 정렬 문자열 생성 (SQL Injection 주의 - 컬럼명 화이트리스트 방식 권장)
+    pub fn get_order_by(&self, allowed_columns: &[&str]) -> String {
+        let (sort_col, descending) = self.get_sort(allowed_columns);
+        format!("{} {}", sort_col, if descending { "DESC" } else { "ASC" })
+    }
 }
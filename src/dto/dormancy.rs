@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DormancyStatus {
+    Warning,         // 경고 기간 경과
+    DeactivationDue, // 다음 실행에서 비활성화 대상
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DormantAccount {
+    #[schema(example = 7)]
+    pub user_id: i64,
+    #[schema(example = "jane")]
+    pub username: String,
+    #[schema(example = 2)]
+    pub user_type_id: i64,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub last_activity_at: DateTime<Utc>, // 마지막 로그인, 생성, 재활성화 중 가장 최근
+    #[schema(example = 75)]
+    pub inactive_days: i64,
+    pub status: DormancyStatus,
+    pub warned_at: Option<DateTime<Utc>>,
+    pub deactivates_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DormancyReport {
+    pub enabled: bool, // false면 자동 경고/비활성화 없이 보고만 함
+    #[schema(example = 60)]
+    pub warn_after_days: u64,
+    #[schema(example = 90)]
+    pub deactivate_after_days: u64,
+    #[schema(example = 7)]
+    pub warning_grace_days: u64,
+    pub accounts: Vec<DormantAccount>, // 미사용 기간이 긴 순
+}
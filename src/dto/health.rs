@@ -1,7 +1,33 @@
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Up,
+    Degraded, // 동작은 하지만 주의 필요 (예: 연결 풀 포화)
+    Down,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthCheck {
+    #[schema(example = "database")]
+    pub name: String,
+    pub status: HealthStatus,
+    #[schema(example = 3)]
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Object)]
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HealthCheckResponse {
-    pub status: String,
-    pub uptime: u64, // 예: 서버 동작 시간(초)
+    pub status: HealthStatus, // 가장 나쁜 개별 검사 결과
+    #[schema(example = 120)]
+    pub uptime: u64, // 서버 동작 시간(초)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<HealthCheck>,
 }
@@ -0,0 +1,22 @@
+use crate::models::JobRun;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JobResponse {
+    #[schema(example = "backup")]
+    pub name: String,
+    #[schema(example = "Takes a backup of the SQLite database")]
+    pub description: String,
+    #[schema(example = "0 0 3 * * *")]
+    pub schedule: Option<String>, // 없으면 수동 실행만 가능
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run: Option<JobRun>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct JobRunsQuery {
+    #[schema(example = 20)]
+    pub limit: Option<i64>, // 기본 20, 최대 100
+}
@@ -1,7 +1,17 @@
+pub mod access_review;
+pub mod admin;
 pub mod auth;
+pub mod authz;
+pub mod backup;
 pub mod common;
+pub mod dormancy;
 pub mod health;
+pub mod job;
 pub mod menu;
 pub mod permission;
+pub mod settings;
+pub mod setup;
+pub mod snapshot;
+pub mod transfer;
 pub mod user;
 pub mod user_type;
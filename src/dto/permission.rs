@@ -42,3 +42,61 @@ impl From<crate::models::Permission> for PermissionResponse {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionEffect {
+    Allow, // 사용자 종류에 없는 권한 추가
+    Deny,  // 사용자 종류(또는 "*")로 받은 권한 제외
+}
+
+impl PermissionEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetUserPermissionRequest {
+    pub effect: PermissionEffect,
+    #[schema(example = "Quarter-end reporting")]
+    #[validate(length(max = 500, message = "Reason must be at most 500 characters long"))]
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>, // 없으면 만료 없음
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserPermissionResponse {
+    #[schema(example = "report:export")]
+    pub permission_code: String,
+    #[schema(example = "allow")]
+    pub effect: String,
+    #[schema(example = "Quarter-end reporting")]
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    #[schema(example = "admin")]
+    pub granted_by: String,
+    pub granted_at: DateTime<Utc>,
+    #[schema(example = false)]
+    pub expired: bool, // 만료된 예외는 권한 계산에서 무시
+}
+
+impl UserPermissionResponse {
+    pub fn new(user_permission: crate::models::UserPermission, now: DateTime<Utc>) -> Self {
+        let expires_at = user_permission
+            .expires_at
+            .map(|t| Utc.from_utc_datetime(&t));
+        Self {
+            permission_code: user_permission.permission_code,
+            effect: user_permission.effect,
+            reason: user_permission.reason,
+            expired: expires_at.is_some_and(|t| t <= now),
+            expires_at,
+            granted_by: user_permission.granted_by,
+            granted_at: Utc.from_utc_datetime(&user_permission.granted_at),
+        }
+    }
+}
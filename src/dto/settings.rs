@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SettingResponse {
+    #[schema(example = "auth.jwt_expires_in_seconds")]
+    pub key: String,
+    #[schema(example = "Lifetime of newly issued access tokens")]
+    pub description: String,
+    #[schema(value_type = Object, example = json!({"type": "integer", "minimum": 60, "maximum": 604800}))]
+    pub schema: serde_json::Value, // 값의 JSON Schema
+    #[schema(value_type = Object, example = 3600)]
+    pub value: serde_json::Value, // 현재 적용 중인 값
+    #[schema(value_type = Object, example = 3600)]
+    pub default: serde_json::Value, // 설정 파일/기본값
+    pub overridden: bool, // DB에 저장된 값이 있는지 여부
+    pub updated_by: Option<i64>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSettingRequest {
+    #[schema(value_type = Object, example = 7200)]
+    pub value: serde_json::Value,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetupRequest {
+    #[schema(example = "k3J9...")]
+    #[validate(length(min = 1, message = "Setup token cannot be empty"))]
+    pub setup_token: String,
+    #[schema(example = "admin")]
+    #[validate(length(min = 3, message = "Username must be at least 3 characters long"))]
+    pub username: String,
+    #[schema(example = "Str0ngP@ssw0rd!")]
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SetupStatusResponse {
+    #[schema(example = true)]
+    pub setup_required: bool,
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+fn default_snapshot_version() -> u32 {
+    SNAPSHOT_VERSION
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// 환경 간 설정 이전용 스냅샷 (자동 증가 ID 대신 name/code/path 자연 키 사용)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ConfigSnapshot {
+    #[schema(example = 1)]
+    #[serde(default = "default_snapshot_version")]
+    pub version: u32,
+    #[validate(nested)]
+    #[serde(default)]
+    pub permissions: Vec<PermissionSnapshot>,
+    #[validate(nested)]
+    #[serde(default)]
+    pub user_types: Vec<UserTypeSnapshot>,
+    #[validate(nested)]
+    #[serde(default)]
+    pub menus: Vec<MenuSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, Validate, ToSchema)]
+pub struct PermissionSnapshot {
+    #[schema(example = "report:generate")]
+    #[validate(length(min = 1, message = "Code cannot be empty"))]
+    pub code: String,
+    #[schema(example = "Allows generating new reports")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UserTypeSnapshot {
+    #[schema(example = "ReportViewer")]
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+    #[schema(example = "Can view generated reports")]
+    pub description: Option<String>,
+    // user_type_permission 매핑 (permission.code 목록)
+    #[schema(example = json!(["report:generate"]))]
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    // user_type_menu 매핑 (menu_item.path 목록)
+    #[schema(example = json!(["/admin/reports"]))]
+    #[serde(default)]
+    pub menus: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, Validate, ToSchema)]
+pub struct MenuSnapshot {
+    #[schema(example = "/admin/reports")]
+    #[validate(length(min = 1, message = "Path cannot be empty"))]
+    pub path: String,
+    #[schema(example = "Reports")]
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+    #[schema(example = "icon-reports")]
+    pub icon: Option<String>,
+    #[schema(example = "/admin")]
+    pub parent_path: Option<String>, // 부모 메뉴의 path (null이면 최상위)
+    #[schema(example = 10)]
+    #[serde(default)]
+    pub display_order: i64,
+    #[schema(example = true)]
+    #[serde(default = "default_true")]
+    pub is_visible: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SnapshotApplyParams {
+    // true이면 스냅샷에 없는 역할/권한/메뉴를 삭제
+    #[param(example = false)]
+    #[serde(default)]
+    pub prune: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotEntity {
+    Permission,
+    UserType,
+    MenuItem,
+    UserTypePermission,
+    UserTypeMenu,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotChange {
+    pub entity: SnapshotEntity,
+    pub action: SnapshotAction,
+    #[schema(example = "report:generate")]
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotPlanResponse {
+    #[schema(example = false)]
+    pub applied: bool, // false이면 미리보기(plan)만 수행하고 롤백됨
+    pub changes: Vec<SnapshotChange>,
+}
@@ -0,0 +1,112 @@
+use crate::errors::AppError;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+// 가져오기/내보내기 파일 형식
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl TransferFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+            TransferFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "csv",
+            TransferFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+// 내보내기 쿼리 파라미터 (필터/정렬은 ListQueryParams를 그대로 사용)
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQueryParams {
+    #[param(example = "csv")]
+    #[serde(default)]
+    pub format: TransferFormat,
+}
+
+// 가져오기 쿼리 파라미터
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQueryParams {
+    // 생략 시 업로드 파일 확장자로 판단
+    #[param(example = "csv")]
+    pub format: Option<TransferFormat>,
+    // true이면 검증만 수행하고 변경 사항은 롤백
+    #[param(example = false)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowError {
+    #[schema(example = 3)]
+    pub row: usize, // 1부터 시작 (CSV 헤더 제외)
+    #[schema(example = "Input validation failed")]
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReport {
+    #[schema(example = false)]
+    pub dry_run: bool,
+    #[schema(example = 10)]
+    pub total: usize,
+    #[schema(example = 9)]
+    pub imported: usize,
+    #[schema(example = 1)]
+    pub failed: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            total: 0,
+            imported: 0,
+            failed: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    // 행 단위 처리 결과 기록
+    pub fn record(&mut self, row: usize, result: Result<(), AppError>) {
+        self.total += 1;
+        let err = match result {
+            Ok(()) => {
+                self.imported += 1;
+                return;
+            }
+            Err(err) => err,
+        };
+
+        self.failed += 1;
+        let (message, details) = match err {
+            AppError::ValidationError(ref e) => (
+                "Input validation failed".to_string(),
+                Some(serde_json::to_value(e.field_errors()).unwrap_or_default()),
+            ),
+            AppError::DatabaseError(sqlx::Error::Database(ref db_err)) => {
+                (db_err.message().to_string(), None)
+            }
+            _ => (err.to_string(), None),
+        };
+        self.errors.push(ImportRowError {
+            row,
+            message,
+            details,
+        });
+    }
+}
@@ -31,6 +31,13 @@ pub struct UpdateUserRequest {
     pub is_active: Option<bool>,
 }
 
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResetPasswordRequest {
+    #[schema(example = "N3wStr0ngP@ss!")]
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub password: String,
+}
+
 #[derive(Debug, Serialize, ToSchema, Clone)]
 pub struct UserResponse {
     #[schema(example = 101)]
@@ -44,6 +51,9 @@ pub struct UserResponse {
     pub last_login_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deactivated_at: Option<DateTime<Utc>>,
+    #[schema(example = "Dormant: no login for 90 days")]
+    pub deactivated_reason: Option<String>, // 자동 비활성화 사유
 }
 
 // 모델 -> 응답 DTO 변환
@@ -54,11 +64,11 @@ impl From<crate::models::AdminUser> for UserResponse {
             username: user.username,
             user_type_id: user.user_type_id,
             is_active: user.is_active,
-            last_login_at: user
-                .last_login_at
-                .map(|ndt| Utc.from_utc_datetime(&ndt.into())),
+            last_login_at: user.last_login_at.map(|ndt| Utc.from_utc_datetime(&ndt)),
             created_at: Utc.from_utc_datetime(&user.created_at),
             updated_at: Utc.from_utc_datetime(&user.updated_at),
+            deactivated_at: user.deactivated_at.map(|ndt| Utc.from_utc_datetime(&ndt)),
+            deactivated_reason: user.deactivated_reason,
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::{middleware::request_id::current_request_id, services::metrics::METRICS};
 use actix_web::{
     http::{header, StatusCode},
     HttpResponse, ResponseError,
@@ -14,6 +15,8 @@ pub struct ErrorResponse {
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     details: Option<serde_json::Value>, // Validation 에러 상세 정보
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>, // 로그와 대조할 수 있도록 X-Request-Id 값 포함
 }
 
 #[derive(Error, Debug)]
@@ -45,6 +48,18 @@ pub enum AppError {
     #[error("Conflict: {0}")] // 추가 (예: 중복 데이터)
     Conflict(String),
 
+    #[error("Service unavailable: {0}")] // 점검(유지보수) 모드 등
+    ServiceUnavailable(String),
+
+    #[error("Too many requests: {0}")] // 요청 제한 초과
+    TooManyRequests(String),
+
+    #[error("Payload too large: {0}")] // 요청 본문 크기 제한 초과
+    PayloadTooLarge(String),
+
+    #[error("Unsupported media type: {0}")] // 잘못된 Content-Type
+    UnsupportedMediaType(String),
+
     #[error("Internal server error")]
     InternalServerError(#[from] anyhow::Error), // anyhow::Error 처리 추가
 }
@@ -66,6 +81,38 @@ impl AppError {
     pub fn conflict(message: &str) -> Self {
         AppError::Conflict(message.to_string())
     }
+    pub fn service_unavailable(message: &str) -> Self {
+        AppError::ServiceUnavailable(message.to_string())
+    }
+    pub fn too_many_requests(message: &str) -> Self {
+        AppError::TooManyRequests(message.to_string())
+    }
+    pub fn payload_too_large(message: &str) -> Self {
+        AppError::PayloadTooLarge(message.to_string())
+    }
+    pub fn unsupported_media_type(message: &str) -> Self {
+        AppError::UnsupportedMediaType(message.to_string())
+    }
+
+    // 메트릭 라벨용 variant 이름
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            AppError::DatabaseError(_) => "DatabaseError",
+            AppError::PasswordHashingError(_) => "PasswordHashingError",
+            AppError::JwtError(_) => "JwtError",
+            AppError::ValidationError(_) => "ValidationError",
+            AppError::NotFound(_) => "NotFound",
+            AppError::BadRequest(_) => "BadRequest",
+            AppError::Unauthorized(_) => "Unauthorized",
+            AppError::Forbidden(_) => "Forbidden",
+            AppError::Conflict(_) => "Conflict",
+            AppError::ServiceUnavailable(_) => "ServiceUnavailable",
+            AppError::TooManyRequests(_) => "TooManyRequests",
+            AppError::PayloadTooLarge(_) => "PayloadTooLarge",
+            AppError::UnsupportedMediaType(_) => "UnsupportedMediaType",
+            AppError::InternalServerError(_) => "InternalServerError",
+        }
+    }
 }
 
 impl ResponseError for AppError {
@@ -94,12 +141,20 @@ impl ResponseError for AppError {
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
         let status = self.status_code();
+        METRICS
+            .errors
+            .with_label_values(&[self.variant_name()])
+            .inc();
         let (message, details) = match self {
             AppError::InternalServerError(ref e) => {
                 tracing::error!("Internal Server Error: {:?}", e); // 상세 에러 로깅 (tracing 사용)
@@ -135,6 +190,7 @@ impl ResponseError for AppError {
             error: status.canonical_reason().unwrap_or("Error").to_string(),
             message,
             details,
+            request_id: current_request_id(),
         };
 
         let mut response = HttpResponse::build(status).json(error_response);
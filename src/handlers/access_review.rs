@@ -0,0 +1,121 @@
+use crate::{
+    config::db::DbPools,
+    dto::{
+        access_review::{CreateAccessReviewRequest, DecideAccessReviewItemRequest},
+        transfer::ExportQueryParams,
+    },
+    errors::AppError,
+    handlers::export_response,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{
+        AccessReviewRepository, PermissionRepository, UserRepository, UserTypeRepository,
+    },
+    services::access_review,
+};
+use actix_web::{get, post, put, web, HttpResponse, Responder, Scope};
+
+pub fn route() -> Scope {
+    web::scope("/access-reviews")
+        .service(post_access_review)
+        .service(get_access_reviews)
+        .service(get_access_review)
+        .service(put_access_review_item)
+        .service(post_access_review_close)
+        .service(get_access_review_export)
+}
+
+#[post("")]
+async fn post_access_review(
+    reviews: web::Data<dyn AccessReviewRepository>,
+    users: web::Data<dyn UserRepository>,
+    user_types: web::Data<dyn UserTypeRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    req: web::Json<CreateAccessReviewRequest>,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let response = access_review::start_review(
+        reviews.get_ref(),
+        users.get_ref(),
+        user_types.get_ref(),
+        permissions.get_ref(),
+        &user,
+        req.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+#[get("")]
+async fn get_access_reviews(
+    reviews: web::Data<dyn AccessReviewRepository>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let response = access_review::list_reviews(reviews.get_ref()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/{id}")]
+async fn get_access_review(
+    reviews: web::Data<dyn AccessReviewRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let response = access_review::get_review(reviews.get_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[put("/{id}/items/{item_id}")]
+async fn put_access_review_item(
+    reviews: web::Data<dyn AccessReviewRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<(i64, i64)>,
+    req: web::Json<DecideAccessReviewItemRequest>,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let (id, item_id) = path.into_inner();
+    let response =
+        access_review::decide(reviews.get_ref(), &user, id, item_id, req.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[post("/{id}/close")]
+async fn post_access_review_close(
+    reviews: web::Data<dyn AccessReviewRepository>,
+    users: web::Data<dyn UserRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let response = access_review::close_review(
+        reviews.get_ref(),
+        users.get_ref(),
+        permissions.get_ref(),
+        &user,
+        path.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/{id}/export")]
+async fn get_access_review_export(
+    pools: web::Data<DbPools>,
+    reviews: web::Data<dyn AccessReviewRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+    export_params: web::Query<ExportQueryParams>,
+) -> Result<impl Responder, AppError> {
+    access_review::ensure_reviewer(&user)?;
+    let id = path.into_inner();
+    let format = export_params.format;
+    let stream = access_review::export_review(&pools.read, reviews.get_ref(), id, format).await?;
+    Ok(export_response(
+        &format!("access-review-{}", id),
+        format,
+        stream,
+    ))
+}
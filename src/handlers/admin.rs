@@ -0,0 +1,203 @@
+#[cfg(feature = "sqlite")]
+use crate::services::{backup, maintenance::MaintenanceState, setup::SetupState};
+use crate::{
+    config::{db::DbPools, env::Env},
+    dto::{job::JobRunsQuery, settings::UpdateSettingRequest, user::UserResponse},
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{SettingRepository, UserRepository},
+    services::{
+        admin, dormancy, lifecycle::TaskSupervisor, scheduler::Scheduler, settings::Settings,
+    },
+};
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, Scope};
+use chrono::Utc;
+
+pub fn route() -> Scope {
+    let scope = web::scope("/admin")
+        .service(get_migrations)
+        .service(get_settings)
+        .service(get_setting)
+        .service(put_setting)
+        .service(delete_setting)
+        .service(get_jobs)
+        .service(post_job_run)
+        .service(get_job_runs)
+        .service(get_dormant_accounts)
+        .service(post_user_reactivate);
+    // 백업/복원은 SQLite 백엔드에서만 지원
+    #[cfg(feature = "sqlite")]
+    let scope = scope
+        .service(get_backups)
+        .service(post_backup)
+        .service(post_backup_restore);
+    scope
+}
+
+#[get("/migrations")]
+async fn get_migrations(
+    pools: web::Data<DbPools>,
+    config: web::Data<Env>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = admin::get_migration_status(&pools.read, &config).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/settings")]
+async fn get_settings(
+    settings: web::Data<Settings>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    Ok(HttpResponse::Ok().json(settings.list()))
+}
+
+#[get("/settings/{key}")]
+async fn get_setting(
+    settings: web::Data<Settings>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = settings.get_setting(&path)?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[put("/settings/{key}")]
+async fn put_setting(
+    settings: web::Data<Settings>,
+    repo: web::Data<dyn SettingRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<UpdateSettingRequest>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = settings
+        .update(repo.get_ref(), &path, req.into_inner().value, user.id)
+        .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// 기본값으로 되돌리기
+#[delete("/settings/{key}")]
+async fn delete_setting(
+    settings: web::Data<Settings>,
+    repo: web::Data<dyn SettingRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = settings.reset(repo.get_ref(), &path).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/jobs")]
+async fn get_jobs(
+    scheduler: web::Data<Scheduler>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = scheduler.list().await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// 백그라운드에서 실행하고 running 상태의 실행 기록을 바로 반환
+#[post("/jobs/{name}/run")]
+async fn post_job_run(
+    scheduler: web::Data<Scheduler>,
+    supervisor: web::Data<TaskSupervisor>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = scheduler
+        .into_inner()
+        .trigger(&supervisor, &path, user.id)
+        .await?;
+    Ok(HttpResponse::Accepted().json(response))
+}
+
+#[get("/jobs/{name}/runs")]
+async fn get_job_runs(
+    scheduler: web::Data<Scheduler>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    query: web::Query<JobRunsQuery>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = scheduler.runs(&path, query.limit).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// 장기 미사용으로 경고/비활성화 대상인 계정 (접근 검토용)
+#[get("/dormant-accounts")]
+async fn get_dormant_accounts(
+    repo: web::Data<dyn UserRepository>,
+    config: web::Data<Env>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response =
+        dormancy::report(repo.get_ref(), &config.dormancy, Utc::now().naive_utc()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[post("/users/{id}/reactivate")]
+async fn post_user_reactivate(
+    repo: web::Data<dyn UserRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let reactivated = dormancy::reactivate(repo.get_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(UserResponse::from(reactivated)))
+}
+
+#[cfg(feature = "sqlite")]
+#[get("/backups")]
+async fn get_backups(
+    config: web::Data<Env>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = backup::list_backups(&config).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[cfg(feature = "sqlite")]
+#[post("/backups")]
+async fn post_backup(
+    pools: web::Data<DbPools>,
+    config: web::Data<Env>,
+    user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = backup::create_backup(&pools.write, &config).await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+#[cfg(feature = "sqlite")]
+#[post("/backups/{name}/restore")]
+async fn post_backup_restore(
+    pools: web::Data<DbPools>,
+    config: web::Data<Env>,
+    maintenance: web::Data<MaintenanceState>,
+    settings: web::Data<Settings>,
+    setup_state: web::Data<SetupState>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = backup::restore_backup(
+        &pools.write,
+        &config,
+        &maintenance,
+        &settings,
+        &setup_state,
+        &path,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
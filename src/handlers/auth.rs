@@ -1,6 +1,12 @@
 use crate::{
-    dto::auth::LoginRequest, errors::AppError,
-    middleware::auth::authenticated_user::AuthenticatedUser, services::auth,
+    dto::auth::LoginRequest,
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{UserRepository, UserTypeRepository},
+    services::{
+        auth::{self, LoginAttempts},
+        settings::Settings,
+    },
 };
 use actix_web::{get, post, web, HttpResponse, Responder, Scope};
 
@@ -12,19 +18,28 @@ pub fn route() -> Scope {
 
 #[post("/login")]
 async fn post_auth_login(
-    pool: web::Data<sqlx::SqlitePool>,
+    users: web::Data<dyn UserRepository>,
     config: web::Data<crate::config::env::Env>,
+    settings: web::Data<Settings>,
+    attempts: web::Data<LoginAttempts>,
     req: web::Json<LoginRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = auth::login(pool, config, req).await?;
+    let response = auth::login(
+        users.get_ref(),
+        &config,
+        &settings,
+        &attempts,
+        req.into_inner(),
+    )
+    .await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/me")]
 async fn get_auth_me(
-    pool: web::Data<sqlx::SqlitePool>,
+    user_types: web::Data<dyn UserTypeRepository>,
     current_user: AuthenticatedUser,
 ) -> Result<impl Responder, AppError> {
-    let response = auth::get_current_user(pool, current_user).await?;
+    let response = auth::get_current_user(user_types.get_ref(), current_user).await?;
     Ok(HttpResponse::Ok().json(response))
 }
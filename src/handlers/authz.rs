@@ -0,0 +1,31 @@
+use crate::{
+    dto::authz::AuthzCheckRequest,
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{PermissionRepository, UserRepository, UserTypeRepository},
+    services::authz,
+};
+use actix_web::{post, web, HttpResponse, Responder, Scope};
+
+pub fn route() -> Scope {
+    web::scope("/authz").service(post_authz_check)
+}
+
+#[post("/check")]
+async fn post_authz_check(
+    users: web::Data<dyn UserRepository>,
+    user_types: web::Data<dyn UserTypeRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    req: web::Json<AuthzCheckRequest>,
+) -> Result<impl Responder, AppError> {
+    authz::ensure_can_inspect(&user, req.user_id)?;
+    let response = authz::check(
+        users.get_ref(),
+        user_types.get_ref(),
+        permissions.get_ref(),
+        req.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
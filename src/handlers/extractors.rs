@@ -0,0 +1,53 @@
+//! Extractor configuration: body size limits and error handlers that turn actix's plain-text
+//! extractor errors into [`AppError`] responses (JSON body, request ID, error metrics).
+
+use crate::errors::AppError;
+use actix_web::{
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    web, Error, HttpRequest,
+};
+
+pub fn json_config(limit_bytes: usize) -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(limit_bytes)
+        .error_handler(json_error)
+}
+
+pub fn path_config() -> web::PathConfig {
+    web::PathConfig::default().error_handler(path_error)
+}
+
+pub fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default().error_handler(query_error)
+}
+
+fn json_error(err: JsonPayloadError, _req: &HttpRequest) -> Error {
+    let error = match &err {
+        JsonPayloadError::OverflowKnownLength { limit, .. }
+        | JsonPayloadError::Overflow { limit } => {
+            AppError::payload_too_large(&format!("JSON body must not exceed {} bytes", limit))
+        }
+        JsonPayloadError::ContentType => {
+            AppError::unsupported_media_type("Expected Content-Type: application/json")
+        }
+        JsonPayloadError::Deserialize(e) => {
+            AppError::BadRequest(format!("Invalid JSON body: {}", e))
+        }
+        _ => AppError::BadRequest(err.to_string()),
+    };
+    error.into()
+}
+
+fn path_error(err: PathError, _req: &HttpRequest) -> Error {
+    let PathError::Deserialize(e) = &err else {
+        return AppError::BadRequest(err.to_string()).into();
+    };
+    AppError::BadRequest(format!("Invalid path parameter: {}", e)).into()
+}
+
+fn query_error(err: QueryPayloadError, _req: &HttpRequest) -> Error {
+    let QueryPayloadError::Deserialize(e) = &err else {
+        return AppError::BadRequest(err.to_string()).into();
+    };
+    AppError::BadRequest(format!("Invalid query string: {}", e)).into()
+}
@@ -1,11 +1,19 @@
-use crate::services::health;
+use crate::{
+    config::{db::DbPools, env::Env},
+    dto::health::{HealthCheckResponse, HealthStatus},
+    services::{health, lifecycle::TaskSupervisor, maintenance::MaintenanceState},
+};
 use actix_web::{get, web, HttpResponse, Responder, Scope};
 use utoipa;
 
 pub fn route() -> Scope {
-    web::scope("/health").service(get_health)
+    web::scope("/health")
+        .service(get_health)
+        .service(get_health_live)
+        .service(get_health_ready)
 }
 
+// 기존 모니터링 호환용 (동작 시간만 반환)
 #[utoipa::path(
     get,
     path = "/api/v1/health",
@@ -19,3 +27,40 @@ async fn get_health() -> impl Responder {
     let response = health::get_server_runtime();
     HttpResponse::Ok().json(response)
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/live",
+    responses(
+        (status = 200, description = "Process is running", body = HealthCheckResponse)
+    ),
+    tag = "Health"
+)]
+#[get("/live")]
+async fn get_health_live() -> impl Responder {
+    HttpResponse::Ok().json(health::liveness())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/ready",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = HealthCheckResponse),
+        (status = 503, description = "A dependency is unavailable", body = HealthCheckResponse)
+    ),
+    tag = "Health"
+)]
+#[get("/ready")]
+async fn get_health_ready(
+    pools: web::Data<DbPools>,
+    config: web::Data<Env>,
+    maintenance: web::Data<MaintenanceState>,
+    supervisor: web::Data<TaskSupervisor>,
+) -> impl Responder {
+    let response = health::readiness(&pools, &config, &maintenance, &supervisor).await;
+    if response.status == HealthStatus::Down {
+        HttpResponse::ServiceUnavailable().json(response)
+    } else {
+        HttpResponse::Ok().json(response)
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse};
+
+// HTTP 리다이렉트 전용 서버: 모든 요청을 같은 호스트의 HTTPS 주소로 보냄
+pub fn configure(cfg: &mut web::ServiceConfig, https_port: u16) {
+    cfg.app_data(web::Data::new(https_port))
+        .default_service(web::to(redirect));
+}
+
+async fn redirect(req: HttpRequest, https_port: web::Data<u16>) -> HttpResponse {
+    let connection = req.connection_info();
+    let host = connection.host();
+    // IPv6 주소 ([::1]:80)를 고려해 마지막 `:` 뒤가 숫자일 때만 포트로 간주
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    let authority = match **https_port {
+        443 => host.to_string(),
+        port => format!("{}:{}", host, port),
+    };
+    let path = req
+        .uri()
+        .path_and_query()
+        .map_or("/", |p| p.as_str())
+        .to_string();
+
+    // 308: 메서드와 본문을 유지한 채 이동
+    HttpResponse::PermanentRedirect()
+        .insert_header((header::LOCATION, format!("https://{}{}", authority, path)))
+        .finish()
+}
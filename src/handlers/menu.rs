@@ -1,24 +1,36 @@
 use crate::{
-    dto::menu::CreateMenuRequest, errors::AppError,
-    middleware::auth::authenticated_user::AuthenticatedUser, services::menu,
+    config::db::DbPools,
+    dto::{
+        menu::CreateMenuRequest,
+        transfer::{ExportQueryParams, ImportQueryParams},
+    },
+    errors::AppError,
+    handlers::export_response,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::MenuRepository,
+    services::{admin, menu, transfer},
 };
+use actix_multipart::Multipart;
 use actix_web::{get, post, web, HttpResponse, Responder, Scope};
-use sqlx::SqlitePool;
 use utoipa;
 
 pub fn route() -> Scope {
-    web::scope("/menu").service(post_menu).service(get_menu) // 계층 구조 반환 API
+    web::scope("/menu")
+        .service(post_menu)
+        .service(get_menu) // 계층 구조 반환 API
+        .service(get_menu_export)
+        .service(post_menu_import)
 }
 
 /// Create a Menu Item
 #[utoipa::path(tag = "Menu Management")]
 #[post("")]
 async fn post_menu(
-    pool: web::Data<SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn MenuRepository>,
+    _user: AuthenticatedUser,
     req: web::Json<CreateMenuRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = menu::create_menu(pool, user, req).await?;
+    let response = menu::create_menu(repo.get_ref(), req.into_inner()).await?;
     Ok(HttpResponse::Created().json(response))
 }
 
@@ -27,9 +39,38 @@ async fn post_menu(
 #[utoipa::path(tag = "Menu Management")]
 #[get("")]
 async fn get_menu(
-    pool: web::Data<SqlitePool>,
+    repo: web::Data<dyn MenuRepository>,
     _user: AuthenticatedUser,
 ) -> Result<impl Responder, AppError> {
-    let response = menu::get_menu_array(pool, _user).await?;
+    let response = menu::get_menu_array(repo.get_ref()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Export Menu Items as a flat CSV/JSONL file
+#[utoipa::path(tag = "Menu Management", params(ExportQueryParams))]
+#[get("/export")]
+async fn get_menu_export(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    export_params: web::Query<ExportQueryParams>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let format = export_params.format;
+    let stream = menu::export_menu_array(&pools.read, &export_params).await?;
+    Ok(export_response("menus", format, stream))
+}
+
+/// Import Menu Items from a CSV/JSONL upload
+#[utoipa::path(tag = "Menu Management", params(ImportQueryParams))]
+#[post("/import")]
+async fn post_menu_import(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    import_params: web::Query<ImportQueryParams>,
+    payload: Multipart,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let file = transfer::read_upload(payload).await?;
+    let response = menu::import_menu_array(&pools.write, &import_params, &file).await?;
     Ok(HttpResponse::Ok().json(response))
 }
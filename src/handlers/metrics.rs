@@ -0,0 +1,36 @@
+use crate::{
+    config::{db::DbPools, env::Env},
+    errors::AppError,
+    services::metrics::METRICS,
+    util::constant_time_eq,
+};
+use actix_web::{get, http::header::AUTHORIZATION, web, HttpRequest, HttpResponse};
+
+// /api/v1 밖의 최상위 경로에 등록 (Prometheus 기본 경로)
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_metrics);
+}
+
+#[get("/metrics")]
+async fn get_metrics(
+    req: HttpRequest,
+    pools: web::Data<DbPools>,
+    config: web::Data<Env>,
+) -> Result<HttpResponse, AppError> {
+    // JWT가 아닌 별도 토큰으로 보호 (내부 전용 주소에서 토큰 없이 제공하는 경우 제외)
+    if let Some(token) = &config.metrics.token {
+        let provided = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        if !constant_time_eq(provided, token) {
+            return Err(AppError::unauthorized("Invalid metrics token"));
+        }
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(METRICS.render(&pools)))
+}
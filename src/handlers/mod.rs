@@ -1,21 +1,61 @@
+pub mod access_review;
+pub mod admin;
 pub mod auth;
+pub mod authz;
+pub mod extractors;
 pub mod health;
+pub mod https_redirect;
 pub mod menu;
+pub mod metrics;
 pub mod permission;
+pub mod setup;
+pub mod snapshot;
 pub mod user;
 pub mod user_type;
 
-use crate::handlers;
-use actix_web::web;
+use crate::{
+    config::env::SecurityConfig, dto::transfer::TransferFormat, errors::AppError, handlers,
+};
+use actix_web::{
+    http::header::ContentDisposition,
+    web::{self, Bytes},
+    HttpResponse,
+};
+use futures_util::Stream;
 
-pub fn configure(cfg: &mut web::ServiceConfig) {
+pub fn configure(cfg: &mut web::ServiceConfig, security: &SecurityConfig) {
+    cfg.app_data(extractors::json_config(security.json_limit_bytes))
+        .app_data(extractors::path_config())
+        .app_data(extractors::query_config());
     cfg.service(
         web::scope("/api/v1")
+            .service(handlers::access_review::route())
+            .service(handlers::admin::route())
             .service(handlers::auth::route())
+            .service(handlers::authz::route())
             .service(handlers::health::route())
             .service(handlers::menu::route())
             .service(handlers::permission::route())
+            .service(handlers::setup::route())
+            .service(handlers::snapshot::route(
+                security.snapshot_json_limit_bytes,
+            ))
             .service(handlers::user::route())
             .service(handlers::user_type::route()),
     );
 }
+
+// 내보내기 스트림을 첨부 파일 응답으로 변환
+fn export_response<S>(name: &str, format: TransferFormat, stream: S) -> HttpResponse
+where
+    S: Stream<Item = Result<Bytes, AppError>> + 'static,
+{
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition::attachment(format!(
+            "{}.{}",
+            name,
+            format.extension()
+        )))
+        .streaming(stream)
+}
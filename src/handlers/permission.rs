@@ -1,44 +1,83 @@
 use crate::{
-    dto::{common::ListQueryParams, permission::CreatePermissionRequest},
+    config::db::DbPools,
+    dto::{
+        common::ListQueryParams,
+        permission::CreatePermissionRequest,
+        transfer::{ExportQueryParams, ImportQueryParams},
+    },
     errors::AppError,
+    handlers::export_response,
     middleware::auth::authenticated_user::AuthenticatedUser,
-    services::permission,
+    repositories::PermissionRepository,
+    services::{admin, permission, transfer},
 };
+use actix_multipart::Multipart;
 use actix_web::{get, post, web, HttpResponse, Responder, Scope};
 
 pub fn route() -> Scope {
     web::scope("/permission")
         .service(post_permission)
         .service(get_permission)
+        .service(get_permission_export)
+        .service(post_permission_import)
         .service(get_permission_by_id)
 }
 
 #[post("")]
 async fn post_permission(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn PermissionRepository>,
+    _user: AuthenticatedUser,
     req: web::Json<CreatePermissionRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = permission::create_permission(pool, user, req).await?;
+    let response = permission::create_permission(repo.get_ref(), req.into_inner()).await?;
     Ok(HttpResponse::Created().json(response))
 }
 
 #[get("")]
 async fn get_permission(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn PermissionRepository>,
+    _user: AuthenticatedUser,
     query: web::Query<ListQueryParams>,
 ) -> Result<impl Responder, AppError> {
-    let response = permission::get_permissions(pool, user, query).await?;
+    let response = permission::get_permissions(repo.get_ref(), &query).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Export Permissions as a flat CSV/JSONL file
+#[utoipa::path(tag = "Permission Management", params(ExportQueryParams))]
+#[get("/export")]
+async fn get_permission_export(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    export_params: web::Query<ExportQueryParams>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let format = export_params.format;
+    let stream = permission::export_permissions(&pools.read, &export_params).await?;
+    Ok(export_response("permissions", format, stream))
+}
+
+/// Import Permissions from a CSV/JSONL upload
+#[utoipa::path(tag = "Permission Management", params(ImportQueryParams))]
+#[post("/import")]
+async fn post_permission_import(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    import_params: web::Query<ImportQueryParams>,
+    payload: Multipart,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let file = transfer::read_upload(payload).await?;
+    let response = permission::import_permissions(&pools.write, &import_params, &file).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/{id}")]
 async fn get_permission_by_id(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
-    path: web::Path<i32>,
+    repo: web::Data<dyn PermissionRepository>,
+    _user: AuthenticatedUser,
+    path: web::Path<i64>,
 ) -> Result<impl Responder, AppError> {
-    let response = permission::get_permission_by_id(pool, user, path).await?;
+    let response = permission::get_permission_by_id(repo.get_ref(), path.into_inner()).await?;
     Ok(HttpResponse::Ok().json(response))
 }
@@ -0,0 +1,21 @@
+use crate::{config::db::DbPools, dto::setup::SetupRequest, errors::AppError, services::setup};
+use actix_web::{get, post, web, HttpResponse, Responder, Scope};
+
+pub fn route() -> Scope {
+    web::scope("/setup").service(get_setup).service(post_setup)
+}
+
+#[get("")]
+async fn get_setup(state: web::Data<setup::SetupState>) -> impl Responder {
+    HttpResponse::Ok().json(setup::get_setup_status(&state))
+}
+
+#[post("")]
+async fn post_setup(
+    pools: web::Data<DbPools>,
+    state: web::Data<setup::SetupState>,
+    req: web::Json<SetupRequest>,
+) -> Result<impl Responder, AppError> {
+    let response = setup::complete_setup(&pools.write, &state, req.into_inner()).await?;
+    Ok(HttpResponse::Created().json(response))
+}
@@ -0,0 +1,51 @@
+use crate::{
+    config::db::DbPools,
+    dto::snapshot::{ConfigSnapshot, SnapshotApplyParams},
+    errors::AppError,
+    handlers,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    services::{admin, snapshot},
+};
+use actix_web::{get, post, web, HttpResponse, Responder, Scope};
+
+pub fn route(json_limit_bytes: usize) -> Scope {
+    web::scope("/snapshot")
+        .app_data(handlers::extractors::json_config(json_limit_bytes))
+        .service(get_snapshot)
+        .service(post_snapshot_plan)
+        .service(post_snapshot_apply)
+}
+
+#[get("")]
+async fn get_snapshot(
+    pools: web::Data<DbPools>,
+    _user: AuthenticatedUser,
+) -> Result<impl Responder, AppError> {
+    let response = snapshot::export_snapshot(&pools.read).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[post("/plan")]
+async fn post_snapshot_plan(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    params: web::Query<SnapshotApplyParams>,
+    req: web::Json<ConfigSnapshot>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    // 계획도 트랜잭션 안에서 실제로 실행한 뒤 롤백하므로 쓰기 풀 사용 (읽기 풀은 query_only)
+    let response = snapshot::plan_snapshot(&pools.write, &params, &req).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[post("/apply")]
+async fn post_snapshot_apply(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    params: web::Query<SnapshotApplyParams>,
+    req: web::Json<ConfigSnapshot>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let response = snapshot::apply_snapshot(&pools.write, &user.username, &params, &req).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
@@ -1,44 +1,174 @@
 use crate::{
-    dto::{common::ListQueryParams, user::CreateUserRequest},
+    config::db::DbPools,
+    dto::{
+        common::ListQueryParams,
+        permission::SetUserPermissionRequest,
+        transfer::{ExportQueryParams, ImportQueryParams},
+        user::CreateUserRequest,
+    },
     errors::AppError,
+    handlers::export_response,
     middleware::auth::authenticated_user::AuthenticatedUser,
-    services::user,
+    repositories::{PermissionRepository, UserRepository, UserTypeRepository},
+    services::{admin, authz, permission, transfer, user},
 };
-use actix_web::{get, post, web, HttpResponse, Responder, Scope};
+use actix_multipart::Multipart;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, Scope};
 
 pub fn route() -> Scope {
     web::scope("/user")
         .service(post_user)
         .service(get_user)
+        .service(get_user_export)
+        .service(post_user_import)
         .service(get_user_by_id)
+        .service(get_user_effective_permissions)
+        .service(get_user_permissions)
+        .service(get_user_permission_history)
+        .service(put_user_permission)
+        .service(delete_user_permission)
 }
 
 #[post("")]
 async fn post_user(
-    pool: web::Data<sqlx::SqlitePool>,
+    repo: web::Data<dyn UserRepository>,
     // user: AuthenticatedUser,
     req: web::Json<CreateUserRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = user::create_user(pool,  req).await?;
+    let response = user::create_user(repo.get_ref(), req.into_inner()).await?;
     Ok(HttpResponse::Created().json(response))
 }
 
 #[get("")]
 async fn get_user(
-    pool: web::Data<sqlx::SqlitePool>,
+    repo: web::Data<dyn UserRepository>,
+    _user: AuthenticatedUser,
+    query_params: web::Query<ListQueryParams>,
+) -> Result<impl Responder, AppError> {
+    let response = user::get_user_array(repo.get_ref(), &query_params).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Export Users as a flat CSV/JSONL file
+#[utoipa::path(tag = "User Management", params(ListQueryParams, ExportQueryParams))]
+#[get("/export")]
+async fn get_user_export(
+    pools: web::Data<DbPools>,
     user: AuthenticatedUser,
     query_params: web::Query<ListQueryParams>,
+    export_params: web::Query<ExportQueryParams>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let format = export_params.format;
+    let stream = user::export_user_array(&pools.read, &query_params, &export_params).await?;
+    Ok(export_response("users", format, stream))
+}
+
+/// Import Users from a CSV/JSONL upload
+#[utoipa::path(tag = "User Management", params(ImportQueryParams))]
+#[post("/import")]
+async fn post_user_import(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    import_params: web::Query<ImportQueryParams>,
+    payload: Multipart,
 ) -> Result<impl Responder, AppError> {
-    let response = user::get_user_array(pool, user, query_params).await?;
+    admin::ensure_super_admin(&user)?;
+    let file = transfer::read_upload(payload).await?;
+    let response = user::import_user_array(&pools.write, &import_params, &file).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/{id}")]
 async fn get_user_by_id(
-    pool: web::Data<sqlx::SqlitePool>,
+    repo: web::Data<dyn UserRepository>,
+    _user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let response = user::get_user_by_id(repo.get_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/{id}/effective-permissions")]
+async fn get_user_effective_permissions(
+    users: web::Data<dyn UserRepository>,
+    user_types: web::Data<dyn UserTypeRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
     user: AuthenticatedUser,
     path: web::Path<i64>,
 ) -> Result<impl Responder, AppError> {
-    let response = user::get_user_by_id(pool, user, path).await?;
+    let id = path.into_inner();
+    authz::ensure_can_inspect(&user, id)?;
+    let response = authz::effective_permissions(
+        users.get_ref(),
+        user_types.get_ref(),
+        permissions.get_ref(),
+        id,
+    )
+    .await?;
     Ok(HttpResponse::Ok().json(response))
 }
+
+#[get("/{id}/permissions")]
+async fn get_user_permissions(
+    users: web::Data<dyn UserRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    authz::ensure_can_inspect(&user, id)?;
+    let response =
+        permission::list_user_permissions(users.get_ref(), permissions.get_ref(), id).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[get("/{id}/permissions/history")]
+async fn get_user_permission_history(
+    users: web::Data<dyn UserRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<i64>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    authz::ensure_can_inspect(&user, id)?;
+    let response =
+        permission::user_permission_history(users.get_ref(), permissions.get_ref(), id).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[put("/{id}/permissions/{code}")]
+async fn put_user_permission(
+    users: web::Data<dyn UserRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<(i64, String)>,
+    req: web::Json<SetUserPermissionRequest>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let (id, code) = path.into_inner();
+    let response = permission::set_user_permission(
+        users.get_ref(),
+        permissions.get_ref(),
+        &user,
+        id,
+        &code,
+        req.into_inner(),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[delete("/{id}/permissions/{code}")]
+async fn delete_user_permission(
+    users: web::Data<dyn UserRepository>,
+    permissions: web::Data<dyn PermissionRepository>,
+    user: AuthenticatedUser,
+    path: web::Path<(i64, String)>,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let (id, code) = path.into_inner();
+    permission::remove_user_permission(users.get_ref(), permissions.get_ref(), &user, id, &code)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,18 +1,25 @@
 use crate::{
+    config::db::DbPools,
     dto::{
         common::ListQueryParams,
+        transfer::{ExportQueryParams, ImportQueryParams},
         user_type::{CreateUserTypeRequest, UpdateUserTypeRequest},
     },
     errors::AppError,
+    handlers::export_response,
     middleware::auth::authenticated_user::AuthenticatedUser,
-    services::user_type,
+    repositories::{UserRepository, UserTypeRepository},
+    services::{admin, transfer, user_type},
 };
+use actix_multipart::Multipart;
 use actix_web::{delete, get, post, put, web, HttpResponse, Responder, Scope};
 
 pub fn route() -> Scope {
     web::scope("/user-types")
         .service(post_user_type)
         .service(get_user_type)
+        .service(get_user_type_export)
+        .service(post_user_type_import)
         .service(get_user_type_by_id)
         .service(put_user_type)
         .service(delete_user_type)
@@ -20,51 +27,86 @@ pub fn route() -> Scope {
 
 #[post("")]
 async fn post_user_type(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn UserTypeRepository>,
+    _user: AuthenticatedUser,
     req: web::Json<CreateUserTypeRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = user_type::create_user_type(pool, user, req).await?;
+    let response = user_type::create_user_type(repo.get_ref(), req.into_inner()).await?;
     Ok(HttpResponse::Created().json(response))
 }
 
 #[get("")]
 async fn get_user_type(
-    pool: web::Data<sqlx::SqlitePool>,
+    repo: web::Data<dyn UserTypeRepository>,
+    _user: AuthenticatedUser,
+    query: web::Query<ListQueryParams>,
+) -> Result<impl Responder, AppError> {
+    let response = user_type::get_user_type_array(repo.get_ref(), &query).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Export User Types as a flat CSV/JSONL file
+#[utoipa::path(
+    tag = "User Type Management",
+    params(ListQueryParams, ExportQueryParams)
+)]
+#[get("/export")]
+async fn get_user_type_export(
+    pools: web::Data<DbPools>,
     user: AuthenticatedUser,
     query: web::Query<ListQueryParams>,
+    export_params: web::Query<ExportQueryParams>,
 ) -> Result<impl Responder, AppError> {
-    let response = user_type::get_user_type_array(pool, user, query).await?;
+    admin::ensure_super_admin(&user)?;
+    let format = export_params.format;
+    let stream = user_type::export_user_type_array(&pools.read, &query, &export_params).await?;
+    Ok(export_response("user_types", format, stream))
+}
+
+/// Import User Types from a CSV/JSONL upload
+#[utoipa::path(tag = "User Type Management", params(ImportQueryParams))]
+#[post("/import")]
+async fn post_user_type_import(
+    pools: web::Data<DbPools>,
+    user: AuthenticatedUser,
+    import_params: web::Query<ImportQueryParams>,
+    payload: Multipart,
+) -> Result<impl Responder, AppError> {
+    admin::ensure_super_admin(&user)?;
+    let file = transfer::read_upload(payload).await?;
+    let response = user_type::import_user_type_array(&pools.write, &import_params, &file).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[get("/{id}")]
 async fn get_user_type_by_id(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn UserTypeRepository>,
+    _user: AuthenticatedUser,
     path: web::Path<i64>,
 ) -> Result<impl Responder, AppError> {
-    let response = user_type::get_user_type_by_id(pool, user, path).await?;
+    let response = user_type::get_user_type_by_id(repo.get_ref(), path.into_inner()).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[put("/{id}")]
 async fn put_user_type(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn UserTypeRepository>,
+    _user: AuthenticatedUser,
     path: web::Path<i64>,
     req: web::Json<UpdateUserTypeRequest>,
 ) -> Result<impl Responder, AppError> {
-    let response = user_type::update_user_type(pool, user, path, req).await?;
+    let response =
+        user_type::update_user_type(repo.get_ref(), path.into_inner(), req.into_inner()).await?;
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[delete("/{id}")]
 async fn delete_user_type(
-    pool: web::Data<sqlx::SqlitePool>,
-    user: AuthenticatedUser,
+    repo: web::Data<dyn UserTypeRepository>,
+    users: web::Data<dyn UserRepository>,
+    _user: AuthenticatedUser,
     path: web::Path<i64>,
 ) -> Result<impl Responder, AppError> {
-    user_type::delete_user_type(pool, user, path).await?;
+    user_type::delete_user_type(repo.get_ref(), users.get_ref(), path.into_inner()).await?;
     Ok(HttpResponse::NoContent().finish())
 }
@@ -1,55 +1,254 @@
-use std::env;
+use std::{sync::Arc, time::Duration};
 
 use actix_web::{web, App, HttpServer};
-use anyhow::Result;
-use config::{db, env::Env};
-use dotenv::from_filename;
-
-mod config;
-mod dto;
-mod errors;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-mod util;
+use admin_server::{
+    config::{
+        db,
+        env::{ConfigArgs, Env, LogFormat},
+    },
+    handlers, middleware,
+    repositories::{self, SqlxRepository},
+    services,
+};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    EnvFilter, Layer,
+};
+
+/// Admin API server.
+#[derive(Parser)]
+#[command(name = "admin_server", version)]
+struct Args {
+    #[command(flatten)]
+    config: ConfigArgs,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 1. 환경 파일 로드
-    let env_file = match env::var("PROFILE").as_deref() {
-        Ok("prod") => ".env.prod",
-        Ok("dev") => ".env.dev",
-        _ => ".env", // 기본값
-    };
-    from_filename(env_file).ok();
+    // 1. 설정 로드 (기본값 → TOML → 프로필 TOML → 환경 변수 → 명령줄)
+    let args = Args::parse();
+    let env = Env::load(&args.config)?;
 
-    // 2. 로깅 초기화
-    tracing_subscriber::fmt::init();
+    // 2. 로깅 초기화 (sqlx 쿼리 이벤트는 로그 레벨과 무관하게 메트릭으로 수집)
+    let filter = EnvFilter::try_new(&env.logging.level)
+        .with_context(|| format!("Invalid logging.level `{}`", env.logging.level))?;
+    let fmt_layer = match env.logging.format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        // 현재 span(request_id, route, user_id 등) 필드를 각 로그 줄에 포함
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+    };
+    let registry = tracing_subscriber::registry()
+        .with(fmt_layer.with_filter(filter))
+        .with(
+            services::metrics::QueryMetricsLayer
+                .with_filter(Targets::new().with_target("sqlx::query", LevelFilter::DEBUG)),
+        );
+    // OTLP 추적 내보내기 (수집기 주소가 설정된 경우)
+    #[cfg(feature = "otel")]
+    let tracer_provider = services::telemetry::init_tracer_provider(&env.telemetry)?;
+    #[cfg(feature = "otel")]
+    let registry =
+        registry.with(tracer_provider.as_ref().map(|provider| {
+            services::telemetry::layer(provider, EnvFilter::new(&env.logging.level))
+        }));
+    registry.init();
 
-    // 3. 환경 변수 로드
-    let env = Env::from_env()?;
+    // 3. 설정 검증 (prod에서는 약한 JWT 시크릿 거부)
+    env.validate()?;
+    tracing::info!("Loaded configuration (profile: {})", env.profile);
+    #[cfg(feature = "otel")]
+    if let Some(endpoint) = &env.telemetry.otlp_endpoint {
+        tracing::info!("Exporting traces to {}", endpoint);
+    }
 
-    // 4. 데이터베이스 연결
-    let pool = db::create_pool(&env.database_url).await?;
+    // 4. 데이터베이스 연결 (읽기/쓰기 풀 분리)
+    let pools = db::create_pools(&env.database.url, &env.database.pool).await?;
 
-    // 5. 데이터베이스 초기화
-    let _ = db::migrate_db(&pool, &env.migration_dir).await;
+    // 5. 데이터베이스 초기화 (실패하거나 스키마가 맞지 않으면 시작 중단)
+    db::migrate_db(&pools.write, &env.database.migration_dir).await?;
+    let report = db::verify_schema(&pools.read, &env.database.migration_dir).await?;
+    if !report.is_ok() {
+        bail!(
+            "Database schema does not match the migrations: {:?}",
+            report
+        );
+    }
 
     // 6. 서버 시작 시간 초기화
     services::health::initialize_server_start_time();
 
-    // 7. HTTP 서버 실행
-    let server_addr = env.server_addr.clone();
-    HttpServer::new(move || {
+    // 7. 최초 실행 설정 모드 확인 (관리자 계정이 없으면 설정 토큰 발급)
+    let setup_state =
+        web::Data::new(services::setup::SetupState::initialize(&pools.read, &env).await?);
+
+    // 8. 저장소 구성 (핸들러/미들웨어는 trait 객체로 주입받음)
+    let repo = Arc::new(SqlxRepository::new(pools.clone()));
+
+    // 9. 런타임 설정 (DB 저장 값 캐시), 로그인 실패 기록 및 요청 제한 버킷
+    let settings = web::Data::new(services::settings::Settings::load(repo.as_ref(), &env).await?);
+    let login_attempts = web::Data::new(services::auth::LoginAttempts::default());
+    let rate_limiter = web::Data::new(services::rate_limit::RateLimiter::default());
+
+    // 10. 점검 모드 상태, 백그라운드 작업 관리 및 예약 작업
+    //     (실행 이력/감사 이력 정리, 미사용 계정, SQLite 백업)
+    let maintenance_state = web::Data::new(services::maintenance::MaintenanceState::default());
+    let supervisor = web::Data::new(services::lifecycle::TaskSupervisor::default());
+    let job_repo: Arc<dyn repositories::JobRepository> = repo.clone();
+    let jobs = vec![
+        services::scheduler::prune_job_runs_job(
+            job_repo.clone(),
+            env.scheduler.history_retention_days,
+        ),
+        services::scheduler::prune_audit_history_job(
+            repo.clone(),
+            repo.clone(),
+            env.scheduler.audit_retention_days,
+        ),
+        services::dormancy::dormancy_job(repo.clone(), repo.clone(), env.dormancy.clone()),
+        // VACUUM INTO는 query_only 연결에서 실행할 수 없으므로 쓰기 풀 사용
+        #[cfg(feature = "sqlite")]
+        services::backup::backup_job(pools.write.clone(), env.clone()),
+    ];
+    let scheduler = web::Data::new(services::scheduler::Scheduler::new(
+        job_repo,
+        &env.scheduler,
+        jobs,
+    )?);
+    scheduler.clone().into_inner().spawn(&supervisor);
+    services::settings::spawn_reload_job(
+        &supervisor,
+        settings.clone().into_inner(),
+        repo.clone(),
+        env.server.settings_reload_interval_seconds,
+    );
+
+    // 11. 메트릭 노출 (내부 전용 주소 또는 토큰 인증)
+    if let Some(metrics_addr) = env.metrics.listen_addr.clone() {
+        let (env, pools) = (env.clone(), pools.clone());
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(env.clone()))
+                .app_data(web::Data::new(pools.clone()))
+                .configure(handlers::metrics::configure)
+        })
+        .workers(1)
+        .disable_signals()
+        .bind(&metrics_addr)?
+        .run();
+        tracing::info!("Serving metrics on http://{}/metrics", metrics_addr);
+        supervisor.spawn_server("metrics server", server);
+    }
+    let expose_metrics = env.metrics.listen_addr.is_none() && env.metrics.token.is_some();
+    if env.metrics.listen_addr.is_none() && env.metrics.token.is_none() {
+        tracing::info!("Metrics endpoint disabled (set metrics.token or metrics.listen_addr)");
+    }
+
+    // 12. HTTPS 설정 (인증서 파일이 바뀌면 자동으로 다시 읽음) 및 HTTP 리다이렉트 서버
+    let server_addr = env.server.addr.clone();
+    let tls = &env.server.tls;
+    let certificates = if tls.is_enabled() {
+        let store = Arc::new(services::tls::CertificateStore::load(tls)?);
+        services::tls::spawn_reload_job(&supervisor, store.clone(), tls.reload_interval_seconds);
+        Some(store)
+    } else {
+        None
+    };
+    if let Some(redirect_addr) = tls.redirect_addr.clone() {
+        let https_port = server_addr
+            .rsplit_once(':')
+            .and_then(|(_, port)| port.parse().ok())
+            .unwrap_or(443);
+        let server = HttpServer::new(move || {
+            App::new().configure(|cfg| handlers::https_redirect::configure(cfg, https_port))
+        })
+        .workers(1)
+        .disable_signals()
+        .bind(&redirect_addr)?
+        .run();
+        tracing::info!("Redirecting http://{} to HTTPS", redirect_addr);
+        supervisor.spawn_server("redirect server", server);
+    }
+
+    // 13. HTTP 서버 실행
+    let shutdown_delay = Duration::from_secs(env.server.shutdown_delay_seconds);
+    let shutdown_timeout = Duration::from_secs(env.server.shutdown_timeout_seconds);
+    let (app_pools, app_supervisor) = (pools.clone(), supervisor.clone());
+    let security_headers = middleware::security_headers::SecurityHeaders::new(&env.security);
+    let server = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(env.clone()))
-            .app_data(web::Data::new(pool.clone()))
-            .configure(handlers::configure)
-    })
-    .bind(&server_addr)?
-    .run()
-    .await?;
+            .app_data(web::Data::new(app_pools.clone()))
+            .app_data(setup_state.clone())
+            .app_data(maintenance_state.clone())
+            .app_data(settings.clone())
+            .app_data(login_attempts.clone())
+            .app_data(rate_limiter.clone())
+            .app_data(app_supervisor.clone())
+            .app_data(scheduler.clone())
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            // 인증 미들웨어 안쪽: 인증된 사용자 ID를 키로 사용할 수 있음
+            .wrap(middleware::rate_limit::RateLimit)
+            .wrap(middleware::auth::authentication_middleware::Authentication)
+            .wrap(middleware::maintenance::Maintenance)
+            // 인증/점검 모드로 거부한 응답과 preflight에도 CORS 헤더 적용
+            .wrap(middleware::cors::cors(&env.cors, settings.clone()))
+            .wrap(middleware::metrics::RequestMetrics)
+            .wrap(middleware::request_id::RequestId)
+            .wrap(security_headers.clone())
+            .configure(|cfg| handlers::configure(cfg, &env.security))
+            .configure(|cfg| {
+                if expose_metrics {
+                    handlers::metrics::configure(cfg);
+                }
+            })
+    });
+    // 신호는 직접 처리: 준비 상태를 먼저 Down으로 바꾼 뒤 처리 중인 요청을 마치고 종료
+    let server = server
+        .disable_signals()
+        .shutdown_timeout(shutdown_timeout.as_secs());
+    let server = match certificates {
+        Some(store) => {
+            tracing::info!("Serving HTTPS (HTTP/1.1, HTTP/2) on {}", server_addr);
+            server.bind_rustls_0_23(&server_addr, store.server_config()?)?
+        }
+        None => server.bind(&server_addr)?,
+    };
+    let server = server.run();
+    let handle = server.handle();
+    let signal_supervisor = supervisor.clone();
+    tokio::spawn(async move {
+        services::lifecycle::wait_for_signal().await;
+        signal_supervisor.begin_shutdown();
+        if !shutdown_delay.is_zero() {
+            tracing::info!(
+                "Waiting {}s before draining requests",
+                shutdown_delay.as_secs()
+            );
+            tokio::time::sleep(shutdown_delay).await;
+        }
+        tracing::info!("Draining in-flight requests");
+        handle.stop(true).await;
+    });
+    server.await?;
+
+    // 14. 백그라운드 작업 종료, 남은 span 전송, DB 연결 종료
+    supervisor.shutdown(shutdown_timeout).await;
+    #[cfg(feature = "otel")]
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!("Failed to flush traces: {}", e);
+        }
+    }
+    db::close_pools(&pools).await;
+    tracing::info!("Shutdown complete");
 
     Ok(())
 }
@@ -2,18 +2,21 @@ use crate::{
     config::env,
     errors::AppError,
     middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{PermissionRepository, UserRepository},
+    services::authz,
     util::{validate_jwt, Claims},
 };
 use actix_web::{
+    body::EitherBody,
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
     http::header::{HeaderValue, AUTHORIZATION},
     web, Error, HttpMessage,
 };
+use chrono::Utc;
 use futures_util::{
     future::{ok, LocalBoxFuture, Ready},
     FutureExt,
 };
-use sqlx::SqlitePool;
 use std::{
     cell::RefCell,
     collections::HashSet,
@@ -30,7 +33,7 @@ where
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type Transform = AuthenticationMiddleware<S>;
     type InitError = ();
@@ -54,7 +57,7 @@ where
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = Error;
     type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -65,52 +68,95 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
         let config = req.app_data::<web::Data<env::Env>>().cloned();
-        let pool = req.app_data::<web::Data<SqlitePool>>().cloned();
+        let user_repo = req.app_data::<web::Data<dyn UserRepository>>().cloned();
+        let permission_repo = req
+            .app_data::<web::Data<dyn PermissionRepository>>()
+            .cloned();
 
         async move {
-            let config = config.ok_or_else(|| {
-                tracing::error!("Config isn't found in app_data");
-                AppError::InternalServerError(anyhow::anyhow!("Server configuration error"))
-            })?;
-            let pool = pool.ok_or_else(|| {
-                tracing::error!("Database pool isn't found in app_data");
-                AppError::InternalServerError(anyhow::anyhow!("Database connection error"))
-            })?;
-
-            let auth_header = req.headers().get(AUTHORIZATION);
-
-            let claims = match extract_and_validate_token(auth_header, &config) {
-                Ok(claims) => claims,
-                Err(_e) if should_skip_auth(&req) => {
-                    return service.call(req).await;
+            match authenticate(&req, config, user_repo, permission_repo).await {
+                Ok(user) => {
+                    if let Some(user) = user {
+                        tracing::Span::current().record("user_id", user.id);
+                        req.extensions_mut().insert(user);
+                    }
+                    service
+                        .call(req)
+                        .await
+                        .map(ServiceResponse::map_into_left_body)
                 }
-                Err(e) => return Err(Error::from(e)),
-            };
-
-            let permissions = match fetch_user_permissions(&pool, claims.user_type_id).await {
-                Ok(perms) => Rc::new(perms),
-                Err(e) => return Err(Error::from(e)),
-            };
-
-            let authenticated_user = AuthenticatedUser {
-                id: claims.sub,
-                user_type_id: claims.user_type_id,
-                username: claims.username,
-                permissions,
-            };
-            req.extensions_mut().insert(authenticated_user);
-
-            service.call(req).await
+                // 거부한 요청도 응답으로 변환해 바깥 미들웨어(CORS 등)가 헤더를 붙일 수 있게 함
+                Err(e) => Ok(req.error_response(e).map_into_right_body()),
+            }
         }
         .boxed_local()
     }
 }
 
+// 인증된 사용자 (인증을 건너뛰는 경로에서 토큰이 없거나 잘못되면 None)
+async fn authenticate(
+    req: &ServiceRequest,
+    config: Option<web::Data<env::Env>>,
+    user_repo: Option<web::Data<dyn UserRepository>>,
+    permission_repo: Option<web::Data<dyn PermissionRepository>>,
+) -> Result<Option<AuthenticatedUser>, AppError> {
+    let config = config.ok_or_else(|| {
+        tracing::error!("Config isn't found in app_data");
+        AppError::InternalServerError(anyhow::anyhow!("Server configuration error"))
+    })?;
+    let user_repo = user_repo.ok_or_else(|| {
+        tracing::error!("User repository isn't found in app_data");
+        AppError::InternalServerError(anyhow::anyhow!("Database connection error"))
+    })?;
+    let permission_repo = permission_repo.ok_or_else(|| {
+        tracing::error!("Permission repository isn't found in app_data");
+        AppError::InternalServerError(anyhow::anyhow!("Database connection error"))
+    })?;
+
+    let auth_header = req.headers().get(AUTHORIZATION);
+
+    let claims = match extract_and_validate_token(auth_header, &config) {
+        Ok(claims) => claims,
+        Err(_e) if should_skip_auth(req) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    // 토큰 발급 후 비활성화(휴면 등)되거나 삭제된 사용자는 거부하고,
+    // 사용자 종류는 토큰이 아닌 현재 값을 사용
+    let user = user_repo
+        .find_by_id(claims.sub)
+        .await
+        .inspect_err(|e| tracing::error!("사용자 조회 실패: {}", e))?
+        .filter(|user| user.is_active);
+    let user = match user {
+        Some(user) => user,
+        None if should_skip_auth(req) => return Ok(None),
+        None => {
+            return Err(AppError::unauthorized(
+                "User is inactive or no longer exists",
+            ))
+        }
+    };
+
+    let permissions =
+        fetch_user_permissions(permission_repo.get_ref(), user.id, user.user_type_id).await?;
+
+    Ok(Some(AuthenticatedUser {
+        id: user.id,
+        user_type_id: user.user_type_id,
+        username: user.username,
+        permissions: Rc::new(permissions),
+    }))
+}
+
 // 인증 건너뛸 경로 확인 (예시)
 fn should_skip_auth(req: &ServiceRequest) -> bool {
     let path = req.path();
     path == "/api/v1/auth/login"
         || path == "/api/v1/health"
+        || path.starts_with("/api/v1/health/")
+        || path == "/api/v1/setup"
+        || path == "/metrics" // 메트릭 토큰으로 별도 인증
         || path.starts_with("/swagger-ui")
         || path == "/api-docs/openapi.json"
 }
@@ -136,35 +182,21 @@ fn extract_and_validate_token(
     validate_jwt(token, config)
 }
 
-#[derive(sqlx::FromRow)]
-struct PermissionCode {
-    code: String,
-}
-
+// 사용자 종류 권한에 만료되지 않은 사용자별 예외(허용/거부)를 반영
 async fn fetch_user_permissions(
-    pool: &SqlitePool,
+    repo: &dyn PermissionRepository,
+    user_id: i64,
     user_type_id: i64,
 ) -> Result<HashSet<String>, AppError> {
-    let permissions = sqlx::query_as!(
-        PermissionCode,
-        r#"
-        SELECT p.code
-        FROM permission p
-        JOIN user_type_permission utp ON p.id = utp.permission_id
-        WHERE utp.user_type_id = ?
-        LIMIT 1000
-        "#,
-        user_type_id
-    )
-    .fetch_all(pool)
-    .await
-    .map_err(|e| {
-        tracing::error!("권한 조회 실패: {}", e);
-        AppError::DatabaseError(e)
-    })?
-    .into_iter()
-    .map(|p| p.code)
-    .collect::<HashSet<String>>();
+    let codes = repo
+        .codes_for_user_type(user_type_id)
+        .await
+        .inspect_err(|e| tracing::error!("권한 조회 실패: {}", e))?;
+    let overrides = repo
+        .user_overrides(user_id)
+        .await
+        .inspect_err(|e| tracing::error!("사용자별 권한 조회 실패: {}", e))?;
+    let permissions = authz::merge_permissions(codes, &overrides, Utc::now().naive_utc());
 
     if permissions.is_empty() {
         tracing::warn!("사용자 타입 {}에 대한 권한이 없습니다", user_type_id);
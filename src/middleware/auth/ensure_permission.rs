@@ -1,4 +1,6 @@
-use crate::{errors::AppError, middleware::auth::authenticated_user::AuthenticatedUser};
+use crate::{
+    errors::AppError, middleware::auth::authenticated_user::AuthenticatedUser, services::authz,
+};
 use actix_web::{dev::Payload, Error, FromRequest, HttpMessage, HttpRequest};
 use futures_util::future::{ready, Ready};
 
@@ -15,8 +17,7 @@ impl FromRequest for EnsurePermission {
 
         match user_data {
             Some(user) => {
-                if user.permissions.contains("*") || user.permissions.contains(required_permission)
-                {
+                if authz::is_granted(&user.permissions, required_permission) {
                     tracing::debug!(
                         "Permission is granted for user {} to access '{}'",
                         user.username,
@@ -24,9 +25,10 @@ impl FromRequest for EnsurePermission {
                     );
                     ready(Ok(EnsurePermission))
                 } else {
+                    // 거부 사유는 POST /api/v1/authz/check 로 확인
                     tracing::warn!(
-                        "Permission is denied for user {} (ID: {}) attempting to access '{}'. User permissions: {:?}",
-                        user.username, user.id, required_permission, user.permissions
+                        "Permission is denied for user {} (ID: {}, user type: {}) attempting to access '{}' ({} permissions granted)",
+                        user.username, user.id, user.user_type_id, required_permission, user.permissions.len()
                     );
                     ready(Err(Error::from(AppError::forbidden(
                         "Insufficient permissions",
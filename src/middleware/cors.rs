@@ -0,0 +1,124 @@
+use crate::{
+    config::env::CorsConfig,
+    services::settings::{Settings, CORS_ALLOWED_ORIGINS},
+};
+use actix_cors::Cors;
+use actix_web::web;
+
+/// Builds the CORS middleware. Methods, headers, credentials and max-age come from the
+/// configuration file; allowed origins are read from the `cors.allowed_origins` runtime setting
+/// on every request, so changing it through the admin API takes effect immediately.
+pub fn cors(config: &CorsConfig, settings: web::Data<Settings>) -> Cors {
+    let allow_credentials = config.allow_credentials;
+    let mut cors = Cors::default()
+        .allowed_origin_fn(move |origin, _head| {
+            origin.to_str().is_ok_and(|origin| {
+                origin_allowed(
+                    &settings.get(&CORS_ALLOWED_ORIGINS),
+                    origin,
+                    allow_credentials,
+                )
+            })
+        })
+        .allowed_methods(config.allowed_methods.iter().map(String::as_str))
+        .allowed_headers(config.allowed_headers.iter().map(String::as_str))
+        .max_age((config.max_age_seconds > 0).then_some(config.max_age_seconds as usize));
+    if !config.exposed_headers.is_empty() {
+        cors = cors.expose_headers(config.exposed_headers.iter().map(String::as_str));
+    }
+    if allow_credentials {
+        cors = cors.supports_credentials();
+    }
+    cors
+}
+
+/// Whether `origin` (the request's `Origin` header) matches one of `patterns`. `*` is ignored
+/// when credentials are allowed, since browsers reject that combination anyway.
+pub fn origin_allowed(patterns: &[String], origin: &str, allow_credentials: bool) -> bool {
+    patterns.iter().any(|pattern| {
+        if pattern == "*" {
+            !allow_credentials
+        } else {
+            origin_matches(pattern, origin)
+        }
+    })
+}
+
+// https://*.example.com 은 하위 도메인(여러 단계 포함)만, http://localhost:* 는 모든 포트와 일치
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+    let (Some((pattern_scheme, pattern_host)), Some((scheme, host))) =
+        (pattern.split_once("://"), origin.split_once("://"))
+    else {
+        return false;
+    };
+    if !pattern_scheme.eq_ignore_ascii_case(scheme) {
+        return false;
+    }
+
+    let (pattern_host, pattern_port) = split_port(pattern_host);
+    let (host, port) = split_port(host);
+    let port_matches = match pattern_port {
+        Some("*") => true,
+        _ => pattern_port == port,
+    };
+    let host_matches = match pattern_host.strip_prefix("*.") {
+        Some(domain) => {
+            host.len() > domain.len() + 1 && {
+                let (sub, rest) = host.split_at(host.len() - domain.len() - 1);
+                rest.starts_with('.')
+                    && rest[1..].eq_ignore_ascii_case(domain)
+                    && sub.split('.').all(is_dns_label)
+            }
+        }
+        None => pattern_host.eq_ignore_ascii_case(host),
+    };
+    port_matches && host_matches
+}
+
+fn split_port(host: &str) -> (&str, Option<&str>) {
+    match host.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (host, None),
+    }
+}
+
+fn is_dns_label(label: &str) -> bool {
+    !label.is_empty()
+        && label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Checks that `pattern` is `*` or `scheme://host[:port]`, where the host may start with `*.`
+/// and the port may be `*`.
+pub fn validate_origin(pattern: &str) -> Result<(), String> {
+    if pattern == "*" {
+        return Ok(());
+    }
+    let Some((scheme, host)) = pattern.split_once("://") else {
+        return Err("must look like `https://host[:port]`".to_string());
+    };
+    if !matches!(scheme, "http" | "https") {
+        return Err("must use the http or https scheme".to_string());
+    }
+    if host.contains('/') {
+        return Err("must not contain a path or a trailing slash".to_string());
+    }
+
+    let (host, port) = split_port(host);
+    if port.is_some_and(|p| p != "*" && p.parse::<u16>().is_err()) {
+        return Err("has an invalid port".to_string());
+    }
+    let domain = match host.strip_prefix("*.") {
+        // 최상위 도메인 전체(https://*.com)를 허용하지 않도록 점이 하나 이상 있어야 함
+        Some(domain) if !domain.contains('.') => {
+            return Err("wildcard must be followed by a domain like `example.com`".to_string())
+        }
+        Some(domain) => domain,
+        None => host,
+    };
+    if !domain.split('.').all(is_dns_label) {
+        return Err("has an invalid host".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::{errors::AppError, services::maintenance::MaintenanceState};
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error,
+};
+use futures_util::{
+    future::{ok, LocalBoxFuture, Ready},
+    FutureExt,
+};
+use std::rc::Rc;
+
+// 점검 모드 미들웨어 팩토리
+pub struct Maintenance;
+
+impl<S: 'static, B> Transform<S, ServiceRequest> for Maintenance
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = MaintenanceMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(MaintenanceMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct MaintenanceMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for MaintenanceMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let in_maintenance = req
+            .app_data::<web::Data<MaintenanceState>>()
+            .is_some_and(|state| state.is_active());
+
+        // 점검 중에는 헬스 체크를 제외한 모든 요청 거부
+        if in_maintenance && !req.path().starts_with("/api/v1/health") {
+            let res = req.error_response(AppError::service_unavailable(
+                "Server is in maintenance mode",
+            ));
+            return ok(res.map_into_right_body()).boxed_local();
+        }
+
+        self.service
+            .call(req)
+            .map(|res| res.map(ServiceResponse::map_into_left_body))
+            .boxed_local()
+    }
+}
@@ -0,0 +1,91 @@
+use crate::services::metrics::METRICS;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::Method,
+    Error,
+};
+use futures_util::{
+    future::{ok, LocalBoxFuture, Ready},
+    FutureExt,
+};
+use std::{rc::Rc, time::Instant};
+
+// 임의의 확장 메서드로 라벨 조합이 늘어나지 않도록 표준 메서드 외에는 OTHER로 묶음
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::PATCH => "PATCH",
+        Method::DELETE => "DELETE",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        _ => "OTHER",
+    }
+}
+
+// 요청 수/지연 시간 수집 미들웨어 팩토리 (가장 바깥쪽에 등록)
+pub struct RequestMetrics;
+
+impl<S: 'static, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestMetricsMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let started = Instant::now();
+        let method = method_label(req.method());
+        // 경로 값이 아닌 라우트 템플릿(/api/v1/user/{id})을 라벨로 사용해 카디널리티 제한
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+        let service = Rc::clone(&self.service);
+
+        async move {
+            let result = service.call(req).await;
+            let status = match &result {
+                Ok(res) => res.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+
+            let labels = [method, route.as_str(), status.as_str()];
+            METRICS.http_requests.with_label_values(&labels).inc();
+            METRICS
+                .http_request_duration
+                .with_label_values(&labels)
+                .observe(started.elapsed().as_secs_f64());
+            result
+        }
+        .boxed_local()
+    }
+}
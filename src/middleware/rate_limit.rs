@@ -0,0 +1,170 @@
+use crate::{
+    config::env::{Env, RateLimitKey},
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    services::{
+        rate_limit::{self, RateLimitDecision, RateLimiter},
+        settings::{Settings, RATE_LIMIT_ENABLED, RATE_LIMIT_ROUTES},
+    },
+    util::constant_time_eq,
+};
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, RETRY_AFTER},
+    web, Error, HttpMessage,
+};
+use futures_util::{
+    future::{ok, LocalBoxFuture, Ready},
+    FutureExt,
+};
+use std::{rc::Rc, time::Instant};
+
+pub const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+pub const RATELIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+pub const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+
+// 요청 제한 미들웨어 팩토리 (인증 미들웨어 안쪽에 등록해야 사용자별 제한 가능)
+pub struct RateLimit;
+
+impl<S: 'static, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let decision = check(&req);
+
+        if let Some(decision) = decision.filter(|d| !d.allowed) {
+            tracing::warn!("Rate limit exceeded for {} {}", req.method(), req.path());
+            let mut res = req.error_response(AppError::too_many_requests(&format!(
+                "Rate limit exceeded, retry in {} seconds",
+                decision.retry_after_seconds
+            )));
+            set_headers(res.headers_mut(), &decision);
+            res.headers_mut()
+                .insert(RETRY_AFTER, decision.retry_after_seconds.into());
+            return ok(res.map_into_right_body()).boxed_local();
+        }
+
+        let fut = self.service.call(req);
+        async move {
+            let mut res = fut.await?;
+            if let Some(decision) = decision {
+                set_headers(res.headers_mut(), &decision);
+            }
+            Ok(res.map_into_left_body())
+        }
+        .boxed_local()
+    }
+}
+
+// 일치하는 규칙이 없으면 None (제한 없음)
+fn check(req: &ServiceRequest) -> Option<RateLimitDecision> {
+    let limiter = req.app_data::<web::Data<RateLimiter>>()?;
+    // 런타임 설정(rate_limit.*)을 요청마다 읽어 재시작 없이 반영 (없으면 설정 파일 값)
+    let (enabled, routes) = match req.app_data::<web::Data<Settings>>() {
+        Some(settings) => (
+            settings.get(&RATE_LIMIT_ENABLED),
+            settings.get(&RATE_LIMIT_ROUTES),
+        ),
+        None => {
+            let config = &req.app_data::<web::Data<Env>>()?.rate_limit;
+            (config.enabled, config.routes.clone())
+        }
+    };
+    if !enabled {
+        return None;
+    }
+    let route = rate_limit::route_for(&routes, req.method().as_str(), req.path())?;
+
+    let user_id = req.extensions().get::<AuthenticatedUser>().map(|u| u.id);
+    let key = match (route.key, user_id) {
+        (RateLimitKey::Principal | RateLimitKey::ApiKey, Some(id)) => format!("user:{}", id),
+        (RateLimitKey::ApiKey, None) if has_metrics_token(req) => "key:metrics".to_string(),
+        _ => format!("ip:{}", client_ip(req)),
+    };
+    Some(limiter.check(route, &key, Instant::now()))
+}
+
+// 설정된 메트릭 토큰과 일치하는 키만 별도 버킷 사용 (임의의 값은 클라이언트 IP 기준)
+fn has_metrics_token(req: &ServiceRequest) -> bool {
+    let Some(token) = req
+        .app_data::<web::Data<Env>>()
+        .and_then(|env| env.metrics.token.clone())
+    else {
+        return false;
+    };
+    let headers = req.headers();
+    headers
+        .get(X_API_KEY)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+        .is_some_and(|key| constant_time_eq(key, &token))
+}
+
+// 프록시 헤더는 설정으로 신뢰할 때만 사용 (그렇지 않으면 위조 가능)
+fn client_ip(req: &ServiceRequest) -> String {
+    let trust_proxy = req
+        .app_data::<web::Data<Env>>()
+        .is_some_and(|env| env.rate_limit.trust_proxy_headers);
+    if trust_proxy {
+        if let Some(ip) = req.connection_info().realip_remote_addr() {
+            // "ip:port" 형식이면 포트 제거
+            return match ip.parse::<std::net::SocketAddr>() {
+                Ok(addr) => addr.ip().to_string(),
+                Err(_) => ip.to_string(),
+            };
+        }
+    }
+    req.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn set_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATELIMIT_LIMIT, decision.limit.into());
+    headers.insert(RATELIMIT_REMAINING, decision.remaining.into());
+    headers.insert(RATELIMIT_RESET, decision.reset_seconds.into());
+    let policy = format!("{};w={}", decision.limit, decision.window_seconds);
+    if let Ok(value) = HeaderValue::from_str(&policy) {
+        headers.insert(RATELIMIT_POLICY, value);
+    }
+}
@@ -0,0 +1,145 @@
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{HeaderName, HeaderValue},
+    Error,
+};
+use futures_util::{
+    future::{ok, LocalBoxFuture, Ready},
+    FutureExt,
+};
+use std::{rc::Rc, time::Instant};
+use tracing::{field::Empty, Instrument};
+use uuid::Uuid;
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// ID of the request being handled, if called from within [`RequestId`].
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+// 클라이언트가 보낸 ID는 로그에 그대로 남으므로 길이/문자를 제한
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+// 요청 ID 부여 및 요청별 tracing span 생성 미들웨어 팩토리 (가장 바깥쪽에 등록)
+pub struct RequestId;
+
+impl<S: 'static, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestIdMiddleware {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(&X_REQUEST_ID)
+            .and_then(|v| v.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| req.path().to_string());
+        // user_id는 인증 미들웨어, status는 응답 후 기록
+        let span = tracing::info_span!(
+            "request",
+            otel.name = %format!("{} {}", req.method(), route),
+            otel.kind = "server",
+            otel.status_code = Empty,
+            request_id = %request_id,
+            method = %req.method(),
+            route = %route,
+            user_id = Empty,
+            status = Empty,
+        );
+        // 호출한 서비스의 traceparent가 있으면 같은 추적에 연결
+        #[cfg(feature = "otel")]
+        {
+            use tracing_opentelemetry::OpenTelemetrySpanExt;
+            let _ = span.set_parent(crate::services::telemetry::extract_context(req.headers()));
+        }
+        let service = Rc::clone(&self.service);
+        let header_value = HeaderValue::from_str(&request_id).ok();
+
+        let fut = async move {
+            let started = Instant::now();
+            let result = service.call(req).await;
+            let status = match &result {
+                Ok(res) => res.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+            let span = tracing::Span::current();
+            span.record("status", status.as_u16());
+            if status.is_server_error() {
+                span.record("otel.status_code", "error");
+            }
+            tracing::info!(
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "Request completed with status {}",
+                status.as_u16()
+            );
+
+            match result {
+                Ok(mut res) => {
+                    if let Some(value) = header_value {
+                        res.headers_mut().insert(X_REQUEST_ID, value);
+                    }
+                    Ok(res)
+                }
+                // 오류 응답은 밖에서 생성되므로, 본문에 요청 ID가 포함되도록 task-local 범위 안에서 미리 생성
+                Err(e) => {
+                    let mut response = e.error_response();
+                    if let Some(value) = header_value {
+                        response.headers_mut().insert(X_REQUEST_ID, value);
+                    }
+                    Err(InternalError::from_response(e, response).into())
+                }
+            }
+        };
+
+        REQUEST_ID
+            .scope(request_id, fut.instrument(span))
+            .boxed_local()
+    }
+}
@@ -0,0 +1,126 @@
+use crate::config::env::SecurityConfig;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{
+        HeaderMap, HeaderName, HeaderValue, CONTENT_SECURITY_POLICY, REFERRER_POLICY,
+        STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+    },
+    Error,
+};
+use futures_util::{
+    future::{ok, LocalBoxFuture, Ready},
+    FutureExt,
+};
+use std::rc::Rc;
+
+// 모든 응답에 붙일 보안 헤더 (설정에서 한 번만 생성)
+#[derive(Clone)]
+pub struct SecurityHeaders {
+    common: Vec<(HeaderName, HeaderValue)>,
+    api_csp: HeaderValue,
+    swagger_ui_csp: HeaderValue,
+}
+
+impl SecurityHeaders {
+    /// Builds the header set; the configuration must have passed `Env::validate`.
+    pub fn new(config: &SecurityConfig) -> Self {
+        let value = |v: &str| HeaderValue::from_str(v).expect("validated header value");
+        let mut common = vec![
+            (X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff")),
+            (X_FRAME_OPTIONS, value(&config.frame_options.to_uppercase())),
+            (REFERRER_POLICY, value(&config.referrer_policy)),
+        ];
+        if config.hsts_max_age_seconds > 0 {
+            let mut hsts = format!("max-age={}", config.hsts_max_age_seconds);
+            if config.hsts_include_subdomains {
+                hsts.push_str("; includeSubDomains");
+            }
+            common.push((STRICT_TRANSPORT_SECURITY, value(&hsts)));
+        }
+
+        Self {
+            common,
+            api_csp: value(&config.content_security_policy),
+            swagger_ui_csp: value(&config.swagger_ui_content_security_policy),
+        }
+    }
+
+    // 핸들러가 직접 지정한 헤더는 덮어쓰지 않음
+    fn apply(&self, headers: &mut HeaderMap, swagger_ui: bool) {
+        let csp = if swagger_ui {
+            &self.swagger_ui_csp
+        } else {
+            &self.api_csp
+        };
+        for (name, value) in self
+            .common
+            .iter()
+            .chain([(CONTENT_SECURITY_POLICY, csp.clone())].iter())
+        {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl<S: 'static, B> Transform<S, ServiceRequest> for SecurityHeaders
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = SecurityHeadersMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(SecurityHeadersMiddleware {
+            service: Rc::new(service),
+            headers: Rc::new(self.clone()),
+        })
+    }
+}
+
+pub struct SecurityHeadersMiddleware<S> {
+    service: Rc<S>,
+    headers: Rc<SecurityHeaders>,
+}
+
+impl<S, B> Service<ServiceRequest> for SecurityHeadersMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let swagger_ui = req.path().starts_with("/swagger-ui");
+        let headers = Rc::clone(&self.headers);
+        let fut = self.service.call(req);
+
+        async move {
+            match fut.await {
+                Ok(mut res) => {
+                    headers.apply(res.headers_mut(), swagger_ui);
+                    Ok(res)
+                }
+                // 오류는 밖에서 응답으로 변환되므로 미리 생성해 헤더 추가
+                Err(e) => {
+                    let mut response = e.error_response();
+                    headers.apply(response.headers_mut(), swagger_ui);
+                    Err(InternalError::from_response(e, response).into())
+                }
+            }
+        }
+        .boxed_local()
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+// access_review 행 (user_types는 JSON 배열 문자열)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccessReview {
+    pub id: i64,
+    pub name: String,
+    pub status: String, // open / closed
+    pub user_types: String,
+    pub created_by: String,
+    pub due_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub closed_by: Option<String>,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+// access_review_item 행 (permissions는 JSON 배열 문자열)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AccessReviewItem {
+    pub id: i64,
+    pub review_id: i64,
+    pub user_id: Option<i64>,
+    pub username: String,
+    pub user_type_id: i64,
+    pub user_type_name: String,
+    pub permissions: String,
+    pub decision: String, // pending / keep / revoke
+    pub comment: Option<String>,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<NaiveDateTime>,
+    pub applied_at: Option<NaiveDateTime>,
+}
@@ -18,4 +18,9 @@ pub struct AdminUser {
     pub last_login_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub dormancy_warned_at: Option<NaiveDateTime>, // 장기 미사용 경고 시각 (로그인하면 초기화)
+    pub deactivated_at: Option<NaiveDateTime>,
+    #[schema(example = "Dormant: no login for 90 days")]
+    pub deactivated_reason: Option<String>,
+    pub reactivated_at: Option<NaiveDateTime>,
 }
@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+// job_run 행 (예약 작업 실행 이력)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct JobRun {
+    #[schema(example = 12)]
+    pub id: i64,
+    #[schema(example = "backup")]
+    pub job_name: String,
+    #[schema(example = "schedule")]
+    pub trigger_type: String, // schedule / manual
+    #[schema(example = "succeeded")]
+    pub status: String, // running / succeeded / failed
+    #[schema(example = "Created backup `backup-20250101T030000000Z.db` (8192 bytes)")]
+    pub message: Option<String>,
+    pub started_by: Option<i64>, // 수동 실행한 사용자
+    pub instance_id: String,     // 실행한 서버 인스턴스
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
@@ -1,9 +1,17 @@
+pub mod access_review;
 pub mod admin_user;
+pub mod job_run;
 pub mod menu_item;
 pub mod permission;
+pub mod system_setting;
+pub mod user_permission;
 pub mod user_type;
 
+pub use access_review::{AccessReview, AccessReviewItem};
 pub use admin_user::AdminUser;
+pub use job_run::JobRun;
 pub use menu_item::MenuItem;
 pub use permission::Permission;
+pub use system_setting::SystemSetting;
+pub use user_permission::{UserPermission, UserPermissionAudit};
 pub use user_type::UserType;
@@ -0,0 +1,12 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+// system_setting 행 (value는 JSON 문자열)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SystemSetting {
+    pub key: String,
+    pub value: String,
+    pub updated_by: Option<i64>,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+// user_permission 행 (permission_code는 permission 테이블에서 조인)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserPermission {
+    pub id: i64,
+    pub user_id: i64,
+    pub permission_id: i64,
+    pub permission_code: String,
+    pub effect: String, // allow / deny
+    pub reason: Option<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub granted_by: String,
+    pub granted_at: NaiveDateTime,
+}
+
+// user_permission_audit 행 (사용자별 권한 예외 변경 이력)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UserPermissionAudit {
+    #[schema(example = 3)]
+    pub id: i64,
+    #[schema(example = 7)]
+    pub user_id: i64,
+    #[schema(example = "jane")]
+    pub username: String,
+    #[schema(example = "report:export")]
+    pub permission_code: String,
+    #[schema(example = "set")]
+    pub action: String, // set / remove
+    #[schema(example = "allow")]
+    pub effect: Option<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    #[schema(example = "Quarter-end reporting")]
+    pub reason: Option<String>,
+    #[schema(example = "admin")]
+    pub changed_by: String,
+    pub changed_at: NaiveDateTime,
+}
@@ -0,0 +1,202 @@
+use super::SqlxRepository;
+use crate::{
+    errors::AppError,
+    models::{AccessReview, AccessReviewItem},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+pub struct NewAccessReview {
+    pub name: String,
+    pub user_types: String, // JSON 배열
+    pub created_by: String,
+    pub due_at: Option<NaiveDateTime>,
+}
+
+// 캠페인 시작 시점의 사용자 스냅샷
+pub struct NewAccessReviewItem {
+    pub user_id: i64,
+    pub username: String,
+    pub user_type_id: i64,
+    pub user_type_name: String,
+    pub permissions: String, // JSON 배열
+}
+
+#[async_trait]
+pub trait AccessReviewRepository: Send + Sync {
+    // 캠페인과 항목을 한 번에 생성 (일부만 저장되지 않도록 트랜잭션 사용)
+    async fn create_review(
+        &self,
+        review: NewAccessReview,
+        items: Vec<NewAccessReviewItem>,
+    ) -> Result<AccessReview, AppError>;
+    // 최신순
+    async fn list_reviews(&self) -> Result<Vec<AccessReview>, AppError>;
+    async fn find_review(&self, id: i64) -> Result<Option<AccessReview>, AppError>;
+    // 사용자 이름순
+    async fn list_items(&self, review_id: i64) -> Result<Vec<AccessReviewItem>, AppError>;
+    // 진행 중(open)인 캠페인의 아직 적용되지 않은 항목만 변경, 그 외에는 None
+    async fn decide_item(
+        &self,
+        review_id: i64,
+        item_id: i64,
+        decision: &str,
+        comment: Option<&str>,
+        decided_by: &str,
+    ) -> Result<Option<AccessReviewItem>, AppError>;
+    async fn mark_item_applied(&self, item_id: i64) -> Result<(), AppError>;
+    // 진행 중(open)인 캠페인만 종료, 이미 종료된 경우 None
+    async fn close_review(
+        &self,
+        id: i64,
+        closed_by: &str,
+    ) -> Result<Option<AccessReview>, AppError>;
+    // 보관 기간이 지난 종료된 캠페인과 항목 삭제, 삭제한 캠페인 수
+    async fn delete_closed_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl AccessReviewRepository for SqlxRepository {
+    async fn create_review(
+        &self,
+        review: NewAccessReview,
+        items: Vec<NewAccessReviewItem>,
+    ) -> Result<AccessReview, AppError> {
+        let mut tx = self.pools.write.begin().await?;
+        let review = sqlx::query_as::<_, AccessReview>(
+            r#"
+            INSERT INTO access_review (name, status, user_types, created_by, due_at, created_at)
+            VALUES ($1, 'open', $2, $3, $4, CURRENT_TIMESTAMP)
+            RETURNING *
+            "#,
+        )
+        .bind(&review.name)
+        .bind(&review.user_types)
+        .bind(&review.created_by)
+        .bind(review.due_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for item in items {
+            sqlx::query(
+                r#"
+                INSERT INTO access_review_item
+                    (review_id, user_id, username, user_type_id, user_type_name, permissions, decision)
+                VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+                "#,
+            )
+            .bind(review.id)
+            .bind(item.user_id)
+            .bind(&item.username)
+            .bind(item.user_type_id)
+            .bind(&item.user_type_name)
+            .bind(&item.permissions)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(review)
+    }
+
+    async fn list_reviews(&self) -> Result<Vec<AccessReview>, AppError> {
+        let reviews = sqlx::query_as::<_, AccessReview>(
+            "SELECT * FROM access_review ORDER BY created_at DESC, id DESC",
+        )
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(reviews)
+    }
+
+    async fn find_review(&self, id: i64) -> Result<Option<AccessReview>, AppError> {
+        let review = sqlx::query_as::<_, AccessReview>("SELECT * FROM access_review WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(review)
+    }
+
+    async fn list_items(&self, review_id: i64) -> Result<Vec<AccessReviewItem>, AppError> {
+        let items = sqlx::query_as::<_, AccessReviewItem>(
+            "SELECT * FROM access_review_item WHERE review_id = $1 ORDER BY username ASC, id ASC",
+        )
+        .bind(review_id)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(items)
+    }
+
+    async fn decide_item(
+        &self,
+        review_id: i64,
+        item_id: i64,
+        decision: &str,
+        comment: Option<&str>,
+        decided_by: &str,
+    ) -> Result<Option<AccessReviewItem>, AppError> {
+        let item = sqlx::query_as::<_, AccessReviewItem>(
+            r#"
+            UPDATE access_review_item
+            SET decision = $1, comment = $2, decided_by = $3, decided_at = CURRENT_TIMESTAMP
+            WHERE id = $4 AND review_id = $5 AND applied_at IS NULL
+              AND EXISTS (SELECT 1 FROM access_review WHERE id = $5 AND status = 'open')
+            RETURNING *
+            "#,
+        )
+        .bind(decision)
+        .bind(comment)
+        .bind(decided_by)
+        .bind(item_id)
+        .bind(review_id)
+        .fetch_optional(&self.pools.write)
+        .await?;
+        Ok(item)
+    }
+
+    async fn mark_item_applied(&self, item_id: i64) -> Result<(), AppError> {
+        sqlx::query("UPDATE access_review_item SET applied_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(item_id)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(())
+    }
+
+    async fn close_review(
+        &self,
+        id: i64,
+        closed_by: &str,
+    ) -> Result<Option<AccessReview>, AppError> {
+        let review = sqlx::query_as::<_, AccessReview>(
+            r#"
+            UPDATE access_review SET status = 'closed', closed_by = $1, closed_at = CURRENT_TIMESTAMP
+            WHERE id = $2 AND status = 'open'
+            RETURNING *
+            "#,
+        )
+        .bind(closed_by)
+        .bind(id)
+        .fetch_optional(&self.pools.write)
+        .await?;
+        Ok(review)
+    }
+
+    async fn delete_closed_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let mut tx = self.pools.write.begin().await?;
+        sqlx::query(
+            r#"
+            DELETE FROM access_review_item WHERE review_id IN (
+                SELECT id FROM access_review WHERE status = 'closed' AND closed_at < $1
+            )
+            "#,
+        )
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?;
+        let result =
+            sqlx::query("DELETE FROM access_review WHERE status = 'closed' AND closed_at < $1")
+                .bind(cutoff)
+                .execute(&mut *tx)
+                .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+}
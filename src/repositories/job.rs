@@ -0,0 +1,165 @@
+use super::SqlxRepository;
+use crate::{errors::AppError, models::JobRun};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime};
+
+pub struct NewJobRun<'a> {
+    pub job_name: &'a str,
+    pub trigger_type: &'a str,
+    pub started_by: Option<i64>,
+    pub instance_id: &'a str,
+}
+
+#[async_trait]
+pub trait JobRepository: Send + Sync {
+    // 잠금이 없거나 만료(locked_until < now)된 경우에만 획득.
+    // slot이 있으면(예약 실행) 이미 같은 시각 이후의 slot을 처리한 경우에도 실패하고, 획득 시 기록
+    async fn try_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        now: NaiveDateTime,
+        until: NaiveDateTime,
+        slot: Option<NaiveDateTime>,
+    ) -> Result<bool, AppError>;
+    // owner가 잠금을 가진 경우에만 locked_until을 연장. 다른 인스턴스가 가져갔으면 false
+    async fn extend_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        until: NaiveDateTime,
+    ) -> Result<bool, AppError>;
+    // owner가 가진 잠금만 해제 (마지막 slot 기록은 유지)
+    async fn unlock(&self, job_name: &str, owner: &str) -> Result<(), AppError>;
+    async fn start_run(&self, run: &NewJobRun<'_>) -> Result<JobRun, AppError>;
+    async fn finish_run(
+        &self,
+        id: i64,
+        status: &str,
+        message: Option<&str>,
+    ) -> Result<JobRun, AppError>;
+    // 최신순
+    async fn list_runs(&self, job_name: &str, limit: i64) -> Result<Vec<JobRun>, AppError>;
+    async fn delete_runs_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl JobRepository for SqlxRepository {
+    async fn try_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        now: NaiveDateTime,
+        until: NaiveDateTime,
+        slot: Option<NaiveDateTime>,
+    ) -> Result<bool, AppError> {
+        // 다른 인스턴스의 잠금이 유효하거나 slot을 이미 처리했으면 갱신되지 않아 영향받은 행이 0
+        let result = sqlx::query(
+            r#"
+            INSERT INTO job_lock (job_name, locked_by, locked_until, last_slot)
+            VALUES ($1, $2, $3, $5)
+            ON CONFLICT (job_name) DO UPDATE
+                SET locked_by = excluded.locked_by,
+                    locked_until = excluded.locked_until,
+                    last_slot = COALESCE(excluded.last_slot, job_lock.last_slot)
+                WHERE job_lock.locked_until < $4
+                  AND ($5 IS NULL OR job_lock.last_slot IS NULL OR job_lock.last_slot < $5)
+            "#,
+        )
+        .bind(job_name)
+        .bind(owner)
+        .bind(until)
+        .bind(now)
+        .bind(slot)
+        .execute(&self.pools.write)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn extend_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        until: NaiveDateTime,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE job_lock SET locked_until = $3 WHERE job_name = $1 AND locked_by = $2",
+        )
+        .bind(job_name)
+        .bind(owner)
+        .bind(until)
+        .execute(&self.pools.write)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn unlock(&self, job_name: &str, owner: &str) -> Result<(), AppError> {
+        // 행을 지우면 last_slot도 사라지므로 만료 처리만 함
+        sqlx::query("UPDATE job_lock SET locked_until = $3 WHERE job_name = $1 AND locked_by = $2")
+            .bind(job_name)
+            .bind(owner)
+            .bind(DateTime::UNIX_EPOCH.naive_utc())
+            .execute(&self.pools.write)
+            .await?;
+        Ok(())
+    }
+
+    async fn start_run(&self, run: &NewJobRun<'_>) -> Result<JobRun, AppError> {
+        let job_run = sqlx::query_as::<_, JobRun>(
+            r#"
+            INSERT INTO job_run (job_name, trigger_type, status, started_by, instance_id)
+            VALUES ($1, $2, 'running', $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(run.job_name)
+        .bind(run.trigger_type)
+        .bind(run.started_by)
+        .bind(run.instance_id)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(job_run)
+    }
+
+    async fn finish_run(
+        &self,
+        id: i64,
+        status: &str,
+        message: Option<&str>,
+    ) -> Result<JobRun, AppError> {
+        let job_run = sqlx::query_as::<_, JobRun>(
+            r#"
+            UPDATE job_run SET status = $1, message = $2, finished_at = CURRENT_TIMESTAMP
+            WHERE id = $3
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(message)
+        .bind(id)
+        .fetch_optional(&self.pools.write)
+        .await?
+        .ok_or_else(|| AppError::not_found("Job run not found"))?;
+        Ok(job_run)
+    }
+
+    async fn list_runs(&self, job_name: &str, limit: i64) -> Result<Vec<JobRun>, AppError> {
+        let runs = sqlx::query_as::<_, JobRun>(
+            "SELECT * FROM job_run WHERE job_name = $1 ORDER BY started_at DESC, id DESC LIMIT $2",
+        )
+        .bind(job_name)
+        .bind(limit)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(runs)
+    }
+
+    async fn delete_runs_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let result =
+            sqlx::query("DELETE FROM job_run WHERE started_at < $1 AND status <> 'running'")
+                .bind(cutoff)
+                .execute(&self.pools.write)
+                .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,903 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{HashMap, HashSet},
+    sync::{Mutex, MutexGuard},
+};
+
+use super::{
+    access_review::{AccessReviewRepository, NewAccessReview, NewAccessReviewItem},
+    job::{JobRepository, NewJobRun},
+    menu::{MenuRepository, NewMenuItem},
+    permission::{PermissionRepository, UserPermissionChange},
+    setting::SettingRepository,
+    user::{NewUser, UserRepository, USER_SORT_COLUMNS},
+    user_type::{UserTypeChanges, UserTypeRepository, USER_TYPE_SORT_COLUMNS},
+};
+use crate::{
+    dto::common::ListQueryParams,
+    errors::AppError,
+    models::{
+        AccessReview, AccessReviewItem, AdminUser, JobRun, MenuItem, Permission, SystemSetting,
+        UserPermission, UserPermissionAudit, UserType,
+    },
+};
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+
+/// Repository that keeps all rows in memory.
+///
+/// It enforces the same unique keys and user type references as the database schema,
+/// but reports violations as `AppError::Conflict`/`AppError::BadRequest` instead of
+/// database errors.
+#[derive(Default)]
+pub struct InMemoryRepository {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    users: Vec<AdminUser>,
+    user_types: Vec<UserType>,
+    permissions: Vec<Permission>,
+    menus: Vec<MenuItem>,
+    settings: Vec<SystemSetting>,
+    user_type_permissions: HashSet<(i64, i64)>, // (user_type_id, permission_id)
+    job_runs: Vec<JobRun>,
+    job_locks: HashMap<String, (String, NaiveDateTime, Option<NaiveDateTime>)>, // job_name -> (locked_by, locked_until, last_slot)
+    access_reviews: Vec<AccessReview>,
+    access_review_items: Vec<AccessReviewItem>,
+    user_permissions: Vec<UserPermission>,
+    user_permission_audit: Vec<UserPermissionAudit>,
+    last_id: i64,
+}
+
+impl State {
+    fn next_id(&mut self) -> i64 {
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // user_type_permission 매핑 추가 (테스트 데이터 구성용)
+    pub fn grant_permission(&self, user_type_id: i64, permission_id: i64) {
+        self.lock()
+            .user_type_permissions
+            .insert((user_type_id, permission_id));
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        // 다른 스레드가 패닉해도 데이터 자체는 일관된 상태이므로 그대로 사용
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+// ListQueryParams의 정렬 조건을 메모리 내 정렬에 적용하고 페이지를 잘라냄
+fn sort_and_paginate<T: Clone>(
+    mut rows: Vec<T>,
+    params: &ListQueryParams,
+    allowed_columns: &[&str],
+    compare: impl Fn(&T, &T, &str) -> Ordering,
+) -> Vec<T> {
+    let (column, descending) = params.get_sort(allowed_columns);
+    rows.sort_by(|a, b| {
+        let ordering = compare(a, b, column);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    rows.into_iter()
+        .skip(params.get_offset() as usize)
+        .take(params.get_limit() as usize)
+        .collect()
+}
+
+fn compare_users(a: &AdminUser, b: &AdminUser, column: &str) -> Ordering {
+    match column {
+        "username" => a.username.cmp(&b.username),
+        "user_type_id" => a.user_type_id.cmp(&b.user_type_id),
+        "is_active" => a.is_active.cmp(&b.is_active),
+        "last_login_at" => a.last_login_at.cmp(&b.last_login_at),
+        "created_at" => a.created_at.cmp(&b.created_at),
+        "updated_at" => a.updated_at.cmp(&b.updated_at),
+        _ => a.id.cmp(&b.id),
+    }
+}
+
+fn compare_user_types(a: &UserType, b: &UserType, column: &str) -> Ordering {
+    match column {
+        "name" => a.name.cmp(&b.name),
+        "created_at" => a.created_at.cmp(&b.created_at),
+        "updated_at" => a.updated_at.cmp(&b.updated_at),
+        _ => a.id.cmp(&b.id),
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryRepository {
+    async fn create(&self, user: NewUser) -> Result<AdminUser, AppError> {
+        let mut state = self.lock();
+        if state.users.iter().any(|u| u.username == user.username) {
+            return Err(AppError::Conflict(format!(
+                "User `{}` already exists",
+                user.username
+            )));
+        }
+        if !state.user_types.iter().any(|ut| ut.id == user.user_type_id) {
+            return Err(AppError::bad_request("User type does not exist"));
+        }
+
+        let created = AdminUser {
+            id: state.next_id(),
+            username: user.username,
+            password_hash: user.password_hash,
+            user_type_id: user.user_type_id,
+            is_active: user.is_active,
+            last_login_at: None,
+            created_at: now(),
+            updated_at: now(),
+            dormancy_warned_at: None,
+            deactivated_at: None,
+            deactivated_reason: None,
+            reactivated_at: None,
+        };
+        state.users.push(created.clone());
+        Ok(created)
+    }
+
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<AdminUser>, AppError> {
+        let search = params.q.as_deref().map(str::to_lowercase);
+        let users = self
+            .lock()
+            .users
+            .iter()
+            .filter(|u| match &search {
+                Some(q) => u.username.to_lowercase().contains(q),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        Ok(sort_and_paginate(
+            users,
+            params,
+            USER_SORT_COLUMNS,
+            compare_users,
+        ))
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<AdminUser>, AppError> {
+        Ok(self.lock().users.iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<AdminUser>, AppError> {
+        Ok(self
+            .lock()
+            .users
+            .iter()
+            .find(|u| u.username == username)
+            .cloned())
+    }
+
+    async fn update_password(&self, username: &str, password_hash: &str) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        let Some(user) = state.users.iter_mut().find(|u| u.username == username) else {
+            return Ok(false);
+        };
+        user.password_hash = password_hash.to_string();
+        user.updated_at = now();
+        Ok(true)
+    }
+
+    async fn update_user_type(&self, username: &str, user_type_id: i64) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        if !state.user_types.iter().any(|ut| ut.id == user_type_id) {
+            return Err(AppError::bad_request("User type does not exist"));
+        }
+        let Some(user) = state.users.iter_mut().find(|u| u.username == username) else {
+            return Ok(false);
+        };
+        user.user_type_id = user_type_id;
+        user.updated_at = now();
+        Ok(true)
+    }
+
+    async fn touch_last_login(&self, id: i64) -> Result<(), AppError> {
+        if let Some(user) = self.lock().users.iter_mut().find(|u| u.id == id) {
+            user.last_login_at = Some(now());
+            user.dormancy_warned_at = None;
+        }
+        Ok(())
+    }
+
+    async fn count_by_user_type(&self, user_type_id: i64) -> Result<i64, AppError> {
+        Ok(self
+            .lock()
+            .users
+            .iter()
+            .filter(|u| u.user_type_id == user_type_id)
+            .count() as i64)
+    }
+
+    async fn list_active(&self) -> Result<Vec<AdminUser>, AppError> {
+        Ok(self
+            .lock()
+            .users
+            .iter()
+            .filter(|u| u.is_active)
+            .cloned()
+            .collect())
+    }
+
+    async fn mark_dormancy_warned(&self, id: i64) -> Result<(), AppError> {
+        if let Some(user) = self.lock().users.iter_mut().find(|u| u.id == id) {
+            user.dormancy_warned_at = Some(now());
+        }
+        Ok(())
+    }
+
+    async fn deactivate(&self, id: i64, reason: &str) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        let Some(user) = state.users.iter_mut().find(|u| u.id == id && u.is_active) else {
+            return Ok(false);
+        };
+        user.is_active = false;
+        user.deactivated_at = Some(now());
+        user.deactivated_reason = Some(reason.to_string());
+        user.updated_at = now();
+        Ok(true)
+    }
+
+    async fn reactivate(&self, id: i64) -> Result<Option<AdminUser>, AppError> {
+        let mut state = self.lock();
+        let Some(user) = state.users.iter_mut().find(|u| u.id == id && !u.is_active) else {
+            return Ok(None);
+        };
+        user.is_active = true;
+        user.deactivated_at = None;
+        user.deactivated_reason = None;
+        user.dormancy_warned_at = None;
+        user.reactivated_at = Some(now());
+        user.updated_at = now();
+        Ok(Some(user.clone()))
+    }
+}
+
+#[async_trait]
+impl UserTypeRepository for InMemoryRepository {
+    async fn create(&self, name: &str, description: Option<&str>) -> Result<UserType, AppError> {
+        let mut state = self.lock();
+        if state.user_types.iter().any(|ut| ut.name == name) {
+            return Err(AppError::Conflict(format!(
+                "User type `{}` already exists",
+                name
+            )));
+        }
+
+        let created = UserType {
+            id: state.next_id(),
+            name: name.to_string(),
+            description: description.map(str::to_string),
+            created_at: now(),
+            updated_at: now(),
+        };
+        state.user_types.push(created.clone());
+        Ok(created)
+    }
+
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<UserType>, AppError> {
+        let user_types = self.lock().user_types.clone();
+        Ok(sort_and_paginate(
+            user_types,
+            params,
+            USER_TYPE_SORT_COLUMNS,
+            compare_user_types,
+        ))
+    }
+
+    async fn list_all(&self) -> Result<Vec<UserType>, AppError> {
+        Ok(self.lock().user_types.clone())
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<UserType>, AppError> {
+        Ok(self
+            .lock()
+            .user_types
+            .iter()
+            .find(|ut| ut.id == id)
+            .cloned())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<UserType>, AppError> {
+        Ok(self
+            .lock()
+            .user_types
+            .iter()
+            .find(|ut| ut.name == name)
+            .cloned())
+    }
+
+    async fn update(
+        &self,
+        id: i64,
+        changes: UserTypeChanges,
+    ) -> Result<Option<UserType>, AppError> {
+        let mut state = self.lock();
+        if let Some(name) = &changes.name {
+            if state
+                .user_types
+                .iter()
+                .any(|ut| ut.id != id && &ut.name == name)
+            {
+                return Err(AppError::Conflict(format!(
+                    "User type `{}` already exists",
+                    name
+                )));
+            }
+        }
+
+        let Some(user_type) = state.user_types.iter_mut().find(|ut| ut.id == id) else {
+            return Ok(None);
+        };
+        if let Some(name) = changes.name {
+            user_type.name = name;
+        }
+        if let Some(description) = changes.description {
+            user_type.description = Some(description);
+        }
+        user_type.updated_at = now();
+        Ok(Some(user_type.clone()))
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        // admin_user.user_type_id의 ON DELETE RESTRICT
+        if state.users.iter().any(|u| u.user_type_id == id) {
+            return Err(AppError::conflict(
+                "Cannot delete a user type: it is currently assigned to users.",
+            ));
+        }
+
+        let before = state.user_types.len();
+        state.user_types.retain(|ut| ut.id != id);
+        // user_type_permission의 ON DELETE CASCADE
+        state.user_type_permissions.retain(|(ut, _)| *ut != id);
+        Ok(state.user_types.len() < before)
+    }
+}
+
+#[async_trait]
+impl PermissionRepository for InMemoryRepository {
+    async fn create(&self, code: &str, description: Option<&str>) -> Result<Permission, AppError> {
+        let mut state = self.lock();
+        if state.permissions.iter().any(|p| p.code == code) {
+            return Err(AppError::Conflict(format!(
+                "Permission `{}` already exists",
+                code
+            )));
+        }
+
+        let created = Permission {
+            id: Some(state.next_id()),
+            code: code.to_string(),
+            description: description.map(str::to_string),
+            created_at: now(),
+            updated_at: now(),
+        };
+        state.permissions.push(created.clone());
+        Ok(created)
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Permission>, AppError> {
+        let mut permissions = self.lock().permissions.clone();
+        permissions.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(permissions
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn list_all(&self) -> Result<Vec<Permission>, AppError> {
+        let mut permissions = self.lock().permissions.clone();
+        permissions.sort_by(|a, b| a.code.cmp(&b.code));
+        Ok(permissions)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<Permission>, AppError> {
+        Ok(self
+            .lock()
+            .permissions
+            .iter()
+            .find(|p| p.id == Some(id))
+            .cloned())
+    }
+
+    async fn codes_for_user_type(&self, user_type_id: i64) -> Result<HashSet<String>, AppError> {
+        let state = self.lock();
+        Ok(state
+            .permissions
+            .iter()
+            .filter(|p| {
+                p.id.is_some_and(|id| state.user_type_permissions.contains(&(user_type_id, id)))
+            })
+            .map(|p| p.code.clone())
+            .collect())
+    }
+
+    async fn find_by_code(&self, code: &str) -> Result<Option<Permission>, AppError> {
+        Ok(self
+            .lock()
+            .permissions
+            .iter()
+            .find(|p| p.code == code)
+            .cloned())
+    }
+
+    async fn user_overrides(&self, user_id: i64) -> Result<Vec<UserPermission>, AppError> {
+        let mut overrides: Vec<_> = self
+            .lock()
+            .user_permissions
+            .iter()
+            .filter(|up| up.user_id == user_id)
+            .cloned()
+            .collect();
+        overrides.sort_by(|a, b| a.permission_code.cmp(&b.permission_code));
+        Ok(overrides)
+    }
+
+    async fn set_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+        effect: &str,
+        reason: Option<&str>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<UserPermission, AppError> {
+        let mut state = self.lock();
+        // user_permission의 외래 키
+        if !state.users.iter().any(|u| u.id == change.user_id) {
+            return Err(AppError::bad_request("User does not exist"));
+        }
+        let Some(code) = state
+            .permissions
+            .iter()
+            .find(|p| p.id == Some(change.permission_id))
+            .map(|p| p.code.clone())
+        else {
+            return Err(AppError::bad_request("Permission does not exist"));
+        };
+
+        let id =
+            match state.user_permissions.iter().position(|up| {
+                up.user_id == change.user_id && up.permission_id == change.permission_id
+            }) {
+                Some(index) => state.user_permissions.remove(index).id,
+                None => state.next_id(),
+            };
+        let user_permission = UserPermission {
+            id,
+            user_id: change.user_id,
+            permission_id: change.permission_id,
+            permission_code: code,
+            effect: effect.to_string(),
+            reason: reason.map(str::to_string),
+            expires_at,
+            granted_by: change.changed_by.to_string(),
+            granted_at: now(),
+        };
+        state.user_permissions.push(user_permission.clone());
+        push_audit(&mut state, change, "set", Some(effect), expires_at, reason);
+        Ok(user_permission)
+    }
+
+    async fn remove_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+    ) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        let Some(index) = state.user_permissions.iter().position(|up| {
+            up.user_id == change.user_id && up.permission_id == change.permission_id
+        }) else {
+            return Ok(false);
+        };
+        let removed = state.user_permissions.remove(index);
+        push_audit(
+            &mut state,
+            change,
+            "remove",
+            Some(&removed.effect),
+            removed.expires_at,
+            None,
+        );
+        Ok(true)
+    }
+
+    async fn user_override_history(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserPermissionAudit>, AppError> {
+        let mut history: Vec<_> = self
+            .lock()
+            .user_permission_audit
+            .iter()
+            .filter(|a| a.user_id == user_id)
+            .cloned()
+            .collect();
+        history.sort_by_key(|a| Reverse((a.changed_at, a.id)));
+        Ok(history)
+    }
+
+    async fn delete_override_history_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let mut state = self.lock();
+        let before = state.user_permission_audit.len();
+        state
+            .user_permission_audit
+            .retain(|a| a.changed_at >= cutoff);
+        Ok((before - state.user_permission_audit.len()) as u64)
+    }
+}
+
+fn push_audit(
+    state: &mut State,
+    change: &UserPermissionChange<'_>,
+    action: &str,
+    effect: Option<&str>,
+    expires_at: Option<NaiveDateTime>,
+    reason: Option<&str>,
+) {
+    let audit = UserPermissionAudit {
+        id: state.next_id(),
+        user_id: change.user_id,
+        username: change.username.to_string(),
+        permission_code: change.permission_code.to_string(),
+        action: action.to_string(),
+        effect: effect.map(str::to_string),
+        expires_at,
+        reason: reason.map(str::to_string),
+        changed_by: change.changed_by.to_string(),
+        changed_at: now(),
+    };
+    state.user_permission_audit.push(audit);
+}
+
+#[async_trait]
+impl MenuRepository for InMemoryRepository {
+    async fn create(&self, menu: NewMenuItem) -> Result<MenuItem, AppError> {
+        let mut state = self.lock();
+        if state.menus.iter().any(|m| m.path == menu.path) {
+            return Err(AppError::Conflict(format!(
+                "Menu `{}` already exists",
+                menu.path
+            )));
+        }
+        if let Some(parent_id) = menu.parent_id {
+            if !state.menus.iter().any(|m| m.id == parent_id) {
+                return Err(AppError::bad_request("Parent menu does not exist"));
+            }
+        }
+
+        let created = MenuItem {
+            id: state.next_id(),
+            name: menu.name,
+            path: menu.path,
+            icon: menu.icon,
+            parent_id: menu.parent_id,
+            display_order: menu.display_order,
+            is_visible: menu.is_visible,
+            created_at: now(),
+            updated_at: now(),
+        };
+        state.menus.push(created.clone());
+        Ok(created)
+    }
+
+    async fn list_all(&self) -> Result<Vec<MenuItem>, AppError> {
+        let mut menus = self.lock().menus.clone();
+        menus.sort_by_key(|m| m.display_order);
+        Ok(menus)
+    }
+}
+
+#[async_trait]
+impl SettingRepository for InMemoryRepository {
+    async fn list(&self) -> Result<Vec<SystemSetting>, AppError> {
+        let mut settings = self.lock().settings.clone();
+        settings.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(settings)
+    }
+
+    async fn upsert(
+        &self,
+        key: &str,
+        value: &str,
+        updated_by: Option<i64>,
+    ) -> Result<SystemSetting, AppError> {
+        let mut state = self.lock();
+        let setting = SystemSetting {
+            key: key.to_string(),
+            value: value.to_string(),
+            updated_by,
+            updated_at: now(),
+        };
+        state.settings.retain(|s| s.key != key);
+        state.settings.push(setting.clone());
+        Ok(setting)
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        let before = state.settings.len();
+        state.settings.retain(|s| s.key != key);
+        Ok(state.settings.len() < before)
+    }
+}
+
+#[async_trait]
+impl JobRepository for InMemoryRepository {
+    async fn try_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        now: NaiveDateTime,
+        until: NaiveDateTime,
+        slot: Option<NaiveDateTime>,
+    ) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        let last_slot = state.job_locks.get(job_name).and_then(|lock| lock.2);
+        if state
+            .job_locks
+            .get(job_name)
+            .is_some_and(|lock| lock.1 >= now)
+            || slot.is_some_and(|slot| last_slot.is_some_and(|last| last >= slot))
+        {
+            return Ok(false);
+        }
+        state.job_locks.insert(
+            job_name.to_string(),
+            (owner.to_string(), until, slot.or(last_slot)),
+        );
+        Ok(true)
+    }
+
+    async fn extend_lock(
+        &self,
+        job_name: &str,
+        owner: &str,
+        until: NaiveDateTime,
+    ) -> Result<bool, AppError> {
+        let mut state = self.lock();
+        match state
+            .job_locks
+            .get_mut(job_name)
+            .filter(|lock| lock.0 == owner)
+        {
+            Some(lock) => {
+                lock.1 = until;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn unlock(&self, job_name: &str, owner: &str) -> Result<(), AppError> {
+        let mut state = self.lock();
+        if let Some(lock) = state
+            .job_locks
+            .get_mut(job_name)
+            .filter(|lock| lock.0 == owner)
+        {
+            lock.1 = NaiveDateTime::MIN;
+        }
+        Ok(())
+    }
+
+    async fn start_run(&self, run: &NewJobRun<'_>) -> Result<JobRun, AppError> {
+        let mut state = self.lock();
+        let job_run = JobRun {
+            id: state.next_id(),
+            job_name: run.job_name.to_string(),
+            trigger_type: run.trigger_type.to_string(),
+            status: "running".to_string(),
+            message: None,
+            started_by: run.started_by,
+            instance_id: run.instance_id.to_string(),
+            started_at: now(),
+            finished_at: None,
+        };
+        state.job_runs.push(job_run.clone());
+        Ok(job_run)
+    }
+
+    async fn finish_run(
+        &self,
+        id: i64,
+        status: &str,
+        message: Option<&str>,
+    ) -> Result<JobRun, AppError> {
+        let mut state = self.lock();
+        let job_run = state
+            .job_runs
+            .iter_mut()
+            .find(|r| r.id == id)
+            .ok_or_else(|| AppError::not_found("Job run not found"))?;
+        job_run.status = status.to_string();
+        job_run.message = message.map(str::to_string);
+        job_run.finished_at = Some(now());
+        Ok(job_run.clone())
+    }
+
+    async fn list_runs(&self, job_name: &str, limit: i64) -> Result<Vec<JobRun>, AppError> {
+        let mut runs: Vec<_> = self
+            .lock()
+            .job_runs
+            .iter()
+            .filter(|r| r.job_name == job_name)
+            .cloned()
+            .collect();
+        runs.sort_by_key(|r| Reverse((r.started_at, r.id)));
+        runs.truncate(limit.max(0) as usize);
+        Ok(runs)
+    }
+
+    async fn delete_runs_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let mut state = self.lock();
+        let before = state.job_runs.len();
+        state
+            .job_runs
+            .retain(|r| r.started_at >= cutoff || r.status == "running");
+        Ok((before - state.job_runs.len()) as u64)
+    }
+}
+
+#[async_trait]
+impl AccessReviewRepository for InMemoryRepository {
+    async fn create_review(
+        &self,
+        review: NewAccessReview,
+        items: Vec<NewAccessReviewItem>,
+    ) -> Result<AccessReview, AppError> {
+        let mut state = self.lock();
+        let review = AccessReview {
+            id: state.next_id(),
+            name: review.name,
+            status: "open".to_string(),
+            user_types: review.user_types,
+            created_by: review.created_by,
+            due_at: review.due_at,
+            created_at: now(),
+            closed_by: None,
+            closed_at: None,
+        };
+        for item in items {
+            let item = AccessReviewItem {
+                id: state.next_id(),
+                review_id: review.id,
+                user_id: Some(item.user_id),
+                username: item.username,
+                user_type_id: item.user_type_id,
+                user_type_name: item.user_type_name,
+                permissions: item.permissions,
+                decision: "pending".to_string(),
+                comment: None,
+                decided_by: None,
+                decided_at: None,
+                applied_at: None,
+            };
+            state.access_review_items.push(item);
+        }
+        state.access_reviews.push(review.clone());
+        Ok(review)
+    }
+
+    async fn list_reviews(&self) -> Result<Vec<AccessReview>, AppError> {
+        let mut reviews = self.lock().access_reviews.clone();
+        reviews.sort_by_key(|r| Reverse((r.created_at, r.id)));
+        Ok(reviews)
+    }
+
+    async fn find_review(&self, id: i64) -> Result<Option<AccessReview>, AppError> {
+        Ok(self
+            .lock()
+            .access_reviews
+            .iter()
+            .find(|r| r.id == id)
+            .cloned())
+    }
+
+    async fn list_items(&self, review_id: i64) -> Result<Vec<AccessReviewItem>, AppError> {
+        let mut items: Vec<_> = self
+            .lock()
+            .access_review_items
+            .iter()
+            .filter(|i| i.review_id == review_id)
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| a.username.cmp(&b.username).then(a.id.cmp(&b.id)));
+        Ok(items)
+    }
+
+    async fn decide_item(
+        &self,
+        review_id: i64,
+        item_id: i64,
+        decision: &str,
+        comment: Option<&str>,
+        decided_by: &str,
+    ) -> Result<Option<AccessReviewItem>, AppError> {
+        let mut state = self.lock();
+        if !state
+            .access_reviews
+            .iter()
+            .any(|r| r.id == review_id && r.status == "open")
+        {
+            return Ok(None);
+        }
+        let Some(item) = state
+            .access_review_items
+            .iter_mut()
+            .find(|i| i.id == item_id && i.review_id == review_id && i.applied_at.is_none())
+        else {
+            return Ok(None);
+        };
+        item.decision = decision.to_string();
+        item.comment = comment.map(str::to_string);
+        item.decided_by = Some(decided_by.to_string());
+        item.decided_at = Some(now());
+        Ok(Some(item.clone()))
+    }
+
+    async fn mark_item_applied(&self, item_id: i64) -> Result<(), AppError> {
+        if let Some(item) = self
+            .lock()
+            .access_review_items
+            .iter_mut()
+            .find(|i| i.id == item_id)
+        {
+            item.applied_at = Some(now());
+        }
+        Ok(())
+    }
+
+    async fn close_review(
+        &self,
+        id: i64,
+        closed_by: &str,
+    ) -> Result<Option<AccessReview>, AppError> {
+        let mut state = self.lock();
+        let Some(review) = state
+            .access_reviews
+            .iter_mut()
+            .find(|r| r.id == id && r.status == "open")
+        else {
+            return Ok(None);
+        };
+        review.status = "closed".to_string();
+        review.closed_by = Some(closed_by.to_string());
+        review.closed_at = Some(now());
+        Ok(Some(review.clone()))
+    }
+
+    async fn delete_closed_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let mut state = self.lock();
+        let expired: Vec<_> = state
+            .access_reviews
+            .iter()
+            .filter(|r| r.status == "closed" && r.closed_at.is_some_and(|t| t < cutoff))
+            .map(|r| r.id)
+            .collect();
+        state.access_reviews.retain(|r| !expired.contains(&r.id));
+        state
+            .access_review_items
+            .retain(|i| !expired.contains(&i.review_id));
+        Ok(expired.len() as u64)
+    }
+}
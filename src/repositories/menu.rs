@@ -0,0 +1,50 @@
+use super::SqlxRepository;
+use crate::{errors::AppError, models::MenuItem};
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct NewMenuItem {
+    pub name: String,
+    pub path: String,
+    pub icon: Option<String>,
+    pub parent_id: Option<i64>,
+    pub display_order: i64,
+    pub is_visible: bool,
+}
+
+#[async_trait]
+pub trait MenuRepository: Send + Sync {
+    async fn create(&self, menu: NewMenuItem) -> Result<MenuItem, AppError>;
+    // display_order 순 정렬
+    async fn list_all(&self) -> Result<Vec<MenuItem>, AppError>;
+}
+
+#[async_trait]
+impl MenuRepository for SqlxRepository {
+    async fn create(&self, menu: NewMenuItem) -> Result<MenuItem, AppError> {
+        let menu = sqlx::query_as::<_, MenuItem>(
+            r#"
+            INSERT INTO menu_item (name, path, icon, parent_id, display_order, is_visible, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            RETURNING *
+            "#,
+        )
+        .bind(&menu.name)
+        .bind(&menu.path)
+        .bind(&menu.icon)
+        .bind(menu.parent_id)
+        .bind(menu.display_order)
+        .bind(menu.is_visible)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(menu)
+    }
+
+    async fn list_all(&self) -> Result<Vec<MenuItem>, AppError> {
+        let menus =
+            sqlx::query_as::<_, MenuItem>("SELECT * FROM menu_item ORDER BY display_order ASC")
+                .fetch_all(&self.pools.read)
+                .await?;
+        Ok(menus)
+    }
+}
@@ -0,0 +1,77 @@
+//! Persistence layer used by the services.
+//!
+//! Each domain has a repository trait with two implementations: [`SqlxRepository`], backed by
+//! the configured database, and [`InMemoryRepository`], which keeps everything in memory so
+//! service logic can be exercised without actix or a database. Bulk operations that depend on
+//! transactions and streaming (import/export, snapshots, first-run setup) still use the pool
+//! directly.
+
+pub mod access_review;
+pub mod job;
+pub mod memory;
+pub mod menu;
+pub mod permission;
+pub mod setting;
+pub mod user;
+pub mod user_type;
+
+pub use access_review::{AccessReviewRepository, NewAccessReview, NewAccessReviewItem};
+pub use job::{JobRepository, NewJobRun};
+pub use memory::InMemoryRepository;
+pub use menu::{MenuRepository, NewMenuItem};
+pub use permission::{PermissionRepository, UserPermissionChange};
+pub use setting::SettingRepository;
+pub use user::{NewUser, UserRepository};
+pub use user_type::{UserTypeChanges, UserTypeRepository};
+
+use crate::config::db::DbPools;
+use actix_web::web;
+use std::sync::Arc;
+
+// 모든 저장소 trait을 구현하는 sqlx 기반 저장소 (조회는 읽기 풀, 변경은 쓰기 풀 사용)
+#[derive(Clone)]
+pub struct SqlxRepository {
+    pools: DbPools,
+}
+
+impl SqlxRepository {
+    pub fn new(pools: DbPools) -> Self {
+        Self { pools }
+    }
+}
+
+/// Registers `repo` as `web::Data<dyn XxxRepository>` for every repository trait,
+/// which is how handlers and the authentication middleware receive them.
+pub fn register<R>(cfg: &mut web::ServiceConfig, repo: Arc<R>)
+where
+    R: UserRepository
+        + UserTypeRepository
+        + PermissionRepository
+        + MenuRepository
+        + SettingRepository
+        + JobRepository
+        + AccessReviewRepository
+        + 'static,
+{
+    cfg.app_data(web::Data::<dyn UserRepository>::from(
+        repo.clone() as Arc<dyn UserRepository>
+    ))
+    .app_data(web::Data::<dyn UserTypeRepository>::from(
+        repo.clone() as Arc<dyn UserTypeRepository>
+    ))
+    .app_data(web::Data::<dyn PermissionRepository>::from(
+        repo.clone() as Arc<dyn PermissionRepository>
+    ))
+    .app_data(web::Data::<dyn MenuRepository>::from(
+        repo.clone() as Arc<dyn MenuRepository>
+    ))
+    .app_data(web::Data::<dyn SettingRepository>::from(
+        repo.clone() as Arc<dyn SettingRepository>
+    ))
+    .app_data(web::Data::<dyn JobRepository>::from(
+        repo.clone() as Arc<dyn JobRepository>
+    ))
+    .app_data(web::Data::<dyn AccessReviewRepository>::from(
+        repo as Arc<dyn AccessReviewRepository>,
+    ));
+}
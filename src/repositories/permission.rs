@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use super::SqlxRepository;
+use crate::{
+    config::db::DbTransaction,
+    errors::AppError,
+    models::{Permission, UserPermission, UserPermissionAudit},
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+// 사용자별 권한 예외 변경 대상과 변경자 (이력에 그대로 기록)
+pub struct UserPermissionChange<'a> {
+    pub user_id: i64,
+    pub username: &'a str,
+    pub permission_id: i64,
+    pub permission_code: &'a str,
+    pub changed_by: &'a str,
+}
+
+#[async_trait]
+pub trait PermissionRepository: Send + Sync {
+    async fn create(&self, code: &str, description: Option<&str>) -> Result<Permission, AppError>;
+    // code 순 정렬
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Permission>, AppError>;
+    async fn list_all(&self) -> Result<Vec<Permission>, AppError>;
+    async fn find_by_id(&self, id: i64) -> Result<Option<Permission>, AppError>;
+    async fn find_by_code(&self, code: &str) -> Result<Option<Permission>, AppError>;
+    // 사용자 종류에 부여된 권한 코드 (인증 미들웨어에서 사용)
+    async fn codes_for_user_type(&self, user_type_id: i64) -> Result<HashSet<String>, AppError>;
+    // 사용자별 권한 예외 (만료된 항목 포함, code 순 정렬)
+    async fn user_overrides(&self, user_id: i64) -> Result<Vec<UserPermission>, AppError>;
+    // 예외를 추가하거나 덮어쓰고 같은 트랜잭션에서 이력 기록
+    async fn set_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+        effect: &str,
+        reason: Option<&str>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<UserPermission, AppError>;
+    // 대상이 없으면 false (이력도 남기지 않음)
+    async fn remove_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+    ) -> Result<bool, AppError>;
+    // 최신순
+    async fn user_override_history(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserPermissionAudit>, AppError>;
+    // 보관 기간이 지난 변경 이력 삭제
+    async fn delete_override_history_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError>;
+}
+
+const USER_OVERRIDE_QUERY: &str = r#"
+    SELECT up.*, p.code AS permission_code
+    FROM user_permission up
+    JOIN permission p ON p.id = up.permission_id
+"#;
+
+#[async_trait]
+impl PermissionRepository for SqlxRepository {
+    async fn create(&self, code: &str, description: Option<&str>) -> Result<Permission, AppError> {
+        let permission = sqlx::query_as::<_, Permission>(
+            "INSERT INTO permission (code, description) VALUES ($1, $2) RETURNING *",
+        )
+        .bind(code)
+        .bind(description)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(permission)
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Permission>, AppError> {
+        let permissions = sqlx::query_as::<_, Permission>(
+            "SELECT * FROM permission ORDER BY code LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(permissions)
+    }
+
+    async fn list_all(&self) -> Result<Vec<Permission>, AppError> {
+        let permissions = sqlx::query_as::<_, Permission>("SELECT * FROM permission ORDER BY code")
+            .fetch_all(&self.pools.read)
+            .await?;
+        Ok(permissions)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<Permission>, AppError> {
+        let permission = sqlx::query_as::<_, Permission>("SELECT * FROM permission WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(permission)
+    }
+
+    async fn codes_for_user_type(&self, user_type_id: i64) -> Result<HashSet<String>, AppError> {
+        let codes = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT p.code
+            FROM permission p
+            JOIN user_type_permission utp ON p.id = utp.permission_id
+            WHERE utp.user_type_id = $1
+            LIMIT 1000
+            "#,
+        )
+        .bind(user_type_id)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(codes.into_iter().collect())
+    }
+
+    async fn find_by_code(&self, code: &str) -> Result<Option<Permission>, AppError> {
+        let permission =
+            sqlx::query_as::<_, Permission>("SELECT * FROM permission WHERE code = $1")
+                .bind(code)
+                .fetch_optional(&self.pools.read)
+                .await?;
+        Ok(permission)
+    }
+
+    async fn user_overrides(&self, user_id: i64) -> Result<Vec<UserPermission>, AppError> {
+        let overrides = sqlx::query_as::<_, UserPermission>(&format!(
+            "{} WHERE up.user_id = $1 ORDER BY p.code",
+            USER_OVERRIDE_QUERY
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(overrides)
+    }
+
+    async fn set_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+        effect: &str,
+        reason: Option<&str>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<UserPermission, AppError> {
+        let mut tx = self.pools.write.begin().await?;
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO user_permission
+                (user_id, permission_id, effect, reason, expires_at, granted_by, granted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP)
+            ON CONFLICT (user_id, permission_id) DO UPDATE
+                SET effect = excluded.effect, reason = excluded.reason,
+                    expires_at = excluded.expires_at, granted_by = excluded.granted_by,
+                    granted_at = excluded.granted_at
+            RETURNING id
+            "#,
+        )
+        .bind(change.user_id)
+        .bind(change.permission_id)
+        .bind(effect)
+        .bind(reason)
+        .bind(expires_at)
+        .bind(change.changed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        insert_audit(&mut tx, change, "set", Some(effect), expires_at, reason).await?;
+        let user_permission = sqlx::query_as::<_, UserPermission>(&format!(
+            "{} WHERE up.id = $1",
+            USER_OVERRIDE_QUERY
+        ))
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(user_permission)
+    }
+
+    async fn remove_user_override(
+        &self,
+        change: &UserPermissionChange<'_>,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.pools.write.begin().await?;
+        let removed = sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(
+            r#"
+            DELETE FROM user_permission WHERE user_id = $1 AND permission_id = $2
+            RETURNING effect, expires_at
+            "#,
+        )
+        .bind(change.user_id)
+        .bind(change.permission_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((effect, expires_at)) = removed else {
+            return Ok(false);
+        };
+        insert_audit(&mut tx, change, "remove", Some(&effect), expires_at, None).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn user_override_history(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserPermissionAudit>, AppError> {
+        let history = sqlx::query_as::<_, UserPermissionAudit>(
+            "SELECT * FROM user_permission_audit WHERE user_id = $1 ORDER BY changed_at DESC, id DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pools.read)
+        .await?;
+        Ok(history)
+    }
+
+    async fn delete_override_history_before(&self, cutoff: NaiveDateTime) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM user_permission_audit WHERE changed_at < $1")
+            .bind(cutoff)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+async fn insert_audit(
+    tx: &mut DbTransaction<'_>,
+    change: &UserPermissionChange<'_>,
+    action: &str,
+    effect: Option<&str>,
+    expires_at: Option<NaiveDateTime>,
+    reason: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO user_permission_audit
+            (user_id, username, permission_code, action, effect, expires_at, reason, changed_by, changed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CURRENT_TIMESTAMP)
+        "#,
+    )
+    .bind(change.user_id)
+    .bind(change.username)
+    .bind(change.permission_code)
+    .bind(action)
+    .bind(effect)
+    .bind(expires_at)
+    .bind(reason)
+    .bind(change.changed_by)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
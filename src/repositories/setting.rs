@@ -0,0 +1,58 @@
+use super::SqlxRepository;
+use crate::{errors::AppError, models::SystemSetting};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait SettingRepository: Send + Sync {
+    async fn list(&self) -> Result<Vec<SystemSetting>, AppError>;
+    // 없으면 추가, 있으면 값 교체
+    async fn upsert(
+        &self,
+        key: &str,
+        value: &str,
+        updated_by: Option<i64>,
+    ) -> Result<SystemSetting, AppError>;
+    async fn delete(&self, key: &str) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+impl SettingRepository for SqlxRepository {
+    async fn list(&self) -> Result<Vec<SystemSetting>, AppError> {
+        let settings =
+            sqlx::query_as::<_, SystemSetting>("SELECT * FROM system_setting ORDER BY key")
+                .fetch_all(&self.pools.read)
+                .await?;
+        Ok(settings)
+    }
+
+    async fn upsert(
+        &self,
+        key: &str,
+        value: &str,
+        updated_by: Option<i64>,
+    ) -> Result<SystemSetting, AppError> {
+        let setting = sqlx::query_as::<_, SystemSetting>(
+            r#"
+            INSERT INTO system_setting (key, value, updated_by, updated_at)
+            VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+            ON CONFLICT (key) DO UPDATE
+                SET value = excluded.value, updated_by = excluded.updated_by, updated_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .bind(updated_by)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(setting)
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM system_setting WHERE key = $1")
+            .bind(key)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
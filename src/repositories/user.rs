@@ -0,0 +1,208 @@
+use super::SqlxRepository;
+use crate::{
+    config::db::DbArguments, dto::common::ListQueryParams, errors::AppError, models::AdminUser,
+};
+use async_trait::async_trait;
+use sqlx::Arguments;
+
+// 목록 정렬에 허용되는 컬럼
+pub const USER_SORT_COLUMNS: &[&str] = &[
+    "id",
+    "username",
+    "user_type_id",
+    "is_active",
+    "last_login_at",
+    "created_at",
+    "updated_at",
+];
+
+#[derive(Debug, Clone)]
+pub struct NewUser {
+    pub username: String,
+    pub password_hash: String,
+    pub user_type_id: i64,
+    pub is_active: bool,
+}
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn create(&self, user: NewUser) -> Result<AdminUser, AppError>;
+    // q(사용자 이름 부분 일치), 정렬, 페이지네이션 적용
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<AdminUser>, AppError>;
+    async fn find_by_id(&self, id: i64) -> Result<Option<AdminUser>, AppError>;
+    async fn find_by_username(&self, username: &str) -> Result<Option<AdminUser>, AppError>;
+    // 대상 사용자가 없으면 false
+    async fn update_password(&self, username: &str, password_hash: &str) -> Result<bool, AppError>;
+    async fn update_user_type(&self, username: &str, user_type_id: i64) -> Result<bool, AppError>;
+    // 로그인 시각 기록 (장기 미사용 경고도 초기화)
+    async fn touch_last_login(&self, id: i64) -> Result<(), AppError>;
+    async fn count_by_user_type(&self, user_type_id: i64) -> Result<i64, AppError>;
+    async fn list_active(&self) -> Result<Vec<AdminUser>, AppError>;
+    async fn mark_dormancy_warned(&self, id: i64) -> Result<(), AppError>;
+    // 활성 사용자만 비활성화하고 사유 기록 (대상이 아니면 false)
+    async fn deactivate(&self, id: i64, reason: &str) -> Result<bool, AppError>;
+    // 비활성 사용자를 다시 활성화하고 사유/경고 초기화 (대상이 아니면 None)
+    async fn reactivate(&self, id: i64) -> Result<Option<AdminUser>, AppError>;
+}
+
+#[async_trait]
+impl UserRepository for SqlxRepository {
+    async fn create(&self, user: NewUser) -> Result<AdminUser, AppError> {
+        let user = sqlx::query_as::<_, AdminUser>(
+            "INSERT INTO admin_user (username, password_hash, user_type_id, is_active) VALUES ($1, $2, $3, $4) RETURNING *",
+        )
+        .bind(&user.username)
+        .bind(&user.password_hash)
+        .bind(user.user_type_id)
+        .bind(user.is_active)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(user)
+    }
+
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<AdminUser>, AppError> {
+        let (filter_query, mut args) = build_user_filter_query(params)?;
+        args.add(params.get_limit()).map_err(sqlx::Error::Encode)?;
+        args.add(params.get_offset()).map_err(sqlx::Error::Encode)?;
+        let query_str = format!(
+            "{} LIMIT ${} OFFSET ${}",
+            filter_query,
+            args.len() - 1,
+            args.len()
+        );
+
+        let users = sqlx::query_as_with::<_, AdminUser, _>(&query_str, args)
+            .fetch_all(&self.pools.read)
+            .await?;
+        Ok(users)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<AdminUser>, AppError> {
+        let user = sqlx::query_as::<_, AdminUser>("SELECT * FROM admin_user WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(user)
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<AdminUser>, AppError> {
+        let user = sqlx::query_as::<_, AdminUser>("SELECT * FROM admin_user WHERE username = $1")
+            .bind(username)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(user)
+    }
+
+    async fn update_password(&self, username: &str, password_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("UPDATE admin_user SET password_hash = $1 WHERE username = $2")
+            .bind(password_hash)
+            .bind(username)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_user_type(&self, username: &str, user_type_id: i64) -> Result<bool, AppError> {
+        let result = sqlx::query("UPDATE admin_user SET user_type_id = $1 WHERE username = $2")
+            .bind(user_type_id)
+            .bind(username)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn touch_last_login(&self, id: i64) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE admin_user SET last_login_at = CURRENT_TIMESTAMP, dormancy_warned_at = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pools.write)
+        .await?;
+        Ok(())
+    }
+
+    async fn count_by_user_type(&self, user_type_id: i64) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar("SELECT COUNT(1) FROM admin_user WHERE user_type_id = $1")
+            .bind(user_type_id)
+            .fetch_one(&self.pools.read)
+            .await?;
+        Ok(count)
+    }
+
+    async fn list_active(&self) -> Result<Vec<AdminUser>, AppError> {
+        let users =
+            sqlx::query_as::<_, AdminUser>("SELECT * FROM admin_user WHERE is_active ORDER BY id")
+                .fetch_all(&self.pools.read)
+                .await?;
+        Ok(users)
+    }
+
+    async fn mark_dormancy_warned(&self, id: i64) -> Result<(), AppError> {
+        sqlx::query("UPDATE admin_user SET dormancy_warned_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(id)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(())
+    }
+
+    async fn deactivate(&self, id: i64, reason: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE admin_user
+            SET is_active = FALSE, deactivated_at = CURRENT_TIMESTAMP, deactivated_reason = $1,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $2 AND is_active
+            "#,
+        )
+        .bind(reason)
+        .bind(id)
+        .execute(&self.pools.write)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn reactivate(&self, id: i64) -> Result<Option<AdminUser>, AppError> {
+        let user = sqlx::query_as::<_, AdminUser>(
+            r#"
+            UPDATE admin_user
+            SET is_active = TRUE, deactivated_at = NULL, deactivated_reason = NULL,
+                dormancy_warned_at = NULL, reactivated_at = CURRENT_TIMESTAMP,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND NOT is_active
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pools.write)
+        .await?;
+        Ok(user)
+    }
+}
+
+/// Builds the search/sort part of the user list query, shared by the list endpoint and the
+/// streaming export. Pagination is left to the caller.
+pub fn build_user_filter_query(
+    query_params: &ListQueryParams,
+) -> Result<(String, DbArguments), AppError> {
+    let order_by = query_params.get_order_by(USER_SORT_COLUMNS);
+
+    let base_query = "SELECT * FROM admin_user";
+    let mut conditions = Vec::new();
+    let mut args = DbArguments::default();
+
+    if let Some(search_term) = &query_params.q {
+        // 백엔드 간 동작을 맞추기 위해 대소문자 구분 없이 비교
+        args.add(format!("%{}%", search_term.to_lowercase()))
+            .map_err(sqlx::Error::Encode)?;
+        conditions.push(format!("LOWER(username) LIKE ${}", args.len()));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        "".to_string()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let query_str = format!("{} {} ORDER BY {}", base_query, where_clause, order_by);
+    Ok((query_str, args))
+}
@@ -0,0 +1,124 @@
+use super::SqlxRepository;
+use crate::{
+    config::db::DbArguments, dto::common::ListQueryParams, errors::AppError, models::UserType,
+};
+use async_trait::async_trait;
+use sqlx::Arguments;
+
+pub const USER_TYPE_SORT_COLUMNS: &[&str] = &["id", "name", "created_at", "updated_at"];
+
+// 변경할 필드만 Some
+#[derive(Debug, Clone, Default)]
+pub struct UserTypeChanges {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl UserTypeChanges {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none()
+    }
+}
+
+#[async_trait]
+pub trait UserTypeRepository: Send + Sync {
+    async fn create(&self, name: &str, description: Option<&str>) -> Result<UserType, AppError>;
+    // 정렬, 페이지네이션 적용
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<UserType>, AppError>;
+    async fn list_all(&self) -> Result<Vec<UserType>, AppError>;
+    async fn find_by_id(&self, id: i64) -> Result<Option<UserType>, AppError>;
+    async fn find_by_name(&self, name: &str) -> Result<Option<UserType>, AppError>;
+    // 대상이 없으면 None
+    async fn update(&self, id: i64, changes: UserTypeChanges)
+        -> Result<Option<UserType>, AppError>;
+    // 대상이 없으면 false
+    async fn delete(&self, id: i64) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+impl UserTypeRepository for SqlxRepository {
+    async fn create(&self, name: &str, description: Option<&str>) -> Result<UserType, AppError> {
+        let user_type = sqlx::query_as::<_, UserType>(
+            "INSERT INTO user_type (name, description) VALUES ($1, $2) RETURNING *",
+        )
+        .bind(name)
+        .bind(description)
+        .fetch_one(&self.pools.write)
+        .await?;
+        Ok(user_type)
+    }
+
+    async fn list(&self, params: &ListQueryParams) -> Result<Vec<UserType>, AppError> {
+        let query_str = format!(
+            "SELECT * FROM user_type ORDER BY {} LIMIT $1 OFFSET $2",
+            params.get_order_by(USER_TYPE_SORT_COLUMNS)
+        );
+        let user_types = sqlx::query_as::<_, UserType>(&query_str)
+            .bind(params.get_limit())
+            .bind(params.get_offset())
+            .fetch_all(&self.pools.read)
+            .await?;
+        Ok(user_types)
+    }
+
+    async fn list_all(&self) -> Result<Vec<UserType>, AppError> {
+        let user_types = sqlx::query_as::<_, UserType>("SELECT * FROM user_type ORDER BY id")
+            .fetch_all(&self.pools.read)
+            .await?;
+        Ok(user_types)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<UserType>, AppError> {
+        let user_type = sqlx::query_as::<_, UserType>("SELECT * FROM user_type WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(user_type)
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<UserType>, AppError> {
+        let user_type = sqlx::query_as::<_, UserType>("SELECT * FROM user_type WHERE name = $1")
+            .bind(name)
+            .fetch_optional(&self.pools.read)
+            .await?;
+        Ok(user_type)
+    }
+
+    async fn update(
+        &self,
+        id: i64,
+        changes: UserTypeChanges,
+    ) -> Result<Option<UserType>, AppError> {
+        let mut set_clauses = Vec::new();
+        let mut args = DbArguments::default();
+        if let Some(name) = changes.name {
+            args.add(name).map_err(sqlx::Error::Encode)?;
+            set_clauses.push(format!("name = ${}", args.len()));
+        }
+        if let Some(description) = changes.description {
+            args.add(description).map_err(sqlx::Error::Encode)?;
+            set_clauses.push(format!("description = ${}", args.len()));
+        }
+        set_clauses.push("updated_at = CURRENT_TIMESTAMP".to_string());
+
+        args.add(id).map_err(sqlx::Error::Encode)?;
+        let query_str = format!(
+            "UPDATE user_type SET {} WHERE id = ${} RETURNING *",
+            set_clauses.join(", "),
+            args.len()
+        );
+
+        let user_type = sqlx::query_as_with::<_, UserType, _>(&query_str, args)
+            .fetch_optional(&self.pools.write)
+            .await?;
+        Ok(user_type)
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM user_type WHERE id = $1")
+            .bind(id)
+            .execute(&self.pools.write)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
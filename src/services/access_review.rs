@@ -0,0 +1,350 @@
+//! Periodic access review (recertification) campaigns.
+//!
+//! Starting a campaign snapshots every active user of the selected user types together with
+//! their effective permissions at that moment (user type grants plus per-user overrides).
+//! Reviewers then mark each item `keep` or `revoke`; closing the campaign requires every item
+//! to be decided and, for every revoked item, adds a direct `deny` override of each reviewed
+//! permission (recorded in the user permission audit trail) before the campaign is marked
+//! closed. Closed campaigns are kept for `scheduler.audit_retention_days` (forever by default)
+//! so they can be exported as evidence.
+//!
+//! The wildcard permission cannot be overridden per user, so holders of `*` can only be kept;
+//! their access is removed by changing their user type.
+//!
+//! Reviewers need the `access_review:manage` permission (or `*`) and cannot decide on their
+//! own access.
+
+use crate::{
+    config::db::{DbArguments, DbPool},
+    dto::{
+        access_review::{
+            AccessReviewDetailResponse, AccessReviewEvidence, AccessReviewItemResponse,
+            AccessReviewResponse, CreateAccessReviewRequest, DecideAccessReviewItemRequest,
+        },
+        transfer::TransferFormat,
+    },
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    models::{AccessReview, AccessReviewItem},
+    repositories::{
+        AccessReviewRepository, NewAccessReview, NewAccessReviewItem, PermissionRepository,
+        UserPermissionChange, UserRepository, UserTypeRepository,
+    },
+    services::{authz, transfer},
+};
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use sqlx::Arguments;
+use validator::Validate;
+
+pub const ACCESS_REVIEW_PERMISSION: &str = "access_review:manage";
+
+const STATUS_OPEN: &str = "open";
+const DECISION_PENDING: &str = "pending";
+const DECISION_REVOKE: &str = "revoke";
+
+const EVIDENCE_QUERY: &str = r#"
+    SELECT r.id AS review_id, r.name AS review_name, r.status AS review_status,
+           r.created_by AS review_created_by, r.created_at AS review_created_at,
+           r.closed_by AS review_closed_by, r.closed_at AS review_closed_at,
+           i.id AS item_id, i.user_id, i.username, i.user_type_name, i.permissions,
+           i.decision, i.comment, i.decided_by, i.decided_at, i.applied_at
+    FROM access_review_item i
+    JOIN access_review r ON r.id = i.review_id
+    WHERE r.id = $1
+    ORDER BY i.username ASC, i.id ASC
+"#;
+
+pub fn ensure_reviewer(user: &AuthenticatedUser) -> Result<(), AppError> {
+    if authz::is_granted(&user.permissions, ACCESS_REVIEW_PERMISSION) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("Access review permission required"))
+    }
+}
+
+// 스냅샷 중 허용된 권한 (이미 거부된 `!code` 제외)
+fn granted_codes(item: &AccessReviewItem) -> Vec<String> {
+    serde_json::from_str::<Vec<String>>(&item.permissions)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|code| !code.starts_with(authz::DENY_PREFIX))
+        .collect()
+}
+
+fn holds_wildcard(item: &AccessReviewItem) -> bool {
+    granted_codes(item)
+        .iter()
+        .any(|code| code == authz::WILDCARD)
+}
+
+async fn find_review(
+    reviews: &dyn AccessReviewRepository,
+    id: i64,
+) -> Result<AccessReview, AppError> {
+    reviews
+        .find_review(id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Access review not found"))
+}
+
+async fn detail(
+    reviews: &dyn AccessReviewRepository,
+    review: AccessReview,
+) -> Result<AccessReviewDetailResponse, AppError> {
+    let items = reviews.list_items(review.id).await?;
+    Ok(AccessReviewDetailResponse {
+        review: AccessReviewResponse::new(review, &items),
+        items: items
+            .into_iter()
+            .map(AccessReviewItemResponse::from)
+            .collect(),
+    })
+}
+
+/// Opens a campaign over the active users of `req.user_type_ids`.
+pub async fn start_review(
+    reviews: &dyn AccessReviewRepository,
+    users: &dyn UserRepository,
+    user_types: &dyn UserTypeRepository,
+    permissions: &dyn PermissionRepository,
+    reviewer: &AuthenticatedUser,
+    req: CreateAccessReviewRequest,
+) -> Result<AccessReviewDetailResponse, AppError> {
+    req.validate()?;
+
+    let mut selected = Vec::with_capacity(req.user_type_ids.len());
+    for id in &req.user_type_ids {
+        if selected.iter().any(|(user_type, _)| user_type == id) {
+            continue;
+        }
+        let user_type = user_types
+            .find_by_id(*id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("User type {} does not exist", id)))?;
+        let codes = permissions.codes_for_user_type(user_type.id).await?;
+        selected.push((user_type.id, (user_type.name, codes)));
+    }
+
+    // 사용자 종류 권한에 사용자별 예외(허용/거부)를 반영한 실제 권한을 기록 (거부는 `!code`)
+    let now = chrono::Utc::now().naive_utc();
+    let mut items = Vec::new();
+    for user in users.list_active().await? {
+        let Some((_, (user_type_name, codes))) = selected
+            .iter()
+            .find(|(user_type, _)| *user_type == user.user_type_id)
+        else {
+            continue;
+        };
+        let overrides = permissions.user_overrides(user.id).await?;
+        let mut codes: Vec<_> = authz::merge_permissions(codes.clone(), &overrides, now)
+            .into_iter()
+            .collect();
+        codes.sort();
+        items.push(NewAccessReviewItem {
+            user_id: user.id,
+            username: user.username,
+            user_type_id: user.user_type_id,
+            user_type_name: user_type_name.clone(),
+            permissions: serde_json::to_string(&codes).unwrap_or_default(),
+        });
+    }
+    if items.is_empty() {
+        return Err(AppError::bad_request(
+            "The selected user types have no active users to review",
+        ));
+    }
+
+    let names: Vec<_> = selected.iter().map(|(_, (name, _))| name).collect();
+    let review = reviews
+        .create_review(
+            NewAccessReview {
+                name: req.name,
+                user_types: serde_json::to_string(&names).unwrap_or_default(),
+                created_by: reviewer.username.clone(),
+                due_at: req.due_at.map(|t| t.naive_utc()),
+            },
+            items,
+        )
+        .await?;
+    tracing::info!(
+        "Access review `{}` (#{}) started by `{}`",
+        review.name,
+        review.id,
+        reviewer.username
+    );
+    detail(reviews, review).await
+}
+
+pub async fn list_reviews(
+    reviews: &dyn AccessReviewRepository,
+) -> Result<Vec<AccessReviewResponse>, AppError> {
+    let mut responses = Vec::new();
+    for review in reviews.list_reviews().await? {
+        let items = reviews.list_items(review.id).await?;
+        responses.push(AccessReviewResponse::new(review, &items));
+    }
+    Ok(responses)
+}
+
+pub async fn get_review(
+    reviews: &dyn AccessReviewRepository,
+    id: i64,
+) -> Result<AccessReviewDetailResponse, AppError> {
+    let review = find_review(reviews, id).await?;
+    detail(reviews, review).await
+}
+
+/// Records a keep/revoke decision; decisions can be changed until the campaign is closed
+/// or, for a revocation, until it has been applied.
+pub async fn decide(
+    reviews: &dyn AccessReviewRepository,
+    reviewer: &AuthenticatedUser,
+    review_id: i64,
+    item_id: i64,
+    req: DecideAccessReviewItemRequest,
+) -> Result<AccessReviewItemResponse, AppError> {
+    req.validate()?;
+
+    let review = find_review(reviews, review_id).await?;
+    if review.status != STATUS_OPEN {
+        return Err(AppError::conflict("Access review is already closed"));
+    }
+    let item = reviews
+        .list_items(review_id)
+        .await?
+        .into_iter()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| AppError::not_found("Access review item not found"))?;
+    if item.user_id == Some(reviewer.id) {
+        return Err(AppError::forbidden(
+            "Reviewers cannot decide on their own access",
+        ));
+    }
+    if req.decision.as_str() == DECISION_REVOKE && holds_wildcard(&item) {
+        return Err(AppError::bad_request(
+            "The wildcard permission can only be revoked by changing the user type",
+        ));
+    }
+    // 중단된 종료 요청이 이미 회수한 항목
+    if item.applied_at.is_some() {
+        return Err(AppError::conflict(
+            "The revocation has already been applied",
+        ));
+    }
+
+    let item = reviews
+        .decide_item(
+            review_id,
+            item_id,
+            req.decision.as_str(),
+            req.comment.as_deref(),
+            &reviewer.username,
+        )
+        .await?
+        // 확인 이후 다른 요청이 캠페인을 종료했거나 회수를 적용한 경우
+        .ok_or_else(|| AppError::conflict("Access review is already closed"))?;
+    Ok(AccessReviewItemResponse::from(item))
+}
+
+/// Denies the reviewed permissions of every item marked `revoke`, then closes the campaign.
+///
+/// The campaign is closed only after every revocation has been applied, so a close that
+/// fails partway leaves it open and can be retried; revocations already applied are skipped.
+pub async fn close_review(
+    reviews: &dyn AccessReviewRepository,
+    users: &dyn UserRepository,
+    permissions: &dyn PermissionRepository,
+    reviewer: &AuthenticatedUser,
+    id: i64,
+) -> Result<AccessReviewDetailResponse, AppError> {
+    let review = find_review(reviews, id).await?;
+    if review.status != STATUS_OPEN {
+        return Err(AppError::conflict("Access review is already closed"));
+    }
+    let items = reviews.list_items(id).await?;
+    let pending = items
+        .iter()
+        .filter(|item| item.decision == DECISION_PENDING)
+        .count();
+    if pending > 0 {
+        return Err(AppError::Conflict(format!(
+            "{} items are still pending a decision",
+            pending
+        )));
+    }
+
+    let revoked: Vec<_> = items
+        .iter()
+        .filter(|item| item.decision == DECISION_REVOKE && item.applied_at.is_none())
+        .collect();
+    if let Some(item) = revoked.iter().find(|item| holds_wildcard(item)) {
+        return Err(AppError::Conflict(format!(
+            "`{}` holds the wildcard permission; change the user type and keep the item instead",
+            item.username
+        )));
+    }
+
+    let reason = format!("Access review `{}` (#{}): revoked", review.name, review.id);
+    for item in revoked {
+        // 삭제된 사용자나 이후 삭제된 권한은 건너뛰고 회수된 것으로 기록
+        let user = match item.user_id {
+            Some(user_id) => users.find_by_id(user_id).await?,
+            None => None,
+        };
+        if let Some(user) = user {
+            for code in granted_codes(item) {
+                let Some(permission) = permissions.find_by_code(&code).await? else {
+                    continue;
+                };
+                let Some(permission_id) = permission.id else {
+                    continue;
+                };
+                let change = UserPermissionChange {
+                    user_id: user.id,
+                    username: &user.username,
+                    permission_id,
+                    permission_code: &permission.code,
+                    changed_by: &reviewer.username,
+                };
+                permissions
+                    .set_user_override(&change, authz::EFFECT_DENY, Some(&reason), None)
+                    .await?;
+            }
+            tracing::warn!(
+                "Revoked the reviewed access of `{}`: {}",
+                user.username,
+                reason
+            );
+        }
+        reviews.mark_item_applied(item.id).await?;
+    }
+    let review = reviews
+        .close_review(id, &reviewer.username)
+        .await?
+        .ok_or_else(|| AppError::conflict("Access review is already closed"))?;
+    tracing::info!(
+        "Access review `{}` (#{}) closed by `{}`",
+        review.name,
+        review.id,
+        reviewer.username
+    );
+    detail(reviews, review).await
+}
+
+/// Streams the campaign and every decision as CSV/JSONL evidence.
+pub async fn export_review(
+    pool: &DbPool,
+    reviews: &dyn AccessReviewRepository,
+    id: i64,
+    format: TransferFormat,
+) -> Result<impl Stream<Item = Result<Bytes, AppError>>, AppError> {
+    find_review(reviews, id).await?;
+
+    let mut args = DbArguments::default();
+    args.add(id).map_err(sqlx::Error::Encode)?;
+    Ok(transfer::export_stream::<
+        AccessReviewEvidence,
+        AccessReviewEvidence,
+    >(pool.clone(), EVIDENCE_QUERY.to_string(), args, format))
+}
@@ -0,0 +1,25 @@
+use crate::{
+    config::{db, db::DbPool, env::Env},
+    dto::admin::MigrationStatusResponse,
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    services::authz,
+};
+
+// 운영 관리 API는 전체 권한("*")을 가진 사용자만 호출 가능
+pub fn ensure_super_admin(user: &AuthenticatedUser) -> Result<(), AppError> {
+    if authz::is_super_admin(&user.permissions) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("Administrator permission required"))
+    }
+}
+
+pub async fn get_migration_status(
+    pool: &DbPool,
+    config: &Env,
+) -> Result<MigrationStatusResponse, AppError> {
+    let report = db::verify_schema(pool, &config.database.migration_dir).await?;
+
+    Ok(MigrationStatusResponse::from(report))
+}
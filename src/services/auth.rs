@@ -1,57 +1,126 @@
 use crate::{
     config::env::Env,
-    dto::auth::{CurrentUserResponse, LoginRequest, LoginResponse},
+    dto::{
+        auth::{CurrentUserResponse, LoginRequest, LoginResponse},
+        user_type::UserTypeResponse,
+    },
     errors::AppError,
-    models::AdminUser,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{UserRepository, UserTypeRepository},
+    services::{
+        metrics::METRICS,
+        settings::{
+            Settings, JWT_EXPIRES_IN_SECONDS, LOGIN_LOCKOUT_SECONDS, LOGIN_LOCKOUT_THRESHOLD,
+        },
+    },
     util::{create_jwt, verify_password},
 };
-use actix_web::web;
-use chrono::{TimeZone, Utc};
-use sqlx::SqlitePool;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 use validator::Validate;
 
+/// Consecutive failed logins per user. After `auth.lockout_threshold` failures the account
+/// is locked for `auth.lockout_seconds`; both are runtime settings.
+#[derive(Default)]
+pub struct LoginAttempts {
+    failures: Mutex<HashMap<i64, Failures>>,
+}
+
+struct Failures {
+    count: u32,
+    locked_until: Option<Instant>,
+}
+
+impl LoginAttempts {
+    fn is_locked(&self, user_id: i64) -> bool {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures
+            .get(&user_id)
+            .and_then(|f| f.locked_until)
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn record_failure(&self, user_id: i64, threshold: u32, lockout: Duration) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = failures.entry(user_id).or_insert(Failures {
+            count: 0,
+            locked_until: None,
+        });
+        // 잠금 기간이 끝난 뒤의 실패는 처음부터 다시 계산
+        if entry.locked_until.is_some() {
+            entry.count = 0;
+            entry.locked_until = None;
+        }
+        entry.count += 1;
+        if entry.count >= threshold {
+            entry.locked_until = Some(Instant::now() + lockout);
+            tracing::warn!(
+                "Locking user {} for {}s after {} failed logins",
+                user_id,
+                lockout.as_secs(),
+                entry.count
+            );
+        }
+    }
+
+    fn reset(&self, user_id: i64) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.remove(&user_id);
+    }
+}
+
 pub async fn login(
-    pool: web::Data<SqlitePool>,
-    config: web::Data<Env>,
-    req: web::Json<LoginRequest>,
+    users: &dyn UserRepository,
+    config: &Env,
+    settings: &Settings,
+    attempts: &LoginAttempts,
+    req: LoginRequest,
 ) -> Result<LoginResponse, AppError> {
     req.validate()?;
 
-    let user = sqlx::query_as!(
-        AdminUser,
-        r#"SELECT
-            id as "id!",
-            username as "username!",
-            password_hash as "password_hash!",
-            user_type_id,
-            is_active as "is_active!",
-            last_login_at,
-            created_at as "created_at!",
-            updated_at as "updated_at!"
-        FROM admin_user
-        WHERE username = ?"#,
-        req.username
-    )
-    .fetch_optional(pool.get_ref())
-    .await?
-    .ok_or_else(|| AppError::unauthorized("Invalid username or password"))?;
+    let Some(user) = users.find_by_username(&req.username).await? else {
+        METRICS.record_login("failure");
+        return Err(AppError::unauthorized("Invalid username or password"));
+    };
 
     if !user.is_active {
+        METRICS.record_login("inactive");
         return Err(AppError::unauthorized("User account is inactive"));
     }
 
+    let threshold = settings.get(&LOGIN_LOCKOUT_THRESHOLD);
+    if threshold > 0 && attempts.is_locked(user.id) {
+        METRICS.record_login("locked");
+        return Err(AppError::unauthorized(
+            "Account is temporarily locked after too many failed logins",
+        ));
+    }
+
     if !verify_password(&req.password, &user.password_hash).await? {
+        if threshold > 0 {
+            let lockout = Duration::from_secs(settings.get(&LOGIN_LOCKOUT_SECONDS));
+            attempts.record_failure(user.id, threshold, lockout);
+        }
+        METRICS.record_login("failure");
         return Err(AppError::unauthorized("Invalid username or password"));
     }
+    attempts.reset(user.id);
 
-    let token = create_jwt(user.id, user.user_type_id, &user.username, &config)?;
+    let expires_in_seconds = settings.get(&JWT_EXPIRES_IN_SECONDS);
+    let token = create_jwt(
+        user.id,
+        user.user_type_id,
+        &user.username,
+        expires_in_seconds,
+        config,
+    )?;
+    METRICS.record_login("success");
+    METRICS.record_session(user.id, chrono::Utc::now().timestamp() + expires_in_seconds);
 
-    let _ = sqlx::query!(
-        "UPDATE admin_user SET last_login_at = CURRENT_TIMESTAMP WHERE id = ?",
-        user.id
-    )
-    .execute(pool.get_ref())
-    .await;
+    let _ = users.touch_last_login(user.id).await;
 
     Ok(LoginResponse {
         access_token: token,
@@ -60,22 +129,13 @@ pub async fn login(
 }
 
 pub async fn get_current_user(
-    pool: web::Data<SqlitePool>,
-    current_user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
+    user_types: &dyn UserTypeRepository,
+    current_user: AuthenticatedUser,
 ) -> Result<CurrentUserResponse, AppError> {
-    let user_type_info = sqlx::query!(
-        "SELECT id, name, description, created_at, updated_at FROM user_type WHERE id = ?",
-        current_user.user_type_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await?
-    .map(|record| crate::dto::user_type::UserTypeResponse {
-        id: record.id,
-        name: record.name,
-        description: record.description.unwrap_or_default(),
-        created_at: Utc.from_utc_datetime(&record.created_at),
-        updated_at: Utc.from_utc_datetime(&record.updated_at),
-    });
+    let user_type_info = user_types
+        .find_by_id(current_user.user_type_id)
+        .await?
+        .map(UserTypeResponse::from);
 
     Ok(CurrentUserResponse {
         id: current_user.id,
@@ -0,0 +1,280 @@
+//! Permission introspection.
+//!
+//! A user's permission set is the codes granted to their user type, where `*` grants
+//! everything, plus their unexpired per-user overrides: `allow` adds a code and `deny` removes
+//! it, even when the user type holds `*`. [`merge_permissions`] builds the set used by the
+//! authentication middleware and [`EnsurePermission`], and the functions below explain the same
+//! resolution. Users may inspect themselves; inspecting anyone else requires `*`, which only
+//! a user type can grant.
+//!
+//! [`EnsurePermission`]: crate::middleware::auth::ensure_permission::EnsurePermission
+
+use crate::{
+    dto::authz::{
+        AuthzCheckRequest, AuthzCheckResponse, AuthzDecision, AuthzRule, EffectivePermission,
+        EffectivePermissionsResponse, GrantSource, PermissionGrant,
+    },
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    models::{AdminUser, UserPermission, UserType},
+    repositories::{PermissionRepository, UserRepository, UserTypeRepository},
+};
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use std::collections::{BTreeMap, HashSet};
+use validator::Validate;
+
+pub const WILDCARD: &str = "*";
+pub const EFFECT_ALLOW: &str = "allow";
+pub const EFFECT_DENY: &str = "deny";
+// 권한 집합 안에서 "*"보다 우선하는 거부 표시 (예: "!menu:write")
+pub const DENY_PREFIX: &str = "!";
+
+pub fn is_granted(permissions: &HashSet<String>, code: &str) -> bool {
+    !permissions.contains(&format!("{}{}", DENY_PREFIX, code))
+        && (permissions.contains(WILDCARD) || permissions.contains(code))
+}
+
+// 운영 관리 API와 다른 사용자 조회에 필요한 전체 권한("*") 보유 여부
+pub fn is_super_admin(permissions: &HashSet<String>) -> bool {
+    is_granted(permissions, WILDCARD)
+}
+
+fn is_in_effect(user_permission: &UserPermission, now: NaiveDateTime) -> bool {
+    user_permission
+        .expires_at
+        .is_none_or(|expires_at| expires_at > now)
+}
+
+/// Applies the unexpired `overrides` to the codes granted by the user type.
+pub fn merge_permissions(
+    mut codes: HashSet<String>,
+    overrides: &[UserPermission],
+    now: NaiveDateTime,
+) -> HashSet<String> {
+    for user_permission in overrides.iter().filter(|up| is_in_effect(up, now)) {
+        let code = &user_permission.permission_code;
+        if user_permission.effect == EFFECT_DENY {
+            codes.remove(code);
+            codes.insert(format!("{}{}", DENY_PREFIX, code));
+        } else {
+            codes.insert(code.clone());
+        }
+    }
+    codes
+}
+
+pub fn ensure_can_inspect(user: &AuthenticatedUser, user_id: i64) -> Result<(), AppError> {
+    if user.id == user_id || is_super_admin(&user.permissions) {
+        Ok(())
+    } else {
+        Err(AppError::forbidden(
+            "Administrator permission required to inspect other users",
+        ))
+    }
+}
+
+struct Subject {
+    user: AdminUser,
+    user_type: UserType,
+    role_codes: HashSet<String>,
+    overrides: Vec<UserPermission>, // 만료되지 않은 예외만
+}
+
+async fn load_subject(
+    users: &dyn UserRepository,
+    user_types: &dyn UserTypeRepository,
+    permissions: &dyn PermissionRepository,
+    user_id: i64,
+) -> Result<Subject, AppError> {
+    let user = users
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
+    let user_type = user_types
+        .find_by_id(user.user_type_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User type not found"))?;
+    let role_codes = permissions.codes_for_user_type(user_type.id).await?;
+    let now = Utc::now().naive_utc();
+    let overrides = permissions
+        .user_overrides(user.id)
+        .await?
+        .into_iter()
+        .filter(|up| is_in_effect(up, now))
+        .collect();
+    Ok(Subject {
+        user,
+        user_type,
+        role_codes,
+        overrides,
+    })
+}
+
+impl Subject {
+    fn user_override(&self, code: &str, effect: &str) -> Option<&UserPermission> {
+        self.overrides
+            .iter()
+            .find(|up| up.permission_code == code && up.effect == effect)
+    }
+}
+
+fn describe_override(user_permission: &UserPermission) -> String {
+    let verb = if user_permission.effect == EFFECT_DENY {
+        "Denied"
+    } else {
+        "Granted"
+    };
+    let mut detail = format!("{} directly by `{}`", verb, user_permission.granted_by);
+    if let Some(expires_at) = user_permission.expires_at {
+        detail.push_str(&format!(
+            " until {}",
+            Utc.from_utc_datetime(&expires_at).to_rfc3339()
+        ));
+    }
+    if let Some(reason) = &user_permission.reason {
+        detail.push_str(&format!(": {}", reason));
+    }
+    detail
+}
+
+/// Every permission the user holds, with the grants it comes from, and the codes denied to them.
+pub async fn effective_permissions(
+    users: &dyn UserRepository,
+    user_types: &dyn UserTypeRepository,
+    permissions: &dyn PermissionRepository,
+    user_id: i64,
+) -> Result<EffectivePermissionsResponse, AppError> {
+    let subject = load_subject(users, user_types, permissions, user_id).await?;
+    let type_name = &subject.user_type.name;
+
+    let mut grants: BTreeMap<String, Vec<PermissionGrant>> = BTreeMap::new();
+    for code in &subject.role_codes {
+        grants
+            .entry(code.clone())
+            .or_default()
+            .push(PermissionGrant {
+                source: GrantSource::Role,
+                detail: format!("Granted to user type `{}`", type_name),
+            });
+    }
+    for user_permission in &subject.overrides {
+        if user_permission.effect == EFFECT_ALLOW {
+            grants
+                .entry(user_permission.permission_code.clone())
+                .or_default()
+                .push(PermissionGrant {
+                    source: GrantSource::Direct,
+                    detail: describe_override(user_permission),
+                });
+        }
+    }
+    // "*"는 정의된 모든 권한을 포함 (사용자별 예외로는 "*"를 부여/거부할 수 없음)
+    if subject.role_codes.contains(WILDCARD) {
+        for permission in permissions.list_all().await? {
+            if permission.code == WILDCARD {
+                continue;
+            }
+            grants
+                .entry(permission.code)
+                .or_default()
+                .push(PermissionGrant {
+                    source: GrantSource::Wildcard,
+                    detail: "`*` grants every permission".to_string(),
+                });
+        }
+    }
+
+    // 거부 예외는 다른 모든 부여보다 우선
+    let mut denied = Vec::new();
+    for user_permission in &subject.overrides {
+        if user_permission.effect == EFFECT_DENY {
+            grants.remove(&user_permission.permission_code);
+            denied.push(EffectivePermission {
+                code: user_permission.permission_code.clone(),
+                grants: vec![PermissionGrant {
+                    source: GrantSource::Direct,
+                    detail: describe_override(user_permission),
+                }],
+            });
+        }
+    }
+
+    Ok(EffectivePermissionsResponse {
+        user_id: subject.user.id,
+        username: subject.user.username,
+        user_type_id: subject.user_type.id,
+        user_type_name: subject.user_type.name,
+        is_active: subject.user.is_active,
+        permissions: grants
+            .into_iter()
+            .map(|(code, grants)| EffectivePermission { code, grants })
+            .collect(),
+        denied,
+    })
+}
+
+/// Decides whether the user holds `req.permission` and explains which rule decided it.
+pub async fn check(
+    users: &dyn UserRepository,
+    user_types: &dyn UserTypeRepository,
+    permissions: &dyn PermissionRepository,
+    req: AuthzCheckRequest,
+) -> Result<AuthzCheckResponse, AppError> {
+    req.validate()?;
+
+    let subject = load_subject(users, user_types, permissions, req.user_id).await?;
+    let type_name = &subject.user_type.name;
+    let code = req.permission.as_str();
+    let holds_wildcard = subject.role_codes.contains(WILDCARD);
+    let (decision, rule, explanation) = if !subject.user.is_active {
+        (
+            AuthzDecision::Deny,
+            AuthzRule::InactiveAccount,
+            "The account is deactivated and cannot sign in".to_string(),
+        )
+    } else if let Some(deny) = subject.user_override(code, EFFECT_DENY) {
+        (
+            AuthzDecision::Deny,
+            AuthzRule::DirectDeny,
+            describe_override(deny),
+        )
+    } else if subject.role_codes.contains(code) {
+        (
+            AuthzDecision::Allow,
+            AuthzRule::Role,
+            format!("Granted to user type `{}`", type_name),
+        )
+    } else if let Some(allow) = subject.user_override(code, EFFECT_ALLOW) {
+        (
+            AuthzDecision::Allow,
+            AuthzRule::DirectGrant,
+            describe_override(allow),
+        )
+    } else if holds_wildcard {
+        (
+            AuthzDecision::Allow,
+            AuthzRule::Wildcard,
+            "`*` grants every permission".to_string(),
+        )
+    } else {
+        (
+            AuthzDecision::Deny,
+            AuthzRule::NoGrant,
+            format!(
+                "Neither user type `{}` ({} permissions) nor a direct grant provides `{}`",
+                type_name,
+                subject.role_codes.len(),
+                code
+            ),
+        )
+    };
+
+    Ok(AuthzCheckResponse {
+        user_id: subject.user.id,
+        username: subject.user.username,
+        permission: req.permission,
+        decision,
+        rule,
+        explanation,
+    })
+}
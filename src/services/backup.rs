@@ -0,0 +1,353 @@
+//! Online backups of the SQLite database.
+//!
+//! Backups are written with `VACUUM INTO`, which produces a consistent copy while the
+//! server keeps serving requests. A restore first checks the backup in a staging file and
+//! then replaces the contents of the live database inside a single transaction while the
+//! server is in maintenance mode.
+
+use crate::{
+    config::{
+        db::{DbPool, DbPools},
+        env::Env,
+    },
+    dto::backup::{BackupInfo, RestoreResponse},
+    errors::AppError,
+    repositories::SqlxRepository,
+    services::{
+        maintenance::MaintenanceState,
+        scheduler::{Job, JobSchedule},
+        settings::Settings,
+        setup::SetupState,
+    },
+};
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteConnection},
+    ConnectOptions, Connection,
+};
+use std::{fs::File, io, path::Path, time::Duration};
+use tokio::fs;
+
+const BACKUP_PREFIX: &str = "backup-";
+const STAGING_FILE: &str = ".restore-staging.db";
+
+// 경로 조작을 막기 위해 이 서버가 만든 파일 이름 형식만 허용
+fn is_backup_name(name: &str) -> bool {
+    name.starts_with(BACKUP_PREFIX)
+        && (name.ends_with(".db") || name.ends_with(".db.gz"))
+        && !name.contains(['/', '\\'])
+        && !name.contains("..")
+}
+
+async fn backup_info(path: &Path) -> Result<BackupInfo, AppError> {
+    let metadata = fs::metadata(path)
+        .await
+        .with_context(|| format!("Failed to read `{}`", path.display()))?;
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(BackupInfo {
+        compressed: name.ends_with(".gz"),
+        name,
+        size_bytes: metadata.len(),
+        created_at: metadata
+            .modified()
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+pub async fn create_backup(pool: &DbPool, config: &Env) -> Result<BackupInfo, AppError> {
+    let dir = Path::new(&config.backup.dir);
+    fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("Failed to create backup directory `{}`", dir.display()))?;
+
+    let name = format!(
+        "{}{}.db",
+        BACKUP_PREFIX,
+        Utc::now().format("%Y%m%dT%H%M%S%3fZ")
+    );
+    let tmp_path = dir.join(format!("{}.tmp", name));
+
+    // 대상 파일이 이미 있으면 VACUUM INTO가 실패하므로 임시 파일에 쓴 뒤 이름 변경
+    sqlx::query("VACUUM INTO $1")
+        .bind(tmp_path.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+
+    let path = if config.backup.compress {
+        let path = dir.join(format!("{}.gz", name));
+        let (src, dst) = (tmp_path.clone(), path.clone());
+        tokio::task::spawn_blocking(move || gzip_file(&src, &dst))
+            .await
+            .context("Backup compression task failed")?
+            .context("Failed to compress backup")?;
+        fs::remove_file(&tmp_path).await.ok();
+        path
+    } else {
+        let path = dir.join(&name);
+        fs::rename(&tmp_path, &path)
+            .await
+            .context("Failed to move backup into place")?;
+        path
+    };
+
+    let info = backup_info(&path).await?;
+    tracing::info!("Created backup `{}` ({} bytes)", info.name, info.size_bytes);
+
+    prune_backups(dir, config.backup.retention).await?;
+    Ok(info)
+}
+
+fn gzip_file(src: &Path, dst: &Path) -> io::Result<()> {
+    let mut input = File::open(src)?;
+    let mut encoder = GzEncoder::new(File::create(dst)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()
+}
+
+fn gunzip_file(src: &Path, dst: &Path) -> io::Result<()> {
+    let mut decoder = GzDecoder::new(File::open(src)?);
+    let mut output = File::create(dst)?;
+    io::copy(&mut decoder, &mut output)?;
+    output.sync_all()
+}
+
+// 최신순 백업 파일 이름 목록 (이름에 시각이 포함되어 있어 이름순 정렬 = 시간순 정렬)
+async fn backup_names(dir: &Path) -> Result<Vec<String>, AppError> {
+    let mut names = Vec::new();
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(names),
+        Err(e) => return Err(anyhow::Error::from(e).into()),
+    };
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .context("Failed to list backups")?
+    {
+        if let Some(name) = entry.file_name().to_str().filter(|n| is_backup_name(n)) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(names)
+}
+
+async fn prune_backups(dir: &Path, retention: usize) -> Result<(), AppError> {
+    for name in backup_names(dir).await?.into_iter().skip(retention.max(1)) {
+        tracing::info!("Removing old backup `{}`", name);
+        fs::remove_file(dir.join(&name))
+            .await
+            .with_context(|| format!("Failed to remove old backup `{}`", name))?;
+    }
+    Ok(())
+}
+
+pub async fn list_backups(config: &Env) -> Result<Vec<BackupInfo>, AppError> {
+    let dir = Path::new(&config.backup.dir);
+    let mut backups = Vec::new();
+    for name in backup_names(dir).await? {
+        backups.push(backup_info(&dir.join(name)).await?);
+    }
+    Ok(backups)
+}
+
+pub async fn restore_backup(
+    pool: &DbPool,
+    config: &Env,
+    maintenance: &MaintenanceState,
+    settings: &Settings,
+    setup_state: &SetupState,
+    name: &str,
+) -> Result<RestoreResponse, AppError> {
+    let dir = Path::new(&config.backup.dir);
+    let path = dir.join(name);
+    if !is_backup_name(name) || !fs::try_exists(&path).await.unwrap_or(false) {
+        return Err(AppError::not_found("Backup not found"));
+    }
+
+    // 1. 백업을 임시 파일로 풀어서 무결성 확인 (실제 DB는 아직 변경하지 않음)
+    let staging = dir.join(STAGING_FILE);
+    fs::remove_file(&staging).await.ok();
+    let result = async {
+        stage_backup(&path, &staging).await?;
+        verify_backup(pool, &staging).await?;
+
+        // 2. 복원 직전 상태를 백업한 뒤 점검 모드에서 내용 교체
+        let safety_backup = create_backup(pool, config).await?;
+        let _maintenance = maintenance.enter()?;
+        replace_database(pool, &staging).await?;
+        // 메모리에 캐시된 런타임 설정과 최초 설정 상태를 복원된 내용으로 갱신
+        let repo = SqlxRepository::new(DbPools::single(pool.clone()));
+        settings.reload(&repo).await?;
+        setup_state.refresh(pool).await?;
+        tracing::warn!(
+            "Restored database from backup `{}` (previous state saved as `{}`)",
+            name,
+            safety_backup.name
+        );
+
+        Ok(RestoreResponse {
+            restored: name.to_string(),
+            safety_backup,
+        })
+    }
+    .await;
+    fs::remove_file(&staging).await.ok();
+
+    result
+}
+
+async fn stage_backup(path: &Path, staging: &Path) -> Result<(), AppError> {
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let (src, dst) = (path.to_path_buf(), staging.to_path_buf());
+        tokio::task::spawn_blocking(move || gunzip_file(&src, &dst))
+            .await
+            .context("Backup decompression task failed")?
+            .map_err(|e| AppError::BadRequest(format!("Backup cannot be decompressed: {}", e)))?;
+    } else {
+        fs::copy(path, staging)
+            .await
+            .context("Failed to copy backup")?;
+    }
+    Ok(())
+}
+
+#[derive(sqlx::FromRow, PartialEq)]
+struct AppliedMigration {
+    version: i64,
+    checksum: Vec<u8>,
+}
+
+const APPLIED_MIGRATIONS_QUERY: &str =
+    "SELECT version, checksum FROM _sqlx_migrations WHERE success ORDER BY version";
+
+async fn verify_backup(pool: &DbPool, staging: &Path) -> Result<(), AppError> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(staging)
+        .read_only(true)
+        .connect()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Backup cannot be opened: {}", e)))?;
+
+    // 손상된 파일은 PRAGMA 자체가 실패할 수 있음
+    let integrity: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Backup failed the integrity check: {}", e)))?;
+    if integrity != ["ok"] {
+        return Err(AppError::BadRequest(format!(
+            "Backup failed the integrity check: {}",
+            integrity.join("; ")
+        )));
+    }
+
+    let foreign_key_violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Backup failed the integrity check: {}", e)))?;
+    if !foreign_key_violations.is_empty() {
+        return Err(AppError::bad_request(
+            "Backup contains foreign key violations",
+        ));
+    }
+
+    // 같은 스키마(마이그레이션)로 만들어진 백업만 복원 가능
+    let backup_migrations = sqlx::query_as::<_, AppliedMigration>(APPLIED_MIGRATIONS_QUERY)
+        .fetch_all(&mut conn)
+        .await
+        .map_err(|_| AppError::bad_request("Backup does not contain migration history"))?;
+    conn.close().await?;
+
+    let current_migrations = sqlx::query_as::<_, AppliedMigration>(APPLIED_MIGRATIONS_QUERY)
+        .fetch_all(pool)
+        .await?;
+    if backup_migrations != current_migrations {
+        return Err(AppError::bad_request(
+            "Backup was taken with a different schema version; migrate it first",
+        ));
+    }
+
+    Ok(())
+}
+
+async fn replace_database(pool: &DbPool, staging: &Path) -> Result<(), AppError> {
+    // 외래 키를 끈 전용 연결을 사용하고 작업 후 풀에 반환하지 않음
+    let mut conn = pool.acquire().await?.detach();
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(&mut conn)
+        .await?;
+    sqlx::query("ATTACH DATABASE $1 AS restore")
+        .bind(staging.to_string_lossy().into_owned())
+        .execute(&mut conn)
+        .await?;
+
+    let result = copy_tables(&mut conn).await;
+    conn.close().await.ok();
+    result
+}
+
+// 작업 실행 잠금(job_lock)은 백업 시점이 아닌 현재 상태를 유지
+async fn copy_tables(conn: &mut SqliteConnection) -> Result<(), AppError> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM main.sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT IN ('_sqlx_migrations', 'job_lock')",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut tx = conn.begin().await?;
+    for table in tables.iter().map(String::as_str).chain(["sqlite_sequence"]) {
+        sqlx::query(&format!("DELETE FROM main.\"{}\"", table))
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!(
+            "INSERT INTO main.\"{0}\" SELECT * FROM restore.\"{0}\"",
+            table
+        ))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let violations = sqlx::query("PRAGMA main.foreign_key_check")
+        .fetch_all(&mut *tx)
+        .await?;
+    if !violations.is_empty() {
+        return Err(AppError::bad_request(
+            "Restored data contains foreign key violations",
+        ));
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Scheduler job that takes a backup. Unless `scheduler.schedules.backup` is set it runs
+/// every `backup.interval_seconds`, or only manually when that is unset.
+pub fn backup_job(pool: DbPool, config: Env) -> Job {
+    let schedule = config
+        .backup
+        .interval_seconds
+        .map(|seconds| JobSchedule::Every(Duration::from_secs(seconds)));
+    Job::new(
+        "backup",
+        "Takes a backup of the SQLite database",
+        move || {
+            let (pool, config) = (pool.clone(), config.clone());
+            async move {
+                let info = create_backup(&pool, &config).await?;
+                Ok(format!(
+                    "Created backup `{}` ({} bytes)",
+                    info.name, info.size_bytes
+                ))
+            }
+        },
+    )
+    .with_schedule(schedule)
+}
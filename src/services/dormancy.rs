@@ -0,0 +1,213 @@
+//! Dormant account policy.
+//!
+//! An account's last activity is the latest of its last login, creation and reactivation.
+//! Once it has been inactive for `dormancy.warn_after_days` it is flagged (logged and
+//! `dormancy_warned_at` set); after `dormancy.deactivate_after_days` the scheduled
+//! `deactivate_dormant_accounts` job deactivates it and records the reason on the user, but
+//! never before the warning is `dormancy.warning_grace_days` old. An account that reaches the
+//! deactivation threshold without having been warned (e.g. the job did not run) is warned first.
+//! Usernames in `dormancy.exempt_usernames` are never flagged, and the last active SuperAdmin
+//! is never deactivated so the server cannot lock itself out.
+
+use crate::{
+    config::env::DormancyConfig,
+    dto::dormancy::{DormancyReport, DormancyStatus, DormantAccount},
+    errors::AppError,
+    models::AdminUser,
+    repositories::{UserRepository, UserTypeRepository},
+    services::{scheduler::Job, user_type::SUPER_ADMIN_USER_TYPE},
+};
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use std::sync::Arc;
+
+pub fn last_activity(user: &AdminUser) -> NaiveDateTime {
+    [user.last_login_at, user.reactivated_at]
+        .into_iter()
+        .flatten()
+        .fold(user.created_at, NaiveDateTime::max)
+}
+
+fn is_exempt(config: &DormancyConfig, user: &AdminUser) -> bool {
+    config.exempt_usernames.contains(&user.username)
+}
+
+/// Active, non-exempt accounts past the warning threshold, longest inactive first.
+pub async fn report(
+    users: &dyn UserRepository,
+    config: &DormancyConfig,
+    now: NaiveDateTime,
+) -> Result<DormancyReport, AppError> {
+    let mut accounts: Vec<_> = users
+        .list_active()
+        .await?
+        .into_iter()
+        .filter(|user| !is_exempt(config, user))
+        .filter_map(|user| {
+            let last_activity = last_activity(&user);
+            let warned_at = user.dormancy_warned_at;
+            let inactive_days = (now - last_activity).num_days();
+            let status = if inactive_days >= config.deactivate_after_days as i64 {
+                DormancyStatus::DeactivationDue
+            } else if inactive_days >= config.warn_after_days as i64 {
+                DormancyStatus::Warning
+            } else {
+                return None;
+            };
+            Some(DormantAccount {
+                user_id: user.id,
+                username: user.username,
+                user_type_id: user.user_type_id,
+                last_login_at: user.last_login_at.map(|t| Utc.from_utc_datetime(&t)),
+                last_activity_at: Utc.from_utc_datetime(&last_activity),
+                inactive_days,
+                status,
+                warned_at: warned_at.map(|t| Utc.from_utc_datetime(&t)),
+                deactivates_at: Utc.from_utc_datetime(
+                    &(last_activity + Duration::days(config.deactivate_after_days as i64))
+                        .max(grace_ends_at(config, warned_at.unwrap_or(now))),
+                ),
+            })
+        })
+        .collect();
+    accounts.sort_by_key(|account| std::cmp::Reverse(account.inactive_days));
+
+    Ok(DormancyReport {
+        enabled: config.enabled,
+        warn_after_days: config.warn_after_days,
+        deactivate_after_days: config.deactivate_after_days,
+        warning_grace_days: config.warning_grace_days,
+        accounts,
+    })
+}
+
+fn grace_ends_at(config: &DormancyConfig, warned_at: NaiveDateTime) -> NaiveDateTime {
+    warned_at + Duration::days(config.warning_grace_days as i64)
+}
+
+#[derive(Debug, Default)]
+pub struct DormancyOutcome {
+    pub warned: Vec<String>,
+    pub deactivated: Vec<String>,
+    pub kept: Vec<String>, // 마지막 SuperAdmin이라 비활성화하지 않은 계정
+}
+
+/// Warns and deactivates the accounts listed by [`report`].
+pub async fn enforce(
+    users: &dyn UserRepository,
+    user_types: &dyn UserTypeRepository,
+    config: &DormancyConfig,
+    now: NaiveDateTime,
+) -> Result<DormancyOutcome, AppError> {
+    let mut outcome = DormancyOutcome::default();
+    let report = report(users, config, now).await?;
+    if report.accounts.is_empty() {
+        return Ok(outcome);
+    }
+
+    let super_admin_type = user_types
+        .find_by_name(SUPER_ADMIN_USER_TYPE)
+        .await?
+        .map(|user_type| user_type.id);
+    let active = users.list_active().await?;
+    let mut active_super_admins = active
+        .iter()
+        .filter(|user| Some(user.user_type_id) == super_admin_type)
+        .count();
+
+    for account in report.accounts {
+        let warned_at = account.warned_at.map(|t| t.naive_utc());
+        match account.status {
+            // 경고 없이 비활성화 기간이 지난 계정은 먼저 경고하고 유예 기간 뒤에 비활성화
+            DormancyStatus::DeactivationDue | DormancyStatus::Warning if warned_at.is_none() => {
+                users.mark_dormancy_warned(account.user_id).await?;
+                tracing::warn!(
+                    "Account `{}` has been inactive for {} days and will be deactivated at {}",
+                    account.username,
+                    account.inactive_days,
+                    account.deactivates_at
+                );
+                outcome.warned.push(account.username);
+            }
+            DormancyStatus::DeactivationDue
+                if warned_at.is_some_and(|t| grace_ends_at(config, t) > now) => {}
+            DormancyStatus::DeactivationDue => {
+                let is_super_admin = Some(account.user_type_id) == super_admin_type;
+                if is_super_admin && active_super_admins <= 1 {
+                    tracing::warn!(
+                        "Not deactivating dormant account `{}`: it is the last active SuperAdmin",
+                        account.username
+                    );
+                    outcome.kept.push(account.username);
+                    continue;
+                }
+                let reason = format!(
+                    "Dormant: no login for {} days (policy: {} days)",
+                    account.inactive_days, config.deactivate_after_days
+                );
+                if users.deactivate(account.user_id, &reason).await? {
+                    tracing::warn!("Deactivated account `{}`: {}", account.username, reason);
+                    if is_super_admin {
+                        active_super_admins -= 1;
+                    }
+                    outcome.deactivated.push(account.username);
+                }
+            }
+            DormancyStatus::Warning => {}
+        }
+    }
+    Ok(outcome)
+}
+
+/// Re-enables a deactivated account; its dormancy period starts over.
+pub async fn reactivate(users: &dyn UserRepository, id: i64) -> Result<AdminUser, AppError> {
+    if let Some(user) = users.reactivate(id).await? {
+        tracing::info!("Reactivated account `{}`", user.username);
+        return Ok(user);
+    }
+    match users.find_by_id(id).await? {
+        Some(_) => Err(AppError::Conflict("User is already active".to_string())),
+        None => Err(AppError::not_found("User not found")),
+    }
+}
+
+/// Scheduler job applying the policy; it only reports when `dormancy.enabled` is false.
+pub fn dormancy_job(
+    users: Arc<dyn UserRepository>,
+    user_types: Arc<dyn UserTypeRepository>,
+    config: DormancyConfig,
+) -> Job {
+    Job::new(
+        "deactivate_dormant_accounts",
+        "Warns and deactivates accounts that have not been used for dormancy.deactivate_after_days",
+        move || {
+            let (users, user_types, config) = (users.clone(), user_types.clone(), config.clone());
+            async move {
+                if !config.enabled {
+                    return Ok("Dormancy policy is disabled".to_string());
+                }
+                let outcome = enforce(
+                    users.as_ref(),
+                    user_types.as_ref(),
+                    &config,
+                    Utc::now().naive_utc(),
+                )
+                .await?;
+                let mut message = format!(
+                    "Warned {} accounts, deactivated {} accounts",
+                    outcome.warned.len(),
+                    outcome.deactivated.len()
+                );
+                if !outcome.deactivated.is_empty() {
+                    message.push_str(&format!(" ({})", outcome.deactivated.join(", ")));
+                }
+                if !outcome.kept.is_empty() {
+                    message.push_str(&format!(
+                        "; kept last SuperAdmin {}",
+                        outcome.kept.join(", ")
+                    ));
+                }
+                Ok(message)
+            }
+        },
+    )
+}
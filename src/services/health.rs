@@ -1,22 +1,208 @@
-use std::{sync::Once, time::Instant};
+use crate::{
+    config::{
+        db::{self, DbPool, DbPools},
+        env::Env,
+    },
+    dto::health::{HealthCheck, HealthCheckResponse, HealthStatus},
+    services::{lifecycle::TaskSupervisor, maintenance::MaintenanceState},
+};
+use serde_json::json;
+use std::{
+    future::Future,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
 
-static mut SERVER_START_TIME: Option<Instant> = None;
-static INIT: Once = Once::new();
+static SERVER_START_TIME: OnceLock<Instant> = OnceLock::new();
 
 pub fn initialize_server_start_time() {
     tracing::info!("Initializing server start time...");
+    SERVER_START_TIME.get_or_init(Instant::now);
+}
+
+pub fn get_server_runtime() -> u64 {
+    SERVER_START_TIME
+        .get()
+        .map(|start_time| start_time.elapsed().as_secs())
+        .unwrap_or(0)
+}
 
-    unsafe {
-        INIT.call_once(|| {
-            SERVER_START_TIME = Some(Instant::now());
+// 프로세스가 요청을 처리할 수 있으면 항상 Up (의존성은 검사하지 않음)
+pub fn liveness() -> HealthCheckResponse {
+    HealthCheckResponse {
+        status: HealthStatus::Up,
+        uptime: get_server_runtime(),
+        checks: Vec::new(),
+    }
+}
+
+/// Checks every dependency needed to serve traffic. The overall status is the worst
+/// individual status; `Down` means the instance should not receive requests.
+pub async fn readiness(
+    pools: &DbPools,
+    config: &Env,
+    maintenance: &MaintenanceState,
+    supervisor: &TaskSupervisor,
+) -> HealthCheckResponse {
+    let timeout = Duration::from_millis(config.health.timeout_ms);
+    let mut checks = vec![
+        check("database", timeout, check_database(&pools.read)).await,
+        check(
+            "migrations",
+            timeout,
+            check_migrations(&pools.read, &config.database.migration_dir),
+        )
+        .await,
+        check("pool", timeout, async { Ok(check_pools(pools)) }).await,
+    ];
+    #[cfg(feature = "sqlite")]
+    checks.push(check("disk", timeout, check_disk(config)).await);
+    // 종료 중에는 로드 밸런서가 새 요청을 보내지 않도록 Down
+    if supervisor.is_shutting_down() {
+        checks.push(HealthCheck {
+            name: "shutdown".to_string(),
+            status: HealthStatus::Down,
+            duration_ms: 0,
+            message: Some("Server is shutting down".to_string()),
+            details: None,
+        });
+    }
+    if maintenance.is_active() {
+        checks.push(HealthCheck {
+            name: "maintenance".to_string(),
+            status: HealthStatus::Down,
+            duration_ms: 0,
+            message: Some("Server is in maintenance mode".to_string()),
+            details: None,
         });
     }
+
+    HealthCheckResponse {
+        status: checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(HealthStatus::Up),
+        uptime: get_server_runtime(),
+        checks,
+    }
 }
 
-pub fn get_server_runtime() -> u64 {
-    unsafe {
-        SERVER_START_TIME
-            .map(|start_time| start_time.elapsed().as_secs())
-            .unwrap_or(0)
+// 검사 결과: (상태, 메시지, 상세 정보)
+type CheckOutcome = (HealthStatus, Option<String>, Option<serde_json::Value>);
+
+// 제한 시간 내에 끝나지 않거나 오류가 나면 Down
+async fn check(
+    name: &str,
+    timeout: Duration,
+    f: impl Future<Output = anyhow::Result<CheckOutcome>>,
+) -> HealthCheck {
+    let started = Instant::now();
+    let (status, message, details) = match tokio::time::timeout(timeout, f).await {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(e)) => (HealthStatus::Down, Some(e.to_string()), None),
+        Err(_) => (
+            HealthStatus::Down,
+            Some(format!("Timed out after {}ms", timeout.as_millis())),
+            None,
+        ),
+    };
+    if status != HealthStatus::Up {
+        tracing::warn!("Readiness check `{}` is {:?}: {:?}", name, status, message);
+    }
+
+    HealthCheck {
+        name: name.to_string(),
+        status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        message,
+        details,
+    }
+}
+
+async fn check_database(pool: &DbPool) -> anyhow::Result<CheckOutcome> {
+    sqlx::query("SELECT 1").execute(pool).await?;
+    Ok((HealthStatus::Up, None, None))
+}
+
+async fn check_migrations(pool: &DbPool, migration_dir: &str) -> anyhow::Result<CheckOutcome> {
+    let report = db::verify_schema(pool, migration_dir).await?;
+    if report.is_ok() {
+        return Ok((
+            HealthStatus::Up,
+            None,
+            Some(json!({ "applied": report.applied })),
+        ));
+    }
+
+    let pending: Vec<i64> = report.pending.iter().map(|(version, _)| *version).collect();
+    Ok((
+        HealthStatus::Down,
+        Some("Database schema does not match the migrations".to_string()),
+        Some(json!({
+            "pending": pending,
+            "checksum_mismatches": report.checksum_mismatches,
+            "missing_tables": report.missing_tables,
+        })),
+    ))
+}
+
+// 모든 연결이 사용 중이면 요청이 대기하게 되므로 Degraded
+fn check_pools(pools: &DbPools) -> CheckOutcome {
+    let stats = |pool: &DbPool| {
+        let max = pool.options().get_max_connections();
+        let saturated = pool.size() >= max && pool.num_idle() == 0;
+        let details = json!({ "size": pool.size(), "idle": pool.num_idle(), "max": max });
+        (saturated, details)
+    };
+    let (read_saturated, read) = stats(&pools.read);
+    let (write_saturated, write) = stats(&pools.write);
+
+    if read_saturated || write_saturated {
+        (
+            HealthStatus::Degraded,
+            Some("All connections of a pool are in use".to_string()),
+            Some(json!({ "read": read, "write": write })),
+        )
+    } else {
+        (
+            HealthStatus::Up,
+            None,
+            Some(json!({ "read": read, "write": write })),
+        )
+    }
+}
+
+#[cfg(feature = "sqlite")]
+async fn check_disk(config: &Env) -> anyhow::Result<CheckOutcome> {
+    use sqlx::sqlite::SqliteConnectOptions;
+    use std::{path::Path, str::FromStr};
+
+    let options = SqliteConnectOptions::from_str(&config.database.url)?;
+    let filename = options.get_filename();
+    if filename == Path::new(":memory:") {
+        return Ok((
+            HealthStatus::Up,
+            Some("In-memory database".to_string()),
+            None,
+        ));
+    }
+
+    let dir = match filename.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => Path::new(".").to_path_buf(),
+    };
+    let available = tokio::task::spawn_blocking(move || fs4::available_space(dir)).await??;
+    let minimum = config.health.min_free_disk_mb * 1024 * 1024;
+    let details = Some(json!({ "available_bytes": available, "minimum_bytes": minimum }));
+
+    if available < minimum {
+        Ok((
+            HealthStatus::Down,
+            Some("Not enough free disk space for the database".to_string()),
+            details,
+        ))
+    } else {
+        Ok((HealthStatus::Up, None, details))
     }
 }
@@ -0,0 +1,143 @@
+//! Process lifecycle: shutdown signals and the supervisor that owns background tasks.
+//!
+//! Long-running jobs are started through [`TaskSupervisor::spawn`], which hands them a
+//! [`ShutdownSignal`]. On SIGINT/SIGTERM the supervisor is marked as shutting down first, so
+//! `/health/ready` reports `Down` while the HTTP server drains in-flight requests; afterwards
+//! [`TaskSupervisor::shutdown`] waits for every task to finish and aborts the ones that do not
+//! stop within the drain timeout.
+
+use actix_web::dev::Server;
+use std::{future::Future, sync::Mutex, time::Duration};
+use tokio::{sync::watch, task::JoinHandle, time::Instant};
+
+/// Resolves once shutdown has begun.
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    pub async fn recv(&mut self) {
+        // 송신자가 사라진 경우에도 종료로 취급
+        let _ = self.0.wait_for(|shutting_down| *shutting_down).await;
+    }
+
+    pub fn is_set(&self) -> bool {
+        *self.0.borrow()
+    }
+}
+
+pub struct TaskSupervisor {
+    shutdown: watch::Sender<bool>,
+    tasks: Mutex<Vec<(&'static str, JoinHandle<()>)>>,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self {
+            shutdown: watch::channel(false).0,
+            tasks: Mutex::default(),
+        }
+    }
+}
+
+impl TaskSupervisor {
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal(self.shutdown.subscribe())
+    }
+
+    /// Starts a background task. The task should return soon after its signal resolves.
+    pub fn spawn<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handle = tokio::spawn(task(self.signal()));
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.push((name, handle));
+        tracing::debug!("Started background task `{}`", name);
+    }
+
+    /// Runs an additional HTTP server (metrics, redirect) until shutdown.
+    pub fn spawn_server(&self, name: &'static str, server: Server) {
+        self.spawn(name, move |mut shutdown| async move {
+            let handle = server.handle();
+            tokio::pin!(server);
+            tokio::select! {
+                result = &mut server => {
+                    if let Err(e) = result {
+                        tracing::error!("{} stopped: {}", name, e);
+                    }
+                }
+                _ = shutdown.recv() => {
+                    handle.stop(true).await;
+                    let _ = server.await;
+                }
+            }
+        });
+    }
+
+    // 준비 상태를 Down으로 전환 (요청은 계속 처리)
+    pub fn begin_shutdown(&self) {
+        if !self.shutdown.send_replace(true) {
+            tracing::info!("Shutdown started; readiness now reports Down");
+        }
+    }
+
+    /// Signals every task and waits up to `timeout` for them to finish; the rest are aborted.
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.begin_shutdown();
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap_or_else(|e| e.into_inner()));
+        let deadline = Instant::now() + timeout;
+
+        for (name, mut handle) in tasks {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => tracing::debug!("Background task `{}` stopped", name),
+                Ok(Err(e)) if e.is_panic() => {
+                    tracing::error!("Background task `{}` panicked", name)
+                }
+                Ok(Err(_)) => {}
+                Err(_) => {
+                    tracing::warn!(
+                        "Background task `{}` did not stop within {}s; aborting it",
+                        name,
+                        timeout.as_secs()
+                    );
+                    handle.abort();
+                }
+            }
+        }
+    }
+}
+
+/// Waits for SIGINT (Ctrl+C) or, on Unix, SIGTERM.
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received Ctrl+C"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
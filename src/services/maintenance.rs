@@ -0,0 +1,35 @@
+use crate::errors::AppError;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Process-wide maintenance mode. While it is active the maintenance middleware rejects
+/// every request except the health checks with `503 Service Unavailable`.
+#[derive(Default)]
+pub struct MaintenanceState {
+    active: AtomicBool,
+}
+
+impl MaintenanceState {
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    // 점검 모드 진입 (반환된 guard가 drop되면 자동으로 해제)
+    pub fn enter(&self) -> Result<MaintenanceGuard<'_>, AppError> {
+        if self.active.swap(true, Ordering::SeqCst) {
+            return Err(AppError::conflict("Maintenance is already in progress"));
+        }
+        tracing::warn!("Entering maintenance mode");
+        Ok(MaintenanceGuard { state: self })
+    }
+}
+
+pub struct MaintenanceGuard<'a> {
+    state: &'a MaintenanceState,
+}
+
+impl Drop for MaintenanceGuard<'_> {
+    fn drop(&mut self) {
+        self.state.active.store(false, Ordering::SeqCst);
+        tracing::warn!("Leaving maintenance mode");
+    }
+}
@@ -1,73 +1,119 @@
 use std::collections::HashMap;
 
 use crate::{
-    dto::menu::{CreateMenuRequest, MenuResponse},
+    config::db::{DbArguments, DbPool, DbTransaction},
+    dto::{
+        menu::{CreateMenuRequest, MenuResponse},
+        transfer::{ExportQueryParams, ImportQueryParams, ImportReport},
+    },
     errors::AppError,
-    middleware::auth::authenticated_user::AuthenticatedUser,
     models::MenuItem,
+    repositories::{MenuRepository, NewMenuItem},
+    services::transfer::{self, UploadedFile},
 };
-use actix_web::web;
-use sqlx::SqlitePool;
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use sqlx::Connection;
 use validator::Validate;
 
 pub async fn create_menu(
-    pool: web::Data<SqlitePool>,
-    _user: AuthenticatedUser,
-    req: web::Json<CreateMenuRequest>,
+    repo: &dyn MenuRepository,
+    req: CreateMenuRequest,
 ) -> Result<MenuResponse, AppError> {
     req.validate()?;
 
-    let display_order = req.display_order.unwrap_or(0);
-    let is_visible = req.is_visible.unwrap_or(true);
-
-    let result = sqlx::query_as!(
-        MenuItem,
-        r#"
-        INSERT INTO menu_item (name, path, icon, parent_id, display_order, is_visible, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
-        RETURNING *
-        "#,
-        req.name,
-        req.path,
-        req.icon,
-        req.parent_id,
-        display_order,
-        is_visible
-    )
-        .fetch_one(pool.get_ref())
+    let result = repo
+        .create(NewMenuItem {
+            name: req.name,
+            path: req.path,
+            icon: req.icon,
+            parent_id: req.parent_id,
+            display_order: req.display_order.unwrap_or(0).into(),
+            is_visible: req.is_visible.unwrap_or(true),
+        })
         .await?;
 
     Ok(MenuResponse::from(result))
 }
 
-/// Fetches all menu items from the database, ordered by display_order,
+/// Fetches all menu items ordered by display_order
 /// and converts them into a hierarchical structure of `MenuResponse`.
 ///
 /// # Arguments
 ///
-/// * `pool` - A reference to the SqlitePool for database access.
-/// * `_user` - The authenticated user making the request. Currently unused.
+/// * `repo` - The menu repository.
 ///
 /// # Returns
 ///
 /// * `Result<Vec<MenuResponse>, AppError>` - On success, returns a vector of `MenuResponse`
 ///   representing the menu items in a hierarchical tree structure. Returns `AppError` on failure.
-pub async fn get_menu_array(
-    pool: web::Data<SqlitePool>,
-    _user: AuthenticatedUser,
-) -> Result<Vec<MenuResponse>, AppError> {
+pub async fn get_menu_array(repo: &dyn MenuRepository) -> Result<Vec<MenuResponse>, AppError> {
     // 모든 메뉴 항목 조회 (display_order 순으로)
-    let all_menus = sqlx::query_as!(
-        MenuItem,
-        "SELECT * FROM menu_item ORDER BY display_order ASC"
-    )
-    .fetch_all(pool.get_ref())
-    .await?;
+    let all_menus = repo.list_all().await?;
 
     // 계층 구조로 변환
     Ok(build_menu_tree(all_menus))
 }
 
+// 계층 구조 없이 평탄한 목록으로 내보냄 (parent_id로 관계 표현)
+pub async fn export_menu_array(
+    pool: &DbPool,
+    export_params: &ExportQueryParams,
+) -> Result<impl Stream<Item = Result<Bytes, AppError>>, AppError> {
+    Ok(transfer::export_stream::<MenuItem, MenuResponse>(
+        pool.clone(),
+        "SELECT * FROM menu_item ORDER BY display_order ASC, id ASC".to_string(),
+        DbArguments::default(),
+        export_params.format,
+    ))
+}
+
+// 부모 메뉴가 먼저 오도록 정렬된 파일을 가정 (parent_id는 기존 ID 기준)
+pub async fn import_menu_array(
+    pool: &DbPool,
+    import_params: &ImportQueryParams,
+    file: &UploadedFile,
+) -> Result<ImportReport, AppError> {
+    let format = transfer::resolve_format(import_params.format, file.filename.as_deref());
+    let rows = transfer::parse_rows::<CreateMenuRequest>(format, &file.data);
+
+    let mut report = ImportReport::new(import_params.dry_run);
+    let mut tx = pool.begin().await?;
+    for (row, parsed) in rows {
+        let result = match parsed {
+            Ok(req) => insert_menu_row(&mut tx, &req).await,
+            Err(e) => Err(e),
+        };
+        report.record(row, result);
+    }
+
+    transfer::finish_import(tx, report).await
+}
+
+async fn insert_menu_row(
+    tx: &mut DbTransaction<'_>,
+    req: &CreateMenuRequest,
+) -> Result<(), AppError> {
+    let display_order = req.display_order.unwrap_or(0);
+    let is_visible = req.is_visible.unwrap_or(true);
+
+    let mut savepoint = tx.begin().await?;
+    sqlx::query(
+        "INSERT INTO menu_item (name, path, icon, parent_id, display_order, is_visible) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(&req.name)
+    .bind(&req.path)
+    .bind(&req.icon)
+    .bind(req.parent_id)
+    .bind(display_order)
+    .bind(is_visible)
+    .execute(&mut *savepoint)
+    .await?;
+    savepoint.commit().await?;
+
+    Ok(())
+}
+
 // 메뉴 계층 구조 빌드 헬퍼 함수
 fn build_menu_tree(menus: Vec<MenuItem>) -> Vec<MenuResponse> {
     let mut map: HashMap<i64, MenuResponse> = HashMap::new();
@@ -0,0 +1,227 @@
+//! Prometheus metrics.
+//!
+//! All metrics live in the process-wide [`METRICS`] registry so that code without access to
+//! app data (such as `AppError::error_response`) can record them. Gauges that describe the
+//! current state (connection pools, active sessions) are refreshed when `/metrics` is scraped.
+
+use crate::config::db::{DbPool, DbPools};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{layer::Context, Layer};
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests: IntCounterVec,
+    pub http_request_duration: HistogramVec,
+    pub errors: IntCounterVec,
+    pub logins: IntCounterVec,
+    active_sessions: IntGauge,
+    db_pool_connections: IntGaugeVec,
+    db_query_duration: HistogramVec,
+    // 사용자별 마지막으로 발급한 토큰의 만료 시각 (UNIX 초)
+    sessions: Mutex<HashMap<i64, i64>>,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("admin_server".to_string()), None)
+            .expect("valid registry prefix");
+
+        let http_requests = IntCounterVec::new(
+            Opts::new(
+                "http_requests_total",
+                "HTTP requests by route template and status",
+            ),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency by route template and status",
+            ),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let errors = IntCounterVec::new(
+            Opts::new("errors_total", "Error responses by AppError variant"),
+            &["variant"],
+        )
+        .unwrap();
+        let logins = IntCounterVec::new(
+            Opts::new("logins_total", "Login attempts by result"),
+            &["result"],
+        )
+        .unwrap();
+        let active_sessions = IntGauge::new(
+            "active_sessions",
+            "Users holding an unexpired access token issued by this instance",
+        )
+        .unwrap();
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database pool connections by state"),
+            &["pool", "state"],
+        )
+        .unwrap();
+        let db_query_duration = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "Database query latency")
+                .buckets(vec![0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0]),
+            &["operation"],
+        )
+        .unwrap();
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(errors.clone()),
+            Box::new(logins.clone()),
+            Box::new(active_sessions.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(db_query_duration.clone()),
+        ] {
+            registry.register(collector).expect("unique metric names");
+        }
+
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            errors,
+            logins,
+            active_sessions,
+            db_pool_connections,
+            db_query_duration,
+            sessions: Mutex::default(),
+        }
+    }
+
+    pub fn record_login(&self, result: &str) {
+        self.logins.with_label_values(&[result]).inc();
+    }
+
+    pub fn record_session(&self, user_id: i64, expires_at: i64) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.insert(user_id, expires_at);
+    }
+
+    fn update_sessions(&self) {
+        let now = chrono::Utc::now().timestamp();
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.retain(|_, expires_at| *expires_at > now);
+        self.active_sessions.set(sessions.len() as i64);
+    }
+
+    fn update_pool(&self, name: &str, pool: &DbPool) {
+        let size = pool.size() as i64;
+        let idle = pool.num_idle() as i64;
+        let max = pool.options().get_max_connections() as i64;
+        for (state, value) in [("idle", idle), ("in_use", size - idle), ("max", max)] {
+            self.db_pool_connections
+                .with_label_values(&[name, state])
+                .set(value);
+        }
+    }
+
+    /// Refreshes the state gauges and renders every metric in the Prometheus text format.
+    pub fn render(&self, pools: &DbPools) -> String {
+        self.update_sessions();
+        self.update_pool("read", &pools.read);
+        self.update_pool("write", &pools.write);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding does not fail");
+        String::from_utf8(buffer).expect("metrics are valid UTF-8")
+    }
+}
+
+/// Tracing layer that turns sqlx's per-statement log events (`sqlx::query`) into the
+/// `db_query_duration_seconds` histogram.
+pub struct QueryMetricsLayer;
+
+impl<S: Subscriber> Layer<S> for QueryMetricsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "sqlx::query" {
+            return;
+        }
+
+        let mut visitor = QueryVisitor::default();
+        event.record(&mut visitor);
+        if let Some(elapsed) = visitor.elapsed_secs {
+            METRICS
+                .db_query_duration
+                .with_label_values(&[visitor.operation.unwrap_or("other")])
+                .observe(elapsed);
+        }
+    }
+}
+
+// sqlx::query 이벤트 필드 (otel 기능의 쿼리 span 생성에서도 사용)
+#[derive(Default)]
+pub(crate) struct QueryVisitor {
+    pub(crate) elapsed_secs: Option<f64>,
+    pub(crate) operation: Option<&'static str>,
+    pub(crate) statement: Option<String>,
+}
+
+impl QueryVisitor {
+    fn record_text(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => {
+                self.operation = query_operation(value);
+                self.statement = Some(value.to_string());
+            }
+            // 요약과 전체 SQL이 같으면 빈 문자열
+            "db.statement" if !value.trim().is_empty() => {
+                self.statement = Some(value.trim().to_string())
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit for QueryVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_text(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record_text(field, &format!("{:?}", value));
+    }
+}
+
+// 라벨 수가 늘어나지 않도록 SQL 첫 단어만 사용
+fn query_operation(summary: &str) -> Option<&'static str> {
+    let keyword = summary
+        .trim_start_matches(|c: char| c == '"' || c.is_whitespace())
+        .split_whitespace()
+        .next()?
+        .to_ascii_uppercase();
+    Some(match keyword.as_str() {
+        "SELECT" | "WITH" => "select",
+        "INSERT" => "insert",
+        "UPDATE" => "update",
+        "DELETE" => "delete",
+        "BEGIN" | "COMMIT" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" => "transaction",
+        _ => "other",
+    })
+}
@@ -1,6 +1,24 @@
+pub mod access_review;
+pub mod admin;
 pub mod auth;
+pub mod authz;
+#[cfg(feature = "sqlite")]
+pub mod backup;
+pub mod dormancy;
 pub mod health;
+pub mod lifecycle;
+pub mod maintenance;
 pub mod menu;
+pub mod metrics;
 pub mod permission;
+pub mod rate_limit;
+pub mod scheduler;
+pub mod settings;
+pub mod setup;
+pub mod snapshot;
+#[cfg(feature = "otel")]
+pub mod telemetry;
+pub mod tls;
+pub mod transfer;
 pub mod user;
 pub mod user_type;
@@ -1,37 +1,43 @@
 use crate::{
+    config::db::{DbArguments, DbPool, DbTransaction},
     dto::{
         common::ListQueryParams,
-        permission::{CreatePermissionRequest, PermissionResponse},
+        permission::{
+            CreatePermissionRequest, PermissionResponse, SetUserPermissionRequest,
+            UserPermissionResponse,
+        },
+        transfer::{ExportQueryParams, ImportQueryParams, ImportReport},
     },
     errors::AppError,
-    models::Permission,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    models::{AdminUser, Permission, UserPermissionAudit},
+    repositories::{PermissionRepository, UserPermissionChange, UserRepository},
+    services::{
+        authz,
+        transfer::{self, UploadedFile},
+    },
 };
-use actix_web::web;
-use sqlx::SqlitePool;
+use actix_web::web::Bytes;
+use chrono::Utc;
+use futures_util::Stream;
+use sqlx::Connection;
 use validator::Validate;
 
 pub async fn create_permission(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    req: web::Json<CreatePermissionRequest>,
+    repo: &dyn PermissionRepository,
+    req: CreatePermissionRequest,
 ) -> Result<i64, AppError> {
     req.validate()?;
-    let inserted_id = sqlx::query!(
-        "INSERT INTO permission (code, description) VALUES (?, ?) RETURNING id",
-        req.code,
-        req.description
-    )
-    .fetch_one(pool.get_ref())
-    .await?
-    .id;
-
-    Ok(inserted_id)
+    let permission = repo.create(&req.code, req.description.as_deref()).await?;
+
+    permission
+        .id
+        .ok_or_else(|| AppError::Conflict(String::from("Failed to create permission")))
 }
 
 pub async fn get_permissions(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    query: web::Query<ListQueryParams>,
+    repo: &dyn PermissionRepository,
+    query: &ListQueryParams,
 ) -> Result<Vec<PermissionResponse>, AppError> {
     let limit = query.limit.unwrap_or(10);
     let page = query.page.unwrap_or(1);
@@ -40,28 +46,199 @@ pub async fn get_permissions(
     }
     let offset = (page - 1) * limit;
 
-    let permissions = sqlx::query_as!(
-        Permission,
-        "SELECT * FROM permission ORDER BY code LIMIT ? OFFSET ?",
-        limit,
-        offset
-    )
-    .fetch_all(pool.get_ref())
-    .await?;
+    let permissions = repo.list(limit, offset).await?;
+
+    Ok(permissions
+        .into_iter()
+        .map(PermissionResponse::from)
+        .collect())
+}
 
-    Ok(permissions.into_iter().map(PermissionResponse::from).collect())
+pub async fn export_permissions(
+    pool: &DbPool,
+    export_params: &ExportQueryParams,
+) -> Result<impl Stream<Item = Result<Bytes, AppError>>, AppError> {
+    Ok(transfer::export_stream::<Permission, PermissionResponse>(
+        pool.clone(),
+        "SELECT * FROM permission ORDER BY code".to_string(),
+        DbArguments::default(),
+        export_params.format,
+    ))
+}
+
+pub async fn import_permissions(
+    pool: &DbPool,
+    import_params: &ImportQueryParams,
+    file: &UploadedFile,
+) -> Result<ImportReport, AppError> {
+    let format = transfer::resolve_format(import_params.format, file.filename.as_deref());
+    let rows = transfer::parse_rows::<CreatePermissionRequest>(format, &file.data);
+
+    let mut report = ImportReport::new(import_params.dry_run);
+    let mut tx = pool.begin().await?;
+    for (row, parsed) in rows {
+        let result = match parsed {
+            Ok(req) => insert_permission_row(&mut tx, &req).await,
+            Err(e) => Err(e),
+        };
+        report.record(row, result);
+    }
+
+    transfer::finish_import(tx, report).await
+}
+
+async fn insert_permission_row(
+    tx: &mut DbTransaction<'_>,
+    req: &CreatePermissionRequest,
+) -> Result<(), AppError> {
+    let mut savepoint = tx.begin().await?;
+    sqlx::query("INSERT INTO permission (code, description) VALUES ($1, $2)")
+        .bind(&req.code)
+        .bind(&req.description)
+        .execute(&mut *savepoint)
+        .await?;
+    savepoint.commit().await?;
+
+    Ok(())
 }
 
 pub async fn get_permission_by_id(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    path: web::Path<i32>,
+    repo: &dyn PermissionRepository,
+    id: i64,
 ) -> Result<PermissionResponse, AppError> {
-    let id = path.into_inner();
-    let permission = sqlx::query_as!(Permission, "SELECT * FROM permission WHERE id = ?", id)
-        .fetch_one(pool.get_ref())
-        .await
-        .map_err(|_| AppError::not_found("Permission not found"))?;
+    let permission = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Permission not found"))?;
 
     Ok(PermissionResponse::from(permission))
 }
+
+async fn find_user(users: &dyn UserRepository, user_id: i64) -> Result<AdminUser, AppError> {
+    users
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User not found"))
+}
+
+async fn find_by_code(
+    permissions: &dyn PermissionRepository,
+    code: &str,
+) -> Result<Permission, AppError> {
+    permissions
+        .find_by_code(code)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Permission `{}` not found", code)))
+}
+
+/// Per-user overrides of `user_id`, including expired ones.
+pub async fn list_user_permissions(
+    users: &dyn UserRepository,
+    permissions: &dyn PermissionRepository,
+    user_id: i64,
+) -> Result<Vec<UserPermissionResponse>, AppError> {
+    let user = find_user(users, user_id).await?;
+    let now = Utc::now();
+    Ok(permissions
+        .user_overrides(user.id)
+        .await?
+        .into_iter()
+        .map(|user_permission| UserPermissionResponse::new(user_permission, now))
+        .collect())
+}
+
+/// Allows or denies `code` for one user, replacing any existing override for it.
+pub async fn set_user_permission(
+    users: &dyn UserRepository,
+    permissions: &dyn PermissionRepository,
+    actor: &AuthenticatedUser,
+    user_id: i64,
+    code: &str,
+    req: SetUserPermissionRequest,
+) -> Result<UserPermissionResponse, AppError> {
+    req.validate()?;
+    let now = Utc::now();
+    if req.expires_at.is_some_and(|expires_at| expires_at <= now) {
+        return Err(AppError::bad_request("expires_at must be in the future"));
+    }
+    // 전체 권한은 사용자 종류로만 부여 (사용자별 예외로 관리자 권한이 생기거나 사라지지 않도록)
+    if code == authz::WILDCARD {
+        return Err(AppError::bad_request(
+            "The wildcard permission can only be granted through a user type",
+        ));
+    }
+    let user = find_user(users, user_id).await?;
+    let permission = find_by_code(permissions, code).await?;
+    let permission_id = permission
+        .id
+        .ok_or_else(|| AppError::not_found("Permission not found"))?;
+
+    let change = UserPermissionChange {
+        user_id: user.id,
+        username: &user.username,
+        permission_id,
+        permission_code: &permission.code,
+        changed_by: &actor.username,
+    };
+    let user_permission = permissions
+        .set_user_override(
+            &change,
+            req.effect.as_str(),
+            req.reason.as_deref(),
+            req.expires_at.map(|t| t.naive_utc()),
+        )
+        .await?;
+    tracing::info!(
+        "`{}` set a direct `{}` override of `{}` for user `{}`",
+        actor.username,
+        user_permission.effect,
+        permission.code,
+        user.username
+    );
+    Ok(UserPermissionResponse::new(user_permission, now))
+}
+
+pub async fn remove_user_permission(
+    users: &dyn UserRepository,
+    permissions: &dyn PermissionRepository,
+    actor: &AuthenticatedUser,
+    user_id: i64,
+    code: &str,
+) -> Result<(), AppError> {
+    let user = find_user(users, user_id).await?;
+    let permission = find_by_code(permissions, code).await?;
+    let permission_id = permission
+        .id
+        .ok_or_else(|| AppError::not_found("Permission not found"))?;
+
+    let change = UserPermissionChange {
+        user_id: user.id,
+        username: &user.username,
+        permission_id,
+        permission_code: &permission.code,
+        changed_by: &actor.username,
+    };
+    if !permissions.remove_user_override(&change).await? {
+        return Err(AppError::NotFound(format!(
+            "User `{}` has no override for `{}`",
+            user.username, permission.code
+        )));
+    }
+    tracing::info!(
+        "`{}` removed the direct override of `{}` for user `{}`",
+        actor.username,
+        permission.code,
+        user.username
+    );
+    Ok(())
+}
+
+/// Every change made to the user's overrides, newest first.
+pub async fn user_permission_history(
+    users: &dyn UserRepository,
+    permissions: &dyn PermissionRepository,
+    user_id: i64,
+) -> Result<Vec<UserPermissionAudit>, AppError> {
+    let user = find_user(users, user_id).await?;
+    permissions.user_override_history(user.id).await
+}
@@ -0,0 +1,117 @@
+//! In-memory token-bucket rate limiting.
+//!
+//! Each request is matched against the route limits (first match wins) and charged to the
+//! bucket of that route and the request's key (client IP, user or API key). A bucket holds up
+//! to `requests` tokens and refills continuously at `requests / window_seconds` tokens per
+//! second. The limits themselves are runtime settings, so the middleware passes the current
+//! rules on every request and buckets are keyed by the rule rather than its position.
+
+use crate::config::env::RouteRateLimit;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+// 이 횟수마다 가득 찬(오래 쓰이지 않은) 버킷 정리
+const CLEANUP_INTERVAL: u64 = 1024;
+
+/// Result of charging one request to a bucket.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    pub window_seconds: u64,
+    /// Seconds until the bucket is full again.
+    pub reset_seconds: u64,
+    /// Seconds until the next request would be allowed (0 when allowed).
+    pub retry_after_seconds: u64,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    refill: Duration,
+}
+
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<(String, String), Bucket>>,
+    calls: AtomicU64,
+}
+
+/// The first route limit matching the request.
+pub fn route_for<'a>(
+    routes: &'a [RouteRateLimit],
+    method: &str,
+    path: &str,
+) -> Option<&'a RouteRateLimit> {
+    routes.iter().find(|route| {
+        let path_matches = match route.path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => path == route.path,
+        };
+        path_matches
+            && (route.methods.is_empty()
+                || route.methods.iter().any(|m| m.eq_ignore_ascii_case(method)))
+    })
+}
+
+impl RateLimiter {
+    /// Takes one token from the bucket of `route` and `key`, if available.
+    pub fn check(&self, definition: &RouteRateLimit, key: &str, now: Instant) -> RateLimitDecision {
+        let capacity = f64::from(definition.requests);
+        let per_second = capacity / definition.window_seconds as f64;
+        // 규칙 순서가 바뀌어도 같은 버킷을 쓰도록 위치 대신 경로와 메서드로 구분
+        // (한도만 바꾸면 소진된 버킷을 그대로 이어 씀)
+        let route = format!("{} {}", definition.methods.join(","), definition.path);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if self
+            .calls
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(CLEANUP_INTERVAL)
+        {
+            self.cleanup(&mut buckets, now);
+        }
+
+        let refill = Duration::from_secs(definition.window_seconds);
+        let bucket = buckets.entry((route, key.to_string())).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+            refill,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+        bucket.refill = refill;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+        let seconds_until = |tokens: f64| (tokens.max(0.0) / per_second).ceil() as u64;
+
+        RateLimitDecision {
+            allowed,
+            limit: definition.requests,
+            remaining: bucket.tokens.floor() as u32,
+            window_seconds: definition.window_seconds,
+            reset_seconds: seconds_until(capacity - bucket.tokens),
+            retry_after_seconds: if allowed {
+                0
+            } else {
+                seconds_until(1.0 - bucket.tokens).max(1)
+            },
+        }
+    }
+
+    // 지금 가득 차 있을 버킷은 새로 만든 것과 같으므로 삭제
+    fn cleanup(&self, buckets: &mut HashMap<(String, String), Bucket>, now: Instant) {
+        buckets.retain(|_, bucket| now.saturating_duration_since(bucket.updated) < bucket.refill);
+    }
+}
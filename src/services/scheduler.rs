@@ -0,0 +1,407 @@
+//! In-process job scheduler.
+//!
+//! Jobs are registered at startup with a cron expression (`scheduler.schedules.<job>`,
+//! evaluated in UTC) or a fixed interval, and run inside supervised background tasks. Every
+//! run, scheduled or manual, first takes a row lock in `job_lock` so only one instance of a
+//! multi-instance deployment executes a job at a time, and records its outcome in `job_run`.
+//! While a job runs, its lock is renewed every third of `scheduler.lock_ttl_seconds`, so a
+//! lock whose `locked_until` has passed is considered abandoned and can be taken over. A run
+//! that loses its lock to another instance is cancelled and recorded as failed.
+//! Scheduled runs also record the occurrence (slot) they ran for in `job_lock.last_slot`, so an
+//! instance that wakes up late for a slot another instance already ran skips it.
+
+use crate::{
+    config::env::SchedulerConfig,
+    dto::job::JobResponse,
+    errors::AppError,
+    models::JobRun,
+    repositories::{AccessReviewRepository, JobRepository, NewJobRun, PermissionRepository},
+    services::lifecycle::TaskSupervisor,
+};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use std::{future::Future, pin::Pin, str::FromStr, sync::Arc, time::Duration};
+
+pub const TRIGGER_SCHEDULE: &str = "schedule";
+pub const TRIGGER_MANUAL: &str = "manual";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_SUCCEEDED: &str = "succeeded";
+pub const STATUS_FAILED: &str = "failed";
+
+const DEFAULT_RUNS_LIMIT: i64 = 20;
+const MAX_RUNS_LIMIT: i64 = 100;
+const MIN_LOCK_HEARTBEAT: Duration = Duration::from_millis(100);
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<String, AppError>> + Send>>;
+
+pub enum JobSchedule {
+    Cron(Box<cron::Schedule>),
+    Every(Duration),
+}
+
+impl JobSchedule {
+    pub fn cron(expression: &str) -> Result<Self> {
+        let schedule = cron::Schedule::from_str(expression)
+            .map_err(|e| anyhow::anyhow!("Invalid cron expression `{}`: {}", expression, e))?;
+        Ok(Self::Cron(Box::new(schedule)))
+    }
+
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Cron(schedule) => schedule.after(&now).next(),
+            // 인스턴스마다 시작 시각이 달라도 같은 slot이 되도록 epoch 기준 배수로 정렬
+            Self::Every(interval) => {
+                let secs = interval.as_secs().max(1) as i64;
+                DateTime::from_timestamp((now.timestamp().div_euclid(secs) + 1) * secs, 0)
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Cron(schedule) => schedule.source().to_string(),
+            Self::Every(interval) => format!("every {}s", interval.as_secs()),
+        }
+    }
+}
+
+/// A named unit of work. The returned message is stored with a successful run.
+pub struct Job {
+    pub name: &'static str,
+    pub description: &'static str,
+    schedule: Option<JobSchedule>,
+    run: Box<dyn Fn() -> JobFuture + Send + Sync>,
+}
+
+impl Job {
+    pub fn new<F, Fut>(name: &'static str, description: &'static str, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, AppError>> + Send + 'static,
+    {
+        Self {
+            name,
+            description,
+            schedule: None,
+            run: Box::new(move || Box::pin(run())),
+        }
+    }
+
+    // 설정(scheduler.schedules)에 항목이 있으면 그 값이 우선
+    pub fn with_schedule(mut self, schedule: Option<JobSchedule>) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn schedule(&self) -> Option<&JobSchedule> {
+        self.schedule.as_ref()
+    }
+}
+
+pub struct Scheduler {
+    repo: Arc<dyn JobRepository>,
+    jobs: Vec<Arc<Job>>,
+    instance_id: String,
+    lock_ttl: Duration,
+    enabled: bool,
+}
+
+impl Scheduler {
+    /// Applies `config.schedules` to `jobs`; naming a job that does not exist is an error.
+    pub fn new(
+        repo: Arc<dyn JobRepository>,
+        config: &SchedulerConfig,
+        mut jobs: Vec<Job>,
+    ) -> Result<Self> {
+        for (name, expression) in &config.schedules {
+            let Some(job) = jobs.iter_mut().find(|job| job.name == name) else {
+                bail!("scheduler.schedules: unknown job `{}`", name);
+            };
+            job.schedule = match expression.trim() {
+                "" => None,
+                expression => Some(JobSchedule::cron(expression)?),
+            };
+        }
+
+        // 여러 인스턴스가 같은 DB를 쓸 때 실행 이력에서 구분할 수 있도록 호스트 이름 포함
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "local".to_string());
+        let instance_id = format!(
+            "{}-{}",
+            host,
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+
+        Ok(Self {
+            repo,
+            jobs: jobs.into_iter().map(Arc::new).collect(),
+            instance_id,
+            lock_ttl: Duration::from_secs(config.lock_ttl_seconds),
+            enabled: config.enabled,
+        })
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    fn job(&self, name: &str) -> Result<&Arc<Job>, AppError> {
+        self.jobs
+            .iter()
+            .find(|job| job.name == name)
+            .ok_or_else(|| AppError::NotFound(format!("Job `{}` not found", name)))
+    }
+
+    pub async fn list(&self) -> Result<Vec<JobResponse>, AppError> {
+        let now = Utc::now();
+        let mut jobs = Vec::with_capacity(self.jobs.len());
+        for job in &self.jobs {
+            let last_run = self.repo.list_runs(job.name, 1).await?.into_iter().next();
+            jobs.push(JobResponse {
+                name: job.name.to_string(),
+                description: job.description.to_string(),
+                schedule: job.schedule().map(JobSchedule::describe),
+                next_run_at: job
+                    .schedule()
+                    .filter(|_| self.enabled)
+                    .and_then(|schedule| schedule.next_after(now)),
+                last_run,
+            });
+        }
+        Ok(jobs)
+    }
+
+    pub async fn runs(&self, name: &str, limit: Option<i64>) -> Result<Vec<JobRun>, AppError> {
+        let job = self.job(name)?;
+        let limit = limit.unwrap_or(DEFAULT_RUNS_LIMIT).clamp(1, MAX_RUNS_LIMIT);
+        self.repo.list_runs(job.name, limit).await
+    }
+
+    // 잠금을 얻은 경우에만 실행 기록을 남기고 Some 반환. slot은 예약 실행의 예정 시각
+    async fn start(
+        &self,
+        job: &Job,
+        trigger_type: &str,
+        started_by: Option<i64>,
+        slot: Option<DateTime<Utc>>,
+    ) -> Result<Option<JobRun>, AppError> {
+        let now = Utc::now();
+        let until = now + chrono::Duration::from_std(self.lock_ttl).unwrap_or_default();
+        if !self
+            .repo
+            .try_lock(
+                job.name,
+                &self.instance_id,
+                now.naive_utc(),
+                until.naive_utc(),
+                slot.map(|slot| slot.naive_utc()),
+            )
+            .await?
+        {
+            return Ok(None);
+        }
+
+        let run = NewJobRun {
+            job_name: job.name,
+            trigger_type,
+            started_by,
+            instance_id: &self.instance_id,
+        };
+        match self.repo.start_run(&run).await {
+            Ok(run) => Ok(Some(run)),
+            Err(e) => {
+                self.repo.unlock(job.name, &self.instance_id).await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    async fn execute(&self, job: &Job, run: JobRun) -> Result<JobRun, AppError> {
+        let result = tokio::select! {
+            result = (job.run)() => result,
+            lost = self.keep_locked(job) => Err(lost),
+        };
+        let (status, message) = match result {
+            Ok(message) => {
+                tracing::info!("Job `{}` succeeded: {}", job.name, message);
+                (STATUS_SUCCEEDED, message)
+            }
+            Err(e) => {
+                let message = match &e {
+                    AppError::InternalServerError(e) => format!("{:#}", e),
+                    e => e.to_string(),
+                };
+                tracing::error!("Job `{}` failed: {}", job.name, message);
+                (STATUS_FAILED, message)
+            }
+        };
+
+        let finished = self.repo.finish_run(run.id, status, Some(&message)).await;
+        self.repo.unlock(job.name, &self.instance_id).await?;
+        finished
+    }
+
+    // 실행 중 잠금을 주기적으로 연장. 다른 인스턴스가 잠금을 가져간 경우에만 반환
+    async fn keep_locked(&self, job: &Job) -> AppError {
+        let mut heartbeat = tokio::time::interval((self.lock_ttl / 3).max(MIN_LOCK_HEARTBEAT));
+        heartbeat.tick().await; // 첫 tick은 즉시 완료됨
+        loop {
+            heartbeat.tick().await;
+            let until = Utc::now() + chrono::Duration::from_std(self.lock_ttl).unwrap_or_default();
+            match self
+                .repo
+                .extend_lock(job.name, &self.instance_id, until.naive_utc())
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    return AppError::Conflict(format!(
+                        "Job `{}` lost its lock to another instance and was cancelled",
+                        job.name
+                    ))
+                }
+                // 일시적인 DB 오류는 다음 주기에 다시 시도 (잠금이 만료되기 전까지 두 번 더 기회가 있음)
+                Err(e) => tracing::warn!("Failed to extend the lock of job `{}`: {}", job.name, e),
+            }
+        }
+    }
+
+    /// Runs `name` to completion. Returns `None` when another instance holds the job's lock.
+    pub async fn run_now(
+        &self,
+        name: &str,
+        trigger_type: &str,
+        started_by: Option<i64>,
+    ) -> Result<Option<JobRun>, AppError> {
+        let job = self.job(name)?.clone();
+        match self.start(&job, trigger_type, started_by, None).await? {
+            Some(run) => self.execute(&job, run).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Runs the scheduled occurrence `slot` of `name`. Returns `None` when another instance
+    /// holds the job's lock or has already run this (or a later) slot.
+    pub async fn run_scheduled(
+        &self,
+        name: &str,
+        slot: DateTime<Utc>,
+    ) -> Result<Option<JobRun>, AppError> {
+        let job = self.job(name)?.clone();
+        match self.start(&job, TRIGGER_SCHEDULE, None, Some(slot)).await? {
+            Some(run) => self.execute(&job, run).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Starts a manual run in the background and returns its `running` record.
+    pub async fn trigger(
+        self: &Arc<Self>,
+        supervisor: &TaskSupervisor,
+        name: &str,
+        started_by: i64,
+    ) -> Result<JobRun, AppError> {
+        let job = self.job(name)?.clone();
+        let run = self
+            .start(&job, TRIGGER_MANUAL, Some(started_by), None)
+            .await?
+            .ok_or_else(|| AppError::Conflict(format!("Job `{}` is already running", name)))?;
+        tracing::info!(
+            "Job `{}` triggered manually by user {}",
+            job.name,
+            started_by
+        );
+
+        let (scheduler, started) = (self.clone(), run.clone());
+        // 진행 중인 실행은 끝까지 완료 (종료 제한 시간을 넘기면 중단됨)
+        supervisor.spawn(job.name, move |_| async move {
+            if let Err(e) = scheduler.execute(&job, started).await {
+                tracing::error!("Failed to record the result of job `{}`: {}", job.name, e);
+            }
+        });
+        Ok(run)
+    }
+
+    /// Starts one supervised task per scheduled job.
+    pub fn spawn(self: &Arc<Self>, supervisor: &TaskSupervisor) {
+        if !self.enabled {
+            tracing::info!("Scheduler disabled; jobs can only be run manually");
+            return;
+        }
+        for job in &self.jobs {
+            let Some(schedule) = job.schedule() else {
+                continue;
+            };
+            tracing::info!("Scheduling job `{}` ({})", job.name, schedule.describe());
+
+            let (scheduler, job) = (self.clone(), job.clone());
+            supervisor.spawn(job.name, move |mut shutdown| async move {
+                let Some(schedule) = job.schedule() else {
+                    return;
+                };
+                while let Some(next) = schedule.next_after(Utc::now()) {
+                    let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                    tokio::select! {
+                        _ = tokio::time::sleep(wait) => {}
+                        _ = shutdown.recv() => break,
+                    }
+                    match scheduler.run_scheduled(job.name, next).await {
+                        Ok(Some(_)) => {}
+                        Ok(None) => tracing::debug!(
+                            "Skipping job `{}`: running or already run on another instance",
+                            job.name
+                        ),
+                        Err(e) => tracing::error!("Failed to run job `{}`: {}", job.name, e),
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Deletes finished runs older than `retention_days`.
+pub fn prune_job_runs_job(repo: Arc<dyn JobRepository>, retention_days: u64) -> Job {
+    Job::new(
+        "prune_job_runs",
+        "Deletes job run history older than scheduler.history_retention_days",
+        move || {
+            let repo = repo.clone();
+            async move {
+                let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+                let deleted = repo.delete_runs_before(cutoff.naive_utc()).await?;
+                Ok(format!(
+                    "Deleted {} runs older than {} days",
+                    deleted, retention_days
+                ))
+            }
+        },
+    )
+}
+
+/// Deletes user permission override history and closed access reviews older than
+/// `retention_days`; with 0 the audit history is kept forever and the job does nothing.
+pub fn prune_audit_history_job(
+    permissions: Arc<dyn PermissionRepository>,
+    reviews: Arc<dyn AccessReviewRepository>,
+    retention_days: u64,
+) -> Job {
+    Job::new(
+        "prune_audit_history",
+        "Deletes audit history older than scheduler.audit_retention_days (0 keeps it forever)",
+        move || {
+            let (permissions, reviews) = (permissions.clone(), reviews.clone());
+            async move {
+                if retention_days == 0 {
+                    return Ok("Audit history is retained indefinitely".to_string());
+                }
+                let cutoff =
+                    (Utc::now() - chrono::Duration::days(retention_days as i64)).naive_utc();
+                let history = permissions.delete_override_history_before(cutoff).await?;
+                let campaigns = reviews.delete_closed_before(cutoff).await?;
+                Ok(format!(
+                    "Deleted {} permission changes and {} closed access reviews older than {} days",
+                    history, campaigns, retention_days
+                ))
+            }
+        },
+    )
+}
@@ -0,0 +1,346 @@
+//! Runtime settings stored in the `system_setting` table.
+//!
+//! Every setting is declared in [`DEFINITIONS`] with its type and default (usually taken from
+//! the configuration file). Values saved through the admin API override the default and are
+//! kept in an in-process cache that is reloaded whenever a setting changes, so components read
+//! them at request time through [`Settings::get`] without a restart. Other instances of a
+//! multi-instance deployment pick the change up on their next periodic reload
+//! (`server.settings_reload_interval_seconds`, see [`spawn_reload_job`]).
+
+use crate::{
+    config::env::{Env, RouteRateLimit},
+    dto::settings::SettingResponse,
+    errors::AppError,
+    middleware::cors,
+    models::SystemSetting,
+    repositories::SettingRepository,
+    services::lifecycle::TaskSupervisor,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, RwLock, RwLockReadGuard},
+    time::Duration,
+};
+
+/// A typed setting key.
+pub struct Setting<T> {
+    pub key: &'static str,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Setting<T> {
+    const fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            _type: PhantomData,
+        }
+    }
+}
+
+pub const JWT_EXPIRES_IN_SECONDS: Setting<i64> = Setting::new("auth.jwt_expires_in_seconds");
+pub const LOGIN_LOCKOUT_THRESHOLD: Setting<u32> = Setting::new("auth.lockout_threshold");
+pub const LOGIN_LOCKOUT_SECONDS: Setting<u64> = Setting::new("auth.lockout_seconds");
+pub const CORS_ALLOWED_ORIGINS: Setting<Vec<String>> = Setting::new("cors.allowed_origins");
+pub const RATE_LIMIT_ENABLED: Setting<bool> = Setting::new("rate_limit.enabled");
+pub const RATE_LIMIT_ROUTES: Setting<Vec<RouteRateLimit>> = Setting::new("rate_limit.routes");
+
+pub enum SettingType {
+    Integer { min: i64, max: i64 },
+    Boolean,
+    StringList,
+    OriginList,      // CORS 출처 패턴 목록
+    RateLimitRoutes, // 경로별 요청 제한 규칙 목록
+}
+
+impl SettingType {
+    fn schema(&self) -> Value {
+        match self {
+            SettingType::Integer { min, max } => {
+                json!({ "type": "integer", "minimum": min, "maximum": max })
+            }
+            SettingType::Boolean => json!({ "type": "boolean" }),
+            SettingType::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+            SettingType::OriginList => json!({
+                "type": "array",
+                "items": { "type": "string", "examples": ["https://admin.example.com", "https://*.example.com"] }
+            }),
+            SettingType::RateLimitRoutes => json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["path", "requests", "window_seconds", "key"],
+                    "properties": {
+                        "path": { "type": "string", "examples": ["/api/v1/*"] },
+                        "methods": { "type": "array", "items": { "type": "string" } },
+                        "requests": { "type": "integer", "minimum": 1 },
+                        "window_seconds": { "type": "integer", "minimum": 1 },
+                        "key": { "type": "string", "enum": ["ip", "principal", "api_key"] }
+                    }
+                }
+            }),
+        }
+    }
+
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        match self {
+            SettingType::Integer { min, max } => match value.as_i64() {
+                Some(n) if (*min..=*max).contains(&n) => Ok(()),
+                Some(_) => Err(format!("must be between {} and {}", min, max)),
+                None => Err("must be an integer".to_string()),
+            },
+            SettingType::Boolean if value.is_boolean() => Ok(()),
+            SettingType::Boolean => Err("must be a boolean".to_string()),
+            SettingType::StringList => match value.as_array() {
+                Some(items) if items.iter().all(Value::is_string) => Ok(()),
+                _ => Err("must be an array of strings".to_string()),
+            },
+            SettingType::OriginList => {
+                SettingType::StringList.validate(value)?;
+                for origin in value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    cors::validate_origin(origin)
+                        .map_err(|e| format!("contains `{}` which {}", origin, e))?;
+                }
+                Ok(())
+            }
+            SettingType::RateLimitRoutes => {
+                let routes = Vec::<RouteRateLimit>::deserialize(value)
+                    .map_err(|e| format!("must be a list of rate limit rules: {}", e))?;
+                routes
+                    .iter()
+                    .try_for_each(RouteRateLimit::validate)
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub description: &'static str,
+    pub setting_type: SettingType,
+    default: fn(&Env) -> Value,
+}
+
+pub static DEFINITIONS: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: JWT_EXPIRES_IN_SECONDS.key,
+        description: "Lifetime of newly issued access tokens in seconds",
+        setting_type: SettingType::Integer {
+            min: 60,
+            max: 7 * 24 * 3600,
+        },
+        default: |env| json!(env.auth.jwt_expires_in_seconds),
+    },
+    SettingDefinition {
+        key: LOGIN_LOCKOUT_THRESHOLD.key,
+        description:
+            "Failed logins after which an account is temporarily locked (0 disables lockout)",
+        setting_type: SettingType::Integer { min: 0, max: 100 },
+        default: |_| json!(5),
+    },
+    SettingDefinition {
+        key: LOGIN_LOCKOUT_SECONDS.key,
+        description: "How long a locked account stays locked in seconds",
+        setting_type: SettingType::Integer {
+            min: 1,
+            max: 24 * 3600,
+        },
+        default: |_| json!(900),
+    },
+    SettingDefinition {
+        key: CORS_ALLOWED_ORIGINS.key,
+        description:
+            "Origins allowed to call the API from a browser (exact, `https://*.example.com` or `*`)",
+        setting_type: SettingType::OriginList,
+        default: |env| json!(env.cors.allowed_origins),
+    },
+    SettingDefinition {
+        key: RATE_LIMIT_ENABLED.key,
+        description: "Whether per-route rate limits are enforced",
+        setting_type: SettingType::Boolean,
+        default: |env| json!(env.rate_limit.enabled),
+    },
+    SettingDefinition {
+        key: RATE_LIMIT_ROUTES.key,
+        description:
+            "Per-route token buckets, the first matching rule applies (key: ip, principal or api_key)",
+        setting_type: SettingType::RateLimitRoutes,
+        default: |env| json!(env.rate_limit.routes),
+    },
+];
+
+fn definition(key: &str) -> Result<&'static SettingDefinition, AppError> {
+    DEFINITIONS
+        .iter()
+        .find(|d| d.key == key)
+        .ok_or_else(|| AppError::NotFound(format!("Setting `{}` does not exist", key)))
+}
+
+struct StoredValue {
+    value: Value,
+    row: SystemSetting,
+}
+
+/// Effective settings: defaults from the configuration overridden by `system_setting` rows.
+pub struct Settings {
+    defaults: HashMap<&'static str, Value>,
+    stored: RwLock<HashMap<&'static str, StoredValue>>,
+}
+
+impl Settings {
+    pub async fn load(repo: &dyn SettingRepository, config: &Env) -> Result<Self, AppError> {
+        let settings = Self {
+            defaults: DEFINITIONS
+                .iter()
+                .map(|d| (d.key, (d.default)(config)))
+                .collect(),
+            stored: RwLock::default(),
+        };
+        settings.reload(repo).await?;
+        Ok(settings)
+    }
+
+    // DB에서 캐시를 다시 읽음 (설정 변경 시 호출)
+    pub async fn reload(&self, repo: &dyn SettingRepository) -> Result<(), AppError> {
+        let mut stored = HashMap::new();
+        for row in repo.list().await? {
+            let Ok(definition) = definition(&row.key) else {
+                tracing::warn!("Ignoring unknown setting `{}`", row.key);
+                continue;
+            };
+            // DB를 직접 수정해 잘못된 값이 들어간 경우 기본값 사용
+            let value = match serde_json::from_str::<Value>(&row.value) {
+                Ok(value) if definition.setting_type.validate(&value).is_ok() => value,
+                _ => {
+                    tracing::warn!(
+                        "Ignoring invalid value for setting `{}`: {}",
+                        row.key,
+                        row.value
+                    );
+                    continue;
+                }
+            };
+            stored.insert(definition.key, StoredValue { value, row });
+        }
+
+        *self.stored.write().unwrap_or_else(|e| e.into_inner()) = stored;
+        Ok(())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<&'static str, StoredValue>> {
+        self.stored.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current value of `setting`.
+    pub fn get<T: DeserializeOwned>(&self, setting: &Setting<T>) -> T {
+        let value = match self.read().get(setting.key) {
+            Some(stored) => stored.value.clone(),
+            None => self.defaults[setting.key].clone(),
+        };
+        serde_json::from_value(value).unwrap_or_else(|e| {
+            panic!(
+                "setting `{}` has a value of the wrong type: {}",
+                setting.key, e
+            )
+        })
+    }
+
+    fn response(&self, definition: &SettingDefinition) -> SettingResponse {
+        let default = self.defaults[definition.key].clone();
+        let stored = self.read();
+        let stored = stored.get(definition.key);
+
+        SettingResponse {
+            key: definition.key.to_string(),
+            description: definition.description.to_string(),
+            schema: definition.setting_type.schema(),
+            value: stored.map_or_else(|| default.clone(), |s| s.value.clone()),
+            default,
+            overridden: stored.is_some(),
+            updated_by: stored.and_then(|s| s.row.updated_by),
+            updated_at: stored.map(|s| s.row.updated_at),
+        }
+    }
+
+    pub fn list(&self) -> Vec<SettingResponse> {
+        DEFINITIONS.iter().map(|d| self.response(d)).collect()
+    }
+
+    pub fn get_setting(&self, key: &str) -> Result<SettingResponse, AppError> {
+        Ok(self.response(definition(key)?))
+    }
+
+    pub async fn update(
+        &self,
+        repo: &dyn SettingRepository,
+        key: &str,
+        value: Value,
+        updated_by: i64,
+    ) -> Result<SettingResponse, AppError> {
+        let definition = definition(key)?;
+        definition
+            .setting_type
+            .validate(&value)
+            .map_err(|e| AppError::BadRequest(format!("Setting `{}` {}", key, e)))?;
+
+        repo.upsert(key, &value.to_string(), Some(updated_by))
+            .await?;
+        self.reload(repo).await?;
+        tracing::info!(
+            "Setting `{}` changed to {} by user {}",
+            key,
+            value,
+            updated_by
+        );
+
+        Ok(self.response(definition))
+    }
+
+    // 저장된 값을 삭제하여 기본값으로 되돌림
+    pub async fn reset(
+        &self,
+        repo: &dyn SettingRepository,
+        key: &str,
+    ) -> Result<SettingResponse, AppError> {
+        let definition = definition(key)?;
+        repo.delete(key).await?;
+        self.reload(repo).await?;
+        tracing::info!("Setting `{}` reset to its default", key);
+
+        Ok(self.response(definition))
+    }
+}
+
+/// Reloads `settings` from the database every `interval_seconds` (0 disables it).
+pub fn spawn_reload_job(
+    supervisor: &TaskSupervisor,
+    settings: Arc<Settings>,
+    repo: Arc<dyn SettingRepository>,
+    interval_seconds: u64,
+) {
+    if interval_seconds == 0 {
+        return;
+    }
+
+    supervisor.spawn("settings reload", move |mut shutdown| async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+        interval.tick().await; // 첫 tick은 즉시 완료되므로 건너뜀
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => break,
+            }
+            if let Err(e) = settings.reload(repo.as_ref()).await {
+                tracing::error!("Failed to reload runtime settings: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,182 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{
+    config::{
+        db::{DbConnection, DbPool},
+        env::Env,
+    },
+    dto::{
+        setup::{SetupRequest, SetupStatusResponse},
+        user::{CreateUserRequest, UserResponse},
+    },
+    errors::AppError,
+    models::AdminUser,
+    services::user_type::SUPER_ADMIN_USER_TYPE,
+    util::{constant_time_eq, hash_password},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use validator::Validate;
+
+/// First-run setup mode.
+///
+/// While no admin user exists and setup has never been completed, `/setup` accepts a single
+/// request carrying the setup token and creates the initial SuperAdmin. Completion is
+/// recorded in `setup_state`, so the endpoint stays disabled even if all users are deleted.
+pub struct SetupState {
+    token: String,
+    required: AtomicBool,
+}
+
+impl SetupState {
+    pub async fn initialize(pool: &DbPool, config: &Env) -> Result<Self, AppError> {
+        let mut conn = pool.acquire().await?;
+        let required = !is_setup_completed(&mut conn).await?;
+
+        let token = match &config.auth.setup_token {
+            Some(token) => token.clone(),
+            None => {
+                let mut bytes = [0u8; 24];
+                rand::rng().fill_bytes(&mut bytes);
+                URL_SAFE_NO_PAD.encode(bytes)
+            }
+        };
+
+        if required {
+            if config.auth.setup_token.is_some() {
+                tracing::warn!("No admin user exists. Setup mode is enabled: POST /api/v1/setup with SETUP_TOKEN");
+            } else {
+                tracing::warn!(
+                    "No admin user exists. Setup mode is enabled: POST /api/v1/setup with setup token `{}`",
+                    token
+                );
+            }
+        }
+
+        Ok(Self {
+            token,
+            required: AtomicBool::new(required),
+        })
+    }
+
+    // 백업 복원 등으로 DB 내용이 바뀐 뒤 설정 필요 여부를 다시 확인
+    pub async fn refresh(&self, pool: &DbPool) -> Result<(), AppError> {
+        let mut conn = pool.acquire().await?;
+        let required = !is_setup_completed(&mut conn).await?;
+        self.required.store(required, Ordering::SeqCst);
+        Ok(())
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required.load(Ordering::SeqCst)
+    }
+
+    fn disable(&self) {
+        self.required.store(false, Ordering::SeqCst);
+    }
+}
+
+pub fn get_setup_status(state: &SetupState) -> SetupStatusResponse {
+    SetupStatusResponse {
+        setup_required: state.is_required(),
+    }
+}
+
+pub async fn complete_setup(
+    pool: &DbPool,
+    state: &SetupState,
+    req: SetupRequest,
+) -> Result<UserResponse, AppError> {
+    if !state.is_required() {
+        return Err(AppError::not_found("Setup has already been completed"));
+    }
+    req.validate()?;
+    if !constant_time_eq(&req.setup_token, &state.token) {
+        tracing::warn!("Rejected setup attempt with an invalid setup token");
+        return Err(AppError::unauthorized("Invalid setup token"));
+    }
+
+    let password_hash = hash_password(&req.password).await?;
+
+    let mut tx = pool.begin().await?;
+    // 다른 요청이나 CLI로 이미 완료되었는지 트랜잭션 내에서 재확인
+    if is_setup_completed(&mut tx).await? {
+        state.disable();
+        return Err(AppError::not_found("Setup has already been completed"));
+    }
+
+    let user = sqlx::query_as::<_, AdminUser>(
+        r#"INSERT INTO admin_user (username, password_hash, user_type_id, is_active)
+        VALUES ($1, $2, (SELECT id FROM user_type WHERE name = $3), TRUE)
+        RETURNING *"#,
+    )
+    .bind(&req.username)
+    .bind(&password_hash)
+    .bind(SUPER_ADMIN_USER_TYPE)
+    .fetch_one(&mut *tx)
+    .await?;
+    // 동시에 들어온 요청이 모두 위 확인을 통과한 경우 (PostgreSQL READ COMMITTED),
+    // setup_state 행을 먼저 기록한 쪽만 성공하고 나머지는 롤백
+    if !mark_setup_completed(&mut tx, user.id).await? {
+        state.disable();
+        return Err(AppError::not_found("Setup has already been completed"));
+    }
+    tx.commit().await?;
+
+    state.disable();
+    tracing::info!(
+        "Initial SuperAdmin `{}` has been created; setup mode is now disabled",
+        user.username
+    );
+
+    Ok(UserResponse::from(user))
+}
+
+// 관리 CLI용: 최고 관리자 생성과 설정 완료 기록을 한 트랜잭션으로 처리
+// (설정이 이미 완료된 뒤에도 최고 관리자를 추가할 수 있음)
+pub async fn create_super_admin(
+    pool: &DbPool,
+    req: CreateUserRequest,
+) -> Result<UserResponse, AppError> {
+    req.validate()?;
+    let password_hash = hash_password(&req.password).await?;
+
+    let mut tx = pool.begin().await?;
+    let user = sqlx::query_as::<_, AdminUser>(
+        r#"INSERT INTO admin_user (username, password_hash, user_type_id, is_active)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *"#,
+    )
+    .bind(&req.username)
+    .bind(&password_hash)
+    .bind(req.user_type_id)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(&mut *tx)
+    .await?;
+    // 설정이 이미 완료된 경우에는 기존 기록을 유지
+    mark_setup_completed(&mut tx, user.id).await?;
+    tx.commit().await?;
+
+    Ok(UserResponse::from(user))
+}
+
+// 관리 CLI로 최고 관리자를 만든 경우에도 설정 완료로 기록
+// 이미 기록되어 있으면 false (다른 트랜잭션이 먼저 기록 중이면 커밋될 때까지 대기)
+async fn mark_setup_completed(conn: &mut DbConnection, user_id: i64) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO setup_state (id, completed_by) VALUES (1, $1) ON CONFLICT (id) DO NOTHING",
+    )
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+async fn is_setup_completed(conn: &mut DbConnection) -> Result<bool, AppError> {
+    let completed: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM setup_state) OR EXISTS (SELECT 1 FROM admin_user)",
+    )
+    .fetch_one(conn)
+    .await?;
+    Ok(completed)
+}
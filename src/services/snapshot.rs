@@ -0,0 +1,718 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    config::db::{DbConnection, DbPool},
+    dto::snapshot::{
+        ConfigSnapshot, MenuSnapshot, PermissionSnapshot, SnapshotAction, SnapshotApplyParams,
+        SnapshotChange, SnapshotEntity, SnapshotPlanResponse, UserTypeSnapshot, SNAPSHOT_VERSION,
+    },
+    errors::AppError,
+};
+use serde_json::json;
+use validator::Validate;
+
+pub async fn export_snapshot(pool: &DbPool) -> Result<ConfigSnapshot, AppError> {
+    let mut conn = pool.acquire().await?;
+    load_snapshot(&mut conn).await
+}
+
+// 변경 사항을 계산하고 트랜잭션 내에서 실행해 본 뒤 롤백 (미리보기)
+pub async fn plan_snapshot(
+    pool: &DbPool,
+    params: &SnapshotApplyParams,
+    req: &ConfigSnapshot,
+) -> Result<SnapshotPlanResponse, AppError> {
+    run_snapshot(pool, req, params.prune, false).await
+}
+
+pub async fn apply_snapshot(
+    pool: &DbPool,
+    username: &str,
+    params: &SnapshotApplyParams,
+    req: &ConfigSnapshot,
+) -> Result<SnapshotPlanResponse, AppError> {
+    let response = run_snapshot(pool, req, params.prune, true).await?;
+    tracing::info!(
+        "Configuration snapshot applied by user {} ({} changes)",
+        username,
+        response.changes.len()
+    );
+    Ok(response)
+}
+
+async fn run_snapshot(
+    pool: &DbPool,
+    desired: &ConfigSnapshot,
+    prune: bool,
+    commit: bool,
+) -> Result<SnapshotPlanResponse, AppError> {
+    desired.validate()?;
+    validate_snapshot(desired)?;
+
+    let mut tx = pool.begin().await?;
+    let current = load_snapshot(&mut tx).await?;
+    let plan = plan_changes(&current, desired, prune);
+
+    for (operation, _) in &plan {
+        execute_operation(&mut tx, operation).await?;
+    }
+
+    if commit {
+        tx.commit().await?;
+    } else {
+        tx.rollback().await?;
+    }
+
+    Ok(SnapshotPlanResponse {
+        applied: commit,
+        changes: plan.into_iter().map(|(_, change)| change).collect(),
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct PermissionGrant {
+    user_type: String,
+    code: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct MenuGrant {
+    user_type: String,
+    path: String,
+}
+
+async fn load_snapshot(conn: &mut DbConnection) -> Result<ConfigSnapshot, AppError> {
+    let permissions = sqlx::query_as::<_, PermissionSnapshot>(
+        "SELECT code, description FROM permission ORDER BY code",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let user_type_rows = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT name, description FROM user_type ORDER BY name",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let permission_grants = sqlx::query_as::<_, PermissionGrant>(
+        r#"SELECT ut.name as user_type, p.code
+        FROM user_type_permission utp
+        JOIN user_type ut ON ut.id = utp.user_type_id
+        JOIN permission p ON p.id = utp.permission_id
+        ORDER BY p.code"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let menu_grants = sqlx::query_as::<_, MenuGrant>(
+        r#"SELECT ut.name as user_type, m.path
+        FROM user_type_menu utm
+        JOIN user_type ut ON ut.id = utm.user_type_id
+        JOIN menu_item m ON m.id = utm.menu_item_id
+        ORDER BY m.path"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let menus = sqlx::query_as::<_, MenuSnapshot>(
+        r#"SELECT
+            m.path,
+            m.name,
+            m.icon,
+            parent.path as parent_path,
+            m.display_order,
+            m.is_visible
+        FROM menu_item m
+        LEFT JOIN menu_item parent ON parent.id = m.parent_id
+        ORDER BY m.display_order, m.path"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut user_types: Vec<UserTypeSnapshot> = user_type_rows
+        .into_iter()
+        .map(|(name, description)| UserTypeSnapshot {
+            name,
+            description,
+            permissions: Vec::new(),
+            menus: Vec::new(),
+        })
+        .collect();
+    for user_type in &mut user_types {
+        user_type.permissions = permission_grants
+            .iter()
+            .filter(|grant| grant.user_type == user_type.name)
+            .map(|grant| grant.code.clone())
+            .collect();
+        user_type.menus = menu_grants
+            .iter()
+            .filter(|grant| grant.user_type == user_type.name)
+            .map(|grant| grant.path.clone())
+            .collect();
+    }
+
+    Ok(ConfigSnapshot {
+        version: SNAPSHOT_VERSION,
+        permissions,
+        user_types,
+        menus,
+    })
+}
+
+// 스냅샷 내부 참조 무결성 검사 (중복 키, 존재하지 않는 참조, 메뉴 순환 참조)
+fn validate_snapshot(snapshot: &ConfigSnapshot) -> Result<(), AppError> {
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "Unsupported snapshot version {} (expected {})",
+            snapshot.version, SNAPSHOT_VERSION
+        )));
+    }
+
+    let mut codes = HashSet::new();
+    for permission in &snapshot.permissions {
+        if !codes.insert(permission.code.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Duplicate permission code `{}`",
+                permission.code
+            )));
+        }
+    }
+
+    let mut parents = HashMap::new();
+    for menu in &snapshot.menus {
+        if parents
+            .insert(menu.path.as_str(), menu.parent_path.as_deref())
+            .is_some()
+        {
+            return Err(AppError::BadRequest(format!(
+                "Duplicate menu path `{}`",
+                menu.path
+            )));
+        }
+    }
+    for menu in &snapshot.menus {
+        let mut visited = HashSet::from([menu.path.as_str()]);
+        let mut parent = menu.parent_path.as_deref();
+        while let Some(parent_path) = parent {
+            if !parents.contains_key(parent_path) {
+                return Err(AppError::BadRequest(format!(
+                    "Menu `{}` references unknown parent `{}`",
+                    menu.path, parent_path
+                )));
+            }
+            if !visited.insert(parent_path) {
+                return Err(AppError::BadRequest(format!(
+                    "Menu `{}` has a cyclic parent reference",
+                    menu.path
+                )));
+            }
+            parent = parents[parent_path];
+        }
+    }
+
+    let mut names = HashSet::new();
+    for user_type in &snapshot.user_types {
+        if !names.insert(user_type.name.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Duplicate user type name `{}`",
+                user_type.name
+            )));
+        }
+        if let Some(code) = user_type
+            .permissions
+            .iter()
+            .find(|code| !codes.contains(code.as_str()))
+        {
+            return Err(AppError::BadRequest(format!(
+                "User type `{}` references unknown permission `{}`",
+                user_type.name, code
+            )));
+        }
+        if let Some(path) = user_type
+            .menus
+            .iter()
+            .find(|path| !parents.contains_key(path.as_str()))
+        {
+            return Err(AppError::BadRequest(format!(
+                "User type `{}` references unknown menu `{}`",
+                user_type.name, path
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+enum Operation {
+    CreatePermission(PermissionSnapshot),
+    UpdatePermission(PermissionSnapshot),
+    DeletePermission(String),
+    CreateUserType(UserTypeSnapshot),
+    UpdateUserType(UserTypeSnapshot),
+    DeleteUserType(String),
+    CreateMenu(MenuSnapshot),
+    UpdateMenu(MenuSnapshot),
+    DeleteMenu(String),
+    GrantPermission { user_type: String, code: String },
+    RevokePermission { user_type: String, code: String },
+    GrantMenu { user_type: String, path: String },
+    RevokeMenu { user_type: String, path: String },
+}
+
+/// Computes the ordered list of operations that turns `current` into `desired`.
+///
+/// Entities are created/updated first (parent menus before children), then grants are
+/// synchronised for every user type listed in the snapshot, and finally entities missing
+/// from the snapshot are deleted when `prune` is set. Applying the same snapshot twice
+/// yields an empty plan.
+fn plan_changes(
+    current: &ConfigSnapshot,
+    desired: &ConfigSnapshot,
+    prune: bool,
+) -> Vec<(Operation, SnapshotChange)> {
+    let mut plan = Vec::new();
+
+    // 1. 권한
+    let current_permissions: HashMap<&str, &PermissionSnapshot> = current
+        .permissions
+        .iter()
+        .map(|p| (p.code.as_str(), p))
+        .collect();
+    for permission in &desired.permissions {
+        match current_permissions.get(permission.code.as_str()) {
+            None => plan.push((
+                Operation::CreatePermission(permission.clone()),
+                change(
+                    SnapshotEntity::Permission,
+                    SnapshotAction::Create,
+                    &permission.code,
+                    None,
+                    Some(json!(permission)),
+                ),
+            )),
+            Some(existing) if *existing != permission => plan.push((
+                Operation::UpdatePermission(permission.clone()),
+                change(
+                    SnapshotEntity::Permission,
+                    SnapshotAction::Update,
+                    &permission.code,
+                    Some(json!(existing)),
+                    Some(json!(permission)),
+                ),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    // 2. 사용자 종류 (매핑은 아래에서 별도 처리)
+    let current_user_types: HashMap<&str, &UserTypeSnapshot> = current
+        .user_types
+        .iter()
+        .map(|ut| (ut.name.as_str(), ut))
+        .collect();
+    for user_type in &desired.user_types {
+        let after = json!({ "name": user_type.name, "description": user_type.description });
+        match current_user_types.get(user_type.name.as_str()) {
+            None => plan.push((
+                Operation::CreateUserType(user_type.clone()),
+                change(
+                    SnapshotEntity::UserType,
+                    SnapshotAction::Create,
+                    &user_type.name,
+                    None,
+                    Some(after),
+                ),
+            )),
+            Some(existing) if existing.description != user_type.description => plan.push((
+                Operation::UpdateUserType(user_type.clone()),
+                change(
+                    SnapshotEntity::UserType,
+                    SnapshotAction::Update,
+                    &user_type.name,
+                    Some(json!({ "name": existing.name, "description": existing.description })),
+                    Some(after),
+                ),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    // 3. 메뉴 (부모가 먼저 생성되도록 깊이 순으로 정렬)
+    let current_menus: HashMap<&str, &MenuSnapshot> =
+        current.menus.iter().map(|m| (m.path.as_str(), m)).collect();
+    let mut desired_menus: Vec<&MenuSnapshot> = desired.menus.iter().collect();
+    desired_menus.sort_by_key(|menu| menu_depth(menu, &desired.menus));
+    let mut menu_updates = Vec::new();
+    for menu in desired_menus {
+        match current_menus.get(menu.path.as_str()) {
+            None => plan.push((
+                Operation::CreateMenu(menu.clone()),
+                change(
+                    SnapshotEntity::MenuItem,
+                    SnapshotAction::Create,
+                    &menu.path,
+                    None,
+                    Some(json!(menu)),
+                ),
+            )),
+            Some(existing) if *existing != menu => menu_updates.push((
+                Operation::UpdateMenu(menu.clone()),
+                change(
+                    SnapshotEntity::MenuItem,
+                    SnapshotAction::Update,
+                    &menu.path,
+                    Some(json!(existing)),
+                    Some(json!(menu)),
+                ),
+            )),
+            Some(_) => {}
+        }
+    }
+    plan.extend(menu_updates);
+
+    // 4. 스냅샷에 포함된 사용자 종류의 권한/메뉴 매핑 동기화
+    for user_type in &desired.user_types {
+        let (current_codes, current_paths) = match current_user_types.get(user_type.name.as_str()) {
+            Some(existing) => (
+                existing.permissions.iter().cloned().collect(),
+                existing.menus.iter().cloned().collect(),
+            ),
+            None => (BTreeSet::new(), BTreeSet::new()),
+        };
+        let desired_codes: BTreeSet<String> = user_type.permissions.iter().cloned().collect();
+        let desired_paths: BTreeSet<String> = user_type.menus.iter().cloned().collect();
+
+        for code in current_codes.difference(&desired_codes) {
+            plan.push((
+                Operation::RevokePermission {
+                    user_type: user_type.name.clone(),
+                    code: code.clone(),
+                },
+                grant_change(
+                    SnapshotEntity::UserTypePermission,
+                    SnapshotAction::Delete,
+                    &user_type.name,
+                    code,
+                ),
+            ));
+        }
+        for code in desired_codes.difference(&current_codes) {
+            plan.push((
+                Operation::GrantPermission {
+                    user_type: user_type.name.clone(),
+                    code: code.clone(),
+                },
+                grant_change(
+                    SnapshotEntity::UserTypePermission,
+                    SnapshotAction::Create,
+                    &user_type.name,
+                    code,
+                ),
+            ));
+        }
+        for path in current_paths.difference(&desired_paths) {
+            plan.push((
+                Operation::RevokeMenu {
+                    user_type: user_type.name.clone(),
+                    path: path.clone(),
+                },
+                grant_change(
+                    SnapshotEntity::UserTypeMenu,
+                    SnapshotAction::Delete,
+                    &user_type.name,
+                    path,
+                ),
+            ));
+        }
+        for path in desired_paths.difference(&current_paths) {
+            plan.push((
+                Operation::GrantMenu {
+                    user_type: user_type.name.clone(),
+                    path: path.clone(),
+                },
+                grant_change(
+                    SnapshotEntity::UserTypeMenu,
+                    SnapshotAction::Create,
+                    &user_type.name,
+                    path,
+                ),
+            ));
+        }
+    }
+
+    // 5. prune: 스냅샷에 없는 항목 삭제 (관련 매핑도 함께 삭제)
+    if prune {
+        let desired_names: HashSet<&str> = desired
+            .user_types
+            .iter()
+            .map(|ut| ut.name.as_str())
+            .collect();
+        let desired_codes: HashSet<&str> = desired
+            .permissions
+            .iter()
+            .map(|p| p.code.as_str())
+            .collect();
+        let desired_paths: HashSet<&str> = desired.menus.iter().map(|m| m.path.as_str()).collect();
+
+        for user_type in &current.user_types {
+            if !desired_names.contains(user_type.name.as_str()) {
+                plan.push((
+                    Operation::DeleteUserType(user_type.name.clone()),
+                    change(
+                        SnapshotEntity::UserType,
+                        SnapshotAction::Delete,
+                        &user_type.name,
+                        Some(
+                            json!({ "name": user_type.name, "description": user_type.description }),
+                        ),
+                        None,
+                    ),
+                ));
+            }
+        }
+        for permission in &current.permissions {
+            if !desired_codes.contains(permission.code.as_str()) {
+                plan.push((
+                    Operation::DeletePermission(permission.code.clone()),
+                    change(
+                        SnapshotEntity::Permission,
+                        SnapshotAction::Delete,
+                        &permission.code,
+                        Some(json!(permission)),
+                        None,
+                    ),
+                ));
+            }
+        }
+        for menu in &current.menus {
+            if !desired_paths.contains(menu.path.as_str()) {
+                plan.push((
+                    Operation::DeleteMenu(menu.path.clone()),
+                    change(
+                        SnapshotEntity::MenuItem,
+                        SnapshotAction::Delete,
+                        &menu.path,
+                        Some(json!(menu)),
+                        None,
+                    ),
+                ));
+            }
+        }
+    }
+
+    plan
+}
+
+fn menu_depth(menu: &MenuSnapshot, menus: &[MenuSnapshot]) -> usize {
+    let mut depth = 0;
+    let mut parent = menu.parent_path.as_deref();
+    // validate_snapshot에서 순환 참조를 이미 검사함
+    while let Some(parent_path) = parent {
+        depth += 1;
+        parent = menus
+            .iter()
+            .find(|m| m.path == parent_path)
+            .and_then(|m| m.parent_path.as_deref());
+    }
+    depth
+}
+
+fn change(
+    entity: SnapshotEntity,
+    action: SnapshotAction,
+    key: &str,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+) -> SnapshotChange {
+    SnapshotChange {
+        entity,
+        action,
+        key: key.to_string(),
+        before,
+        after,
+    }
+}
+
+fn grant_change(
+    entity: SnapshotEntity,
+    action: SnapshotAction,
+    user_type: &str,
+    target: &str,
+) -> SnapshotChange {
+    change(
+        entity,
+        action,
+        &format!("{} -> {}", user_type, target),
+        None,
+        None,
+    )
+}
+
+async fn execute_operation(conn: &mut DbConnection, operation: &Operation) -> Result<(), AppError> {
+    match operation {
+        Operation::CreatePermission(p) => {
+            sqlx::query("INSERT INTO permission (code, description) VALUES ($1, $2)")
+                .bind(&p.code)
+                .bind(&p.description)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::UpdatePermission(p) => {
+            sqlx::query("UPDATE permission SET description = $1 WHERE code = $2")
+                .bind(&p.description)
+                .bind(&p.code)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::DeletePermission(code) => {
+            sqlx::query(
+                "DELETE FROM user_type_permission WHERE permission_id = (SELECT id FROM permission WHERE code = $1)",
+            )
+            .bind(code)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query("DELETE FROM permission WHERE code = $1")
+                .bind(code)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::CreateUserType(ut) => {
+            sqlx::query("INSERT INTO user_type (name, description) VALUES ($1, $2)")
+                .bind(&ut.name)
+                .bind(&ut.description)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::UpdateUserType(ut) => {
+            sqlx::query("UPDATE user_type SET description = $1 WHERE name = $2")
+                .bind(&ut.description)
+                .bind(&ut.name)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::DeleteUserType(name) => {
+            let user_count: i64 = sqlx::query_scalar(
+                "SELECT COUNT(1) FROM admin_user u JOIN user_type ut ON ut.id = u.user_type_id WHERE ut.name = $1",
+            )
+            .bind(name)
+            .fetch_one(&mut *conn)
+            .await?;
+            if user_count > 0 {
+                return Err(AppError::Conflict(format!(
+                    "Cannot delete user type `{}`: it is currently assigned to users.",
+                    name
+                )));
+            }
+            sqlx::query(
+                "DELETE FROM user_type_permission WHERE user_type_id = (SELECT id FROM user_type WHERE name = $1)",
+            )
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query(
+                "DELETE FROM user_type_menu WHERE user_type_id = (SELECT id FROM user_type WHERE name = $1)",
+            )
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query("DELETE FROM user_type WHERE name = $1")
+                .bind(name)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::CreateMenu(m) => {
+            sqlx::query(
+                r#"INSERT INTO menu_item (name, path, icon, parent_id, display_order, is_visible)
+                VALUES ($1, $2, $3, (SELECT id FROM menu_item WHERE path = $4), $5, $6)"#,
+            )
+            .bind(&m.name)
+            .bind(&m.path)
+            .bind(&m.icon)
+            .bind(&m.parent_path)
+            .bind(m.display_order)
+            .bind(m.is_visible)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Operation::UpdateMenu(m) => {
+            sqlx::query(
+                r#"UPDATE menu_item
+                SET name = $1, icon = $2, parent_id = (SELECT id FROM menu_item WHERE path = $3),
+                    display_order = $4, is_visible = $5
+                WHERE path = $6"#,
+            )
+            .bind(&m.name)
+            .bind(&m.icon)
+            .bind(&m.parent_path)
+            .bind(m.display_order)
+            .bind(m.is_visible)
+            .bind(&m.path)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Operation::DeleteMenu(path) => {
+            sqlx::query(
+                "DELETE FROM user_type_menu WHERE menu_item_id = (SELECT id FROM menu_item WHERE path = $1)",
+            )
+            .bind(path)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query(
+                "UPDATE menu_item SET parent_id = NULL WHERE parent_id = (SELECT id FROM menu_item WHERE path = $1)",
+            )
+            .bind(path)
+            .execute(&mut *conn)
+            .await?;
+            sqlx::query("DELETE FROM menu_item WHERE path = $1")
+                .bind(path)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Operation::GrantPermission { user_type, code } => {
+            sqlx::query(
+                r#"INSERT INTO user_type_permission (user_type_id, permission_id)
+                SELECT ut.id, p.id FROM user_type ut, permission p
+                WHERE ut.name = $1 AND p.code = $2"#,
+            )
+            .bind(user_type)
+            .bind(code)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Operation::RevokePermission { user_type, code } => {
+            sqlx::query(
+                r#"DELETE FROM user_type_permission
+                WHERE user_type_id = (SELECT id FROM user_type WHERE name = $1)
+                  AND permission_id = (SELECT id FROM permission WHERE code = $2)"#,
+            )
+            .bind(user_type)
+            .bind(code)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Operation::GrantMenu { user_type, path } => {
+            sqlx::query(
+                r#"INSERT INTO user_type_menu (user_type_id, menu_item_id)
+                SELECT ut.id, m.id FROM user_type ut, menu_item m
+                WHERE ut.name = $1 AND m.path = $2"#,
+            )
+            .bind(user_type)
+            .bind(path)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Operation::RevokeMenu { user_type, path } => {
+            sqlx::query(
+                r#"DELETE FROM user_type_menu
+                WHERE user_type_id = (SELECT id FROM user_type WHERE name = $1)
+                  AND menu_item_id = (SELECT id FROM menu_item WHERE path = $2)"#,
+            )
+            .bind(user_type)
+            .bind(path)
+            .execute(&mut *conn)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
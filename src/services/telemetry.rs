@@ -0,0 +1,129 @@
+//! OpenTelemetry trace export (`otel` feature).
+//!
+//! Tracing spans — one per HTTP request (opened by `RequestId`, which also honors an incoming
+//! W3C `traceparent`) and one per bcrypt call in `util` — are exported over OTLP/HTTP through
+//! `tracing-opentelemetry`. sqlx only emits a log event per statement, so [`QuerySpanLayer`]
+//! turns each `sqlx::query` event into a client span under the current request span.
+
+use crate::{config::env::TelemetryConfig, services::metrics::QueryVisitor};
+use actix_web::http::header::HeaderMap;
+use anyhow::Context as _;
+use opentelemetry::{
+    global,
+    propagation::Extractor,
+    trace::{Span as _, SpanKind, Tracer as _, TracerProvider as _},
+    Context, KeyValue,
+};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracer, SdkTracerProvider},
+    Resource,
+};
+use std::time::{Duration, SystemTime};
+use tracing::{level_filters::LevelFilter, Event, Subscriber};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{
+    filter::Targets, layer::Context as LayerContext, registry::LookupSpan, EnvFilter, Layer,
+};
+
+#[cfg(feature = "sqlite")]
+const DB_SYSTEM: &str = "sqlite";
+#[cfg(feature = "postgres")]
+const DB_SYSTEM: &str = "postgresql";
+
+/// Creates the OTLP exporter pipeline, or `None` when no collector endpoint is configured.
+/// The returned provider must be shut down on exit to flush buffered spans.
+pub fn init_tracer_provider(config: &TelemetryConfig) -> anyhow::Result<Option<SdkTracerProvider>> {
+    let Some(endpoint) = &config.otlp_endpoint else {
+        return Ok(None);
+    };
+
+    // OTEL_EXPORTER_OTLP_ENDPOINT와 같이 기본 주소에 신호별 경로를 붙임
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .with_timeout(Duration::from_millis(config.export_timeout_ms))
+        .build()
+        .context("Failed to create the OTLP span exporter")?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        // 상위 서비스가 샘플링 여부를 정했으면 그대로 따름
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build();
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    Ok(Some(provider))
+}
+
+/// Layers exporting spans to `provider`. `filter` limits exported spans the same way
+/// `logging.level` limits log output, so spans of dependencies (and of the exporter's own HTTP
+/// client) are not exported.
+pub fn layer<S>(provider: &SdkTracerProvider, filter: EnvFilter) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let tracer = provider.tracer("admin_server");
+    tracing_opentelemetry::layer()
+        .with_tracer(tracer.clone())
+        .with_filter(filter)
+        .and_then(
+            QuerySpanLayer { tracer }
+                .with_filter(Targets::new().with_target("sqlx::query", LevelFilter::DEBUG)),
+        )
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// Remote parent context from the `traceparent`/`tracestate` request headers.
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Records every sqlx statement as a `SpanKind::Client` span ending when the event is emitted.
+pub struct QuerySpanLayer {
+    tracer: SdkTracer,
+}
+
+impl<S: Subscriber> Layer<S> for QuerySpanLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        let mut visitor = QueryVisitor::default();
+        event.record(&mut visitor);
+        let Some(elapsed) = visitor.elapsed_secs else {
+            return;
+        };
+
+        // 쿼리가 끝난 뒤 이벤트가 기록되므로 경과 시간만큼 시작 시각을 되돌림
+        let end = SystemTime::now();
+        let start = end - Duration::from_secs_f64(elapsed);
+        let parent = tracing::Span::current().context();
+        let mut span = self
+            .tracer
+            .span_builder(visitor.operation.unwrap_or("other").to_uppercase())
+            .with_kind(SpanKind::Client)
+            .with_start_time(start)
+            .with_attributes([
+                KeyValue::new("db.system", DB_SYSTEM),
+                KeyValue::new("db.statement", visitor.statement.unwrap_or_default()),
+            ])
+            .start_with_context(&self.tracer, &parent);
+        span.end_with_timestamp(end);
+    }
+}
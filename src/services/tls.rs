@@ -0,0 +1,149 @@
+//! TLS termination with rustls.
+//!
+//! The certificate is served through [`CertificateStore`], a rustls certificate resolver whose
+//! key pair can be swapped at runtime. A background job watches the modification times of the
+//! PEM files and reloads them when they change (for example after an ACME renewal), so new
+//! connections use the new certificate without a restart. HTTP/2 is negotiated through ALPN by
+//! actix-web.
+
+use crate::{config::env::TlsConfig, services::lifecycle::TaskSupervisor};
+use anyhow::{anyhow, Context, Result};
+use rustls::{
+    crypto::{ring, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer},
+    server::{ClientHello, ResolvesServerCert},
+    sign::CertifiedKey,
+    ServerConfig,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+#[derive(Debug)]
+pub struct CertificateStore {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<Arc<CertifiedKey>>,
+    // 마지막으로 읽은 (인증서, 키) 파일 수정 시각
+    loaded_at: Mutex<(Option<SystemTime>, Option<SystemTime>)>,
+}
+
+impl CertificateStore {
+    pub fn load(config: &TlsConfig) -> Result<Self> {
+        let (Some(cert_path), Some(key_path)) = (&config.cert_path, &config.key_path) else {
+            return Err(anyhow!("TLS certificate and key paths are not configured"));
+        };
+        let provider = Arc::new(ring::default_provider());
+        let cert_path = PathBuf::from(cert_path);
+        let key_path = PathBuf::from(key_path);
+        let modified = (modified(&cert_path), modified(&key_path));
+        let key = load_certified_key(&cert_path, &key_path, &provider)?;
+
+        Ok(Self {
+            cert_path,
+            key_path,
+            provider,
+            current: RwLock::new(Arc::new(key)),
+            loaded_at: Mutex::new(modified),
+        })
+    }
+
+    /// Reloads the key pair if either file changed since it was last read. Returns whether a
+    /// new certificate is now in use; on error the previous certificate stays active.
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        let modified = (modified(&self.cert_path), modified(&self.key_path));
+        let mut loaded_at = self.loaded_at.lock().unwrap_or_else(|e| e.into_inner());
+        if *loaded_at == modified {
+            return Ok(false);
+        }
+
+        // 실패하면 수정 시각을 갱신하지 않으므로 다음 확인 때 다시 시도 (파일을 쓰는 도중 등)
+        let key = load_certified_key(&self.cert_path, &self.key_path, &self.provider)?;
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(key);
+        *loaded_at = modified;
+        Ok(true)
+    }
+
+    /// Certificate chain and key presented to new connections.
+    pub fn certificate(&self) -> Arc<CertifiedKey> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// rustls server configuration that always presents the current certificate.
+    pub fn server_config(self: &Arc<Self>) -> Result<ServerConfig> {
+        let config = ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(self.clone());
+        Ok(config)
+    }
+}
+
+impl ResolvesServerCert for CertificateStore {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.certificate())
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to read certificates from `{}`", cert_path.display()))?;
+    if certs.is_empty() {
+        return Err(anyhow!("`{}` contains no certificate", cert_path.display()));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("Failed to read private key from `{}`", key_path.display()))?;
+
+    CertifiedKey::from_der(certs, key, provider).with_context(|| {
+        format!(
+            "`{}` does not match the certificate in `{}`",
+            key_path.display(),
+            cert_path.display()
+        )
+    })
+}
+
+pub fn spawn_reload_job(
+    supervisor: &TaskSupervisor,
+    store: Arc<CertificateStore>,
+    interval_seconds: u64,
+) {
+    if interval_seconds == 0 {
+        return;
+    }
+
+    supervisor.spawn("tls reload", move |mut shutdown| async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+        interval.tick().await; // 첫 tick은 즉시 완료되므로 건너뜀
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => break,
+            }
+            match store.reload_if_changed() {
+                Ok(true) => tracing::info!(
+                    "Reloaded TLS certificate from `{}`",
+                    store.cert_path.display()
+                ),
+                Ok(false) => {}
+                Err(e) => tracing::error!("Failed to reload TLS certificate: {:#}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,201 @@
+use crate::{
+    config::db::{DbArguments, DbPool, DbRow, DbTransaction},
+    dto::transfer::{ImportReport, TransferFormat},
+    errors::AppError,
+};
+use actix_multipart::Multipart;
+use actix_web::web::Bytes;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+const MAX_IMPORT_FILE_SIZE: usize = 10 * 1024 * 1024; // 10MB
+const EXPORT_CHANNEL_CAPACITY: usize = 64;
+
+pub struct UploadedFile {
+    pub filename: Option<String>,
+    pub data: Vec<u8>,
+}
+
+// multipart 요청에서 `file` 필드를 읽어옴
+pub async fn read_upload(mut payload: Multipart) -> Result<UploadedFile, AppError> {
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart payload: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(str::to_string);
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Invalid multipart payload: {}", e)))?
+        {
+            if data.len() + chunk.len() > MAX_IMPORT_FILE_SIZE {
+                return Err(AppError::bad_request("Import file is too large"));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        return Ok(UploadedFile { filename, data });
+    }
+
+    Err(AppError::bad_request("Multipart field `file` is required"))
+}
+
+// 명시된 형식이 없으면 파일 확장자로 판단 (기본값 CSV)
+pub fn resolve_format(requested: Option<TransferFormat>, filename: Option<&str>) -> TransferFormat {
+    requested.unwrap_or_else(|| match filename {
+        Some(name) if name.ends_with(".jsonl") || name.ends_with(".ndjson") => {
+            TransferFormat::Jsonl
+        }
+        _ => TransferFormat::Csv,
+    })
+}
+
+// 업로드된 CSV/JSONL 파일을 요청 DTO로 변환하고 행마다 유효성 검사
+// 행 단위 오류를 보고할 수 있도록 (행 번호, 결과) 쌍을 반환 (1부터 시작, CSV 헤더 제외)
+pub fn parse_rows<T>(format: TransferFormat, data: &[u8]) -> Vec<(usize, Result<T, AppError>)>
+where
+    T: DeserializeOwned + Validate,
+{
+    let parsed: Vec<(usize, Result<T, AppError>)> = match format {
+        TransferFormat::Csv => csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(data)
+            .into_deserialize::<T>()
+            .enumerate()
+            .map(|(i, row)| {
+                let row = row.map_err(|e| AppError::BadRequest(format!("Invalid CSV row: {}", e)));
+                (i + 1, row)
+            })
+            .collect(),
+        TransferFormat::Jsonl => data
+            .split(|b| *b == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.trim_ascii().is_empty())
+            .map(|(i, line)| {
+                let row = serde_json::from_slice::<T>(line)
+                    .map_err(|e| AppError::BadRequest(format!("Invalid JSON line: {}", e)));
+                (i + 1, row)
+            })
+            .collect(),
+    };
+
+    parsed
+        .into_iter()
+        .map(|(row, result)| {
+            let result = result.and_then(|req| {
+                req.validate()?;
+                Ok(req)
+            });
+            (row, result)
+        })
+        .collect()
+}
+
+// dry-run이면 롤백, 아니면 성공한 행만 커밋
+pub async fn finish_import(
+    tx: DbTransaction<'_>,
+    report: ImportReport,
+) -> Result<ImportReport, AppError> {
+    if report.dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    tracing::info!(
+        "Import finished (dry_run: {}): {} imported, {} failed",
+        report.dry_run,
+        report.imported,
+        report.failed
+    );
+    Ok(report)
+}
+
+// `query` 결과를 CSV/JSONL로 인코딩하여 스트리밍
+// 별도 태스크에서 행을 읽어 제한된 크기의 채널로 전달하므로 전체 테이블을 메모리에 올리지 않음
+pub fn export_stream<M, R>(
+    pool: DbPool,
+    query: String,
+    args: DbArguments,
+    format: TransferFormat,
+) -> impl Stream<Item = Result<Bytes, AppError>>
+where
+    M: for<'r> FromRow<'r, DbRow> + Send + Unpin + 'static,
+    R: From<M> + Serialize + 'static,
+{
+    let (sender, receiver) = tokio::sync::mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+
+    actix_web::rt::spawn(async move {
+        let mut encoder = RowEncoder::new(format);
+        let mut rows = sqlx::query_as_with::<_, M, _>(&query, args).fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let chunk = row
+                .map_err(AppError::from)
+                .and_then(|model| encoder.encode(&R::from(model)));
+            let failed = chunk.is_err();
+
+            // 클라이언트 연결이 끊겼거나 오류가 발생하면 중단
+            if sender.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    })
+}
+
+enum RowEncoder {
+    Csv { header_written: bool },
+    Jsonl,
+}
+
+impl RowEncoder {
+    fn new(format: TransferFormat) -> Self {
+        match format {
+            TransferFormat::Csv => RowEncoder::Csv {
+                header_written: false,
+            },
+            TransferFormat::Jsonl => RowEncoder::Jsonl,
+        }
+    }
+
+    fn encode<R: Serialize>(&mut self, row: &R) -> Result<Bytes, AppError> {
+        match self {
+            RowEncoder::Csv { header_written } => {
+                // 헤더는 첫 번째 행에만 출력
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(!*header_written)
+                    .from_writer(Vec::new());
+                writer
+                    .serialize(row)
+                    .map_err(|e| AppError::InternalServerError(e.into()))?;
+                let buf = writer
+                    .into_inner()
+                    .map_err(|e| AppError::InternalServerError(anyhow::anyhow!(e.to_string())))?;
+                *header_written = true;
+                Ok(Bytes::from(buf))
+            }
+            RowEncoder::Jsonl => {
+                let mut line =
+                    serde_json::to_vec(row).map_err(|e| AppError::InternalServerError(e.into()))?;
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            }
+        }
+    }
+}
@@ -1,100 +1,153 @@
 use crate::{
+    config::db::{DbPool, DbTransaction},
     dto::{
         common::ListQueryParams,
-        user::{CreateUserRequest, UserResponse},
+        transfer::{ExportQueryParams, ImportQueryParams, ImportReport},
+        user::{CreateUserRequest, ResetPasswordRequest, UserResponse},
     },
     errors::AppError,
     models::AdminUser,
+    repositories::{user::build_user_filter_query, NewUser, UserRepository},
+    services::transfer::{self, UploadedFile},
     util::hash_password,
 };
-use actix_web::web;
-use sqlx::Arguments;
-use sqlx::SqlitePool;
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use sqlx::Connection;
 use validator::Validate;
 
 pub async fn create_user(
-    pool: web::Data<SqlitePool>,
-    // _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    req: web::Json<CreateUserRequest>,
+    repo: &dyn UserRepository,
+    req: CreateUserRequest,
 ) -> Result<i64, AppError> {
     req.validate()?;
     let password_hash = hash_password(&req.password).await?;
-    let is_active = req.is_active.unwrap_or(true);
 
-    let mut tx = pool.begin().await?;
-    let result = sqlx::query!(
-        "INSERT INTO admin_user (username, password_hash, user_type_id, is_active) VALUES (?, ?, ?, ?) RETURNING id",
-        req.username,
-        password_hash,
-        req.user_type_id,
-        is_active
-    )
-    .fetch_one(&mut *tx)
-    .await?;
-    tx.commit().await?;
+    let user = repo
+        .create(NewUser {
+            username: req.username,
+            password_hash,
+            user_type_id: req.user_type_id,
+            is_active: req.is_active.unwrap_or(true),
+        })
+        .await?;
 
-    result
-        .id
-        .ok_or_else(|| AppError::Conflict(String::from("Failed to create user")))
+    Ok(user.id)
 }
 
 pub async fn get_user_array(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    query_params: web::Query<ListQueryParams>,
+    repo: &dyn UserRepository,
+    query_params: &ListQueryParams,
 ) -> Result<Vec<UserResponse>, AppError> {
-    let limit = query_params.get_limit();
-    let offset = query_params.get_offset();
-    let allowed_sort_columns = [
-        "id",
-        "username",
-        "user_type_id",
-        "is_active",
-        "last_login_at",
-        "created_at",
-        "updated_at",
-    ];
-    let order_by = query_params.get_order_by(&allowed_sort_columns);
-
-    let base_query = "SELECT * FROM admin_user";
-    let mut conditions = Vec::new();
-    let mut args = sqlx::sqlite::SqliteArguments::default();
-
-    if let Some(search_term) = &query_params.q {
-        conditions.push("username LIKE ?");
-        args.add(format!("%{}%", search_term));
+    let users = repo.list(query_params).await?;
+
+    Ok(users.into_iter().map(UserResponse::from).collect())
+}
+
+pub async fn export_user_array(
+    pool: &DbPool,
+    query_params: &ListQueryParams,
+    export_params: &ExportQueryParams,
+) -> Result<impl Stream<Item = Result<Bytes, AppError>>, AppError> {
+    // 목록 API와 동일한 검색/정렬 조건을 사용하되 페이지네이션은 적용하지 않음
+    let (query_str, args) = build_user_filter_query(query_params)?;
+
+    Ok(transfer::export_stream::<AdminUser, UserResponse>(
+        pool.clone(),
+        query_str,
+        args,
+        export_params.format,
+    ))
+}
+
+pub async fn import_user_array(
+    pool: &DbPool,
+    import_params: &ImportQueryParams,
+    file: &UploadedFile,
+) -> Result<ImportReport, AppError> {
+    let format = transfer::resolve_format(import_params.format, file.filename.as_deref());
+    let rows = transfer::parse_rows::<CreateUserRequest>(format, &file.data);
+
+    // bcrypt 해시는 느리므로 쓰기 트랜잭션(SQLite 단일 쓰기 연결)을 열기 전에 계산
+    let mut hashed = Vec::with_capacity(rows.len());
+    for (row, parsed) in rows {
+        let parsed = match parsed {
+            Ok(req) => hash_password(&req.password)
+                .await
+                .map(|password_hash| (req, password_hash)),
+            Err(e) => Err(e),
+        };
+        hashed.push((row, parsed));
     }
 
-    let where_clause = if conditions.is_empty() {
-        "".to_string()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
-    };
-
-    let query_str = format!(
-        "{} {} ORDER BY {} LIMIT ? OFFSET ?",
-        base_query, where_clause, order_by
-    );
-    args.add(limit);
-    args.add(offset);
-
-    let users = sqlx::query_as_with::<_, AdminUser, _>(&query_str, args)
-        .fetch_all(pool.get_ref())
-        .await?;
+    let mut report = ImportReport::new(import_params.dry_run);
+    let mut tx = pool.begin().await?;
+    for (row, parsed) in hashed {
+        let result = match parsed {
+            Ok((req, password_hash)) => insert_user_row(&mut tx, &req, &password_hash).await,
+            Err(e) => Err(e),
+        };
+        report.record(row, result);
+    }
 
-    Ok(users.into_iter().map(UserResponse::from).collect())
+    transfer::finish_import(tx, report).await
 }
 
-pub async fn get_user_by_id(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    path: web::Path<i64>,
-) -> Result<UserResponse, AppError> {
-    let id = path.into_inner();
-    let user = sqlx::query_as!(AdminUser, "SELECT * FROM admin_user WHERE id = ?", id)
-        .fetch_one(pool.get_ref())
-        .await
-        .map_err(|_| AppError::not_found("User not found"))?;
+// 행마다 SAVEPOINT를 사용하여 실패한 행만 롤백
+async fn insert_user_row(
+    tx: &mut DbTransaction<'_>,
+    req: &CreateUserRequest,
+    password_hash: &str,
+) -> Result<(), AppError> {
+    let is_active = req.is_active.unwrap_or(true);
+
+    let mut savepoint = tx.begin().await?;
+    sqlx::query(
+        "INSERT INTO admin_user (username, password_hash, user_type_id, is_active) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(&req.username)
+    .bind(password_hash)
+    .bind(req.user_type_id)
+    .bind(is_active)
+    .execute(&mut *savepoint)
+    .await?;
+    savepoint.commit().await?;
+
+    Ok(())
+}
+
+pub async fn get_user_by_id(repo: &dyn UserRepository, id: i64) -> Result<UserResponse, AppError> {
+    let user = repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User not found"))?;
 
     Ok(UserResponse::from(user))
 }
+
+// 관리 CLI 등에서 사용: 사용자 이름으로 비밀번호 재설정
+pub async fn reset_password(
+    repo: &dyn UserRepository,
+    username: &str,
+    req: ResetPasswordRequest,
+) -> Result<(), AppError> {
+    req.validate()?;
+    let password_hash = hash_password(&req.password).await?;
+
+    if !repo.update_password(username, &password_hash).await? {
+        return Err(AppError::not_found("User not found"));
+    }
+    Ok(())
+}
+
+// 관리 CLI 등에서 사용: 사용자 종류(역할) 변경
+pub async fn assign_user_type(
+    repo: &dyn UserRepository,
+    username: &str,
+    user_type_id: i64,
+) -> Result<(), AppError> {
+    if !repo.update_user_type(username, user_type_id).await? {
+        return Err(AppError::not_found("User not found"));
+    }
+    Ok(())
+}
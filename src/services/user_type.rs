@@ -1,69 +1,103 @@
 use crate::{
+    config::db::{DbArguments, DbPool, DbTransaction},
     dto::{
         common::ListQueryParams,
+        transfer::{ExportQueryParams, ImportQueryParams, ImportReport},
         user_type::{CreateUserTypeRequest, UpdateUserTypeRequest, UserTypeResponse},
     },
     errors::AppError,
     models::UserType,
+    repositories::{
+        user_type::USER_TYPE_SORT_COLUMNS, UserRepository, UserTypeChanges, UserTypeRepository,
+    },
+    services::transfer::{self, UploadedFile},
 };
-use actix_web::web;
-use sqlx::Arguments;
-use sqlx::SqlitePool;
+use actix_web::web::Bytes;
+use futures_util::Stream;
+use sqlx::Connection;
 use validator::Validate;
 
+// 초기 스키마에서 생성되는 최고 관리자 사용자 종류 이름
+pub const SUPER_ADMIN_USER_TYPE: &str = "SuperAdmin";
+
 pub async fn create_user_type(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    req: web::Json<CreateUserTypeRequest>,
+    repo: &dyn UserTypeRepository,
+    req: CreateUserTypeRequest,
 ) -> Result<UserTypeResponse, AppError> {
     req.validate()?;
 
-    let result = sqlx::query!(
-        "INSERT INTO user_type (name, description) VALUES (?, ?) RETURNING id",
-        req.name,
-        req.description
-    )
-    .fetch_one(pool.get_ref())
-    .await?;
-
-    let created_type = sqlx::query_as!(UserType, "SELECT * FROM user_type WHERE id = ?", result.id)
-        .fetch_one(pool.get_ref())
-        .await?;
+    let created_type = repo.create(&req.name, req.description.as_deref()).await?;
 
     Ok(UserTypeResponse::from(created_type))
 }
 
 pub async fn get_user_type_array(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    query: web::Query<ListQueryParams>,
+    repo: &dyn UserTypeRepository,
+    query: &ListQueryParams,
 ) -> Result<Vec<UserTypeResponse>, AppError> {
-    let limit = query.get_limit();
-    let offset = query.get_offset();
-    let allowed_sort_columns = ["id", "name", "created_at", "updated_at"];
-    let order_by = query.get_order_by(&allowed_sort_columns);
+    let user_types = repo.list(query).await?;
 
-    let query_str = format!(
-        "SELECT * FROM user_type ORDER BY {} LIMIT {} OFFSET {}",
-        order_by, limit, offset
-    );
+    Ok(user_types.into_iter().map(UserTypeResponse::from).collect())
+}
 
-    let user_types =
-        sqlx::query_as_with::<_, UserType, _>(&query_str, sqlx::sqlite::SqliteArguments::default())
-            .fetch_all(pool.get_ref())
-            .await?;
+pub async fn export_user_type_array(
+    pool: &DbPool,
+    query: &ListQueryParams,
+    export_params: &ExportQueryParams,
+) -> Result<impl Stream<Item = Result<Bytes, AppError>>, AppError> {
+    let order_by = query.get_order_by(USER_TYPE_SORT_COLUMNS);
+    let query_str = format!("SELECT * FROM user_type ORDER BY {}", order_by);
+
+    Ok(transfer::export_stream::<UserType, UserTypeResponse>(
+        pool.clone(),
+        query_str,
+        DbArguments::default(),
+        export_params.format,
+    ))
+}
 
-    Ok(user_types.into_iter().map(UserTypeResponse::from).collect())
+pub async fn import_user_type_array(
+    pool: &DbPool,
+    import_params: &ImportQueryParams,
+    file: &UploadedFile,
+) -> Result<ImportReport, AppError> {
+    let format = transfer::resolve_format(import_params.format, file.filename.as_deref());
+    let rows = transfer::parse_rows::<CreateUserTypeRequest>(format, &file.data);
+
+    let mut report = ImportReport::new(import_params.dry_run);
+    let mut tx = pool.begin().await?;
+    for (row, parsed) in rows {
+        let result = match parsed {
+            Ok(req) => insert_user_type_row(&mut tx, &req).await,
+            Err(e) => Err(e),
+        };
+        report.record(row, result);
+    }
+
+    transfer::finish_import(tx, report).await
+}
+
+async fn insert_user_type_row(
+    tx: &mut DbTransaction<'_>,
+    req: &CreateUserTypeRequest,
+) -> Result<(), AppError> {
+    let mut savepoint = tx.begin().await?;
+    sqlx::query("INSERT INTO user_type (name, description) VALUES ($1, $2)")
+        .bind(&req.name)
+        .bind(&req.description)
+        .execute(&mut *savepoint)
+        .await?;
+    savepoint.commit().await?;
+
+    Ok(())
 }
 
 pub async fn get_user_type_by_id(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    path: web::Path<i64>,
+    repo: &dyn UserTypeRepository,
+    type_id: i64,
 ) -> Result<UserTypeResponse, AppError> {
-    let type_id = path.into_inner();
-    let user_type = sqlx::query_as!(UserType, "SELECT * FROM user_type WHERE id = ?", type_id)
-        .fetch_optional(pool.get_ref())
+    let user_type = repo
+        .find_by_id(type_id)
         .await?
         .ok_or_else(|| AppError::not_found("User type not found"))?;
 
@@ -71,78 +105,59 @@ pub async fn get_user_type_by_id(
 }
 
 pub async fn update_user_type(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    path: web::Path<i64>,
-    req: web::Json<UpdateUserTypeRequest>,
+    repo: &dyn UserTypeRepository,
+    type_id: i64,
+    req: UpdateUserTypeRequest,
 ) -> Result<UserTypeResponse, AppError> {
     req.validate()?;
-    let type_id = path.into_inner();
 
-    let mut set_clauses = Vec::new();
-    let mut args = sqlx::sqlite::SqliteArguments::default();
-    if let Some(name) = &req.name {
-        set_clauses.push("name = ?");
-        args.add(name);
-    }
-    if req.description.is_some() {
-        set_clauses.push("description = ?");
-        args.add(req.description.as_ref());
-    }
-
-    if set_clauses.is_empty() {
+    let changes = UserTypeChanges {
+        name: req.name,
+        description: req.description,
+    };
+    if changes.is_empty() {
         return Err(AppError::bad_request("No fields to update"));
     }
-    set_clauses.push("updated_at = CURRENT_TIMESTAMP");
-
-    args.add(type_id);
-    let query_str = format!(
-        "UPDATE user_type SET {} WHERE id =?",
-        set_clauses.join(", ")
-    );
 
-    let result = sqlx::query_with(&query_str, args)
-        .execute(pool.get_ref())
-        .await?;
-
-    if result.rows_affected() == 0 {
-        return Err(AppError::not_found("User type not found"));
-    }
-
-    let updated_type = sqlx::query_as!(UserType, "SELECT * FROM user_type WHERE id =?", type_id)
-        .fetch_one(pool.get_ref())
-        .await?;
+    let updated_type = repo
+        .update(type_id, changes)
+        .await?
+        .ok_or_else(|| AppError::not_found("User type not found"))?;
 
     Ok(UserTypeResponse::from(updated_type))
 }
 
 pub async fn delete_user_type(
-    pool: web::Data<SqlitePool>,
-    _user: crate::middleware::auth::authenticated_user::AuthenticatedUser,
-    path: web::Path<i64>,
+    repo: &dyn UserTypeRepository,
+    users: &dyn UserRepository,
+    type_id: i64,
 ) -> Result<(), AppError> {
-    let type_id = path.into_inner();
-    let mut tx = pool.begin().await?;
-
-    let user_count: i64 =
-        sqlx::query_scalar("SELECT COUNT(1) FROM admin_user WHERE user_type_id =?")
-            .bind(type_id)
-            .fetch_one(&mut *tx)
-            .await?;
-
-    if user_count > 0 {
+    if users.count_by_user_type(type_id).await? > 0 {
         return Err(AppError::conflict(
             "Cannot delete a user type: it is currently assigned to users.",
         ));
     }
 
-    let result = sqlx::query!("DELETE FROM user_type WHERE id = ?", type_id)
-        .execute(&mut *tx)
-        .await?;
-
-    if result.rows_affected() == 0 {
+    if !repo.delete(type_id).await? {
         return Err(AppError::not_found("User type not found"));
     }
 
     Ok(())
 }
+
+pub async fn get_user_type_by_name(
+    repo: &dyn UserTypeRepository,
+    name: &str,
+) -> Result<UserType, AppError> {
+    repo.find_by_name(name)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User type `{}` not found", name)))
+}
+
+pub async fn list_user_types(
+    repo: &dyn UserTypeRepository,
+) -> Result<Vec<UserTypeResponse>, AppError> {
+    let user_types = repo.list_all().await?;
+
+    Ok(user_types.into_iter().map(UserTypeResponse::from).collect())
+}
@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 // 비밀번호 해싱
 pub async fn hash_password(password: &str) -> Result<String, AppError> {
     let password_bytes = password.as_bytes().to_vec(); // bcrypt는 비동기 아님, 스레드 풀에서 실행
-    tokio::task::spawn_blocking(move || hash(password_bytes, DEFAULT_COST))
+                                                       // 요청 span 아래에 bcrypt 소요 시간이 드러나도록 별도 span으로 실행
+    let span = tracing::info_span!("bcrypt.hash", cost = DEFAULT_COST);
+    tokio::task::spawn_blocking(move || span.in_scope(|| hash(password_bytes, DEFAULT_COST)))
         .await
         .map_err(|e| {
             AppError::InternalServerError(
@@ -22,7 +24,8 @@ pub async fn hash_password(password: &str) -> Result<String, AppError> {
 pub async fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
     let password_bytes = password.as_bytes().to_vec();
     let hash_str = hash.to_string(); // 해시값 복사
-    tokio::task::spawn_blocking(move || verify(password_bytes, &hash_str))
+    let span = tracing::info_span!("bcrypt.verify");
+    tokio::task::spawn_blocking(move || span.in_scope(|| verify(password_bytes, &hash_str)))
         .await
         .map_err(|e| {
             AppError::InternalServerError(
@@ -32,6 +35,15 @@ pub async fn verify_password(password: &str, hash: &str) -> Result<bool, AppErro
         .map_err(AppError::PasswordHashingError)
 }
 
+// 타이밍 공격 방지를 위한 고정 시간 문자열 비교
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 // --- JWT 관련 ---
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,10 +60,11 @@ pub fn create_jwt(
     user_id: i64,
     user_type_id: i64,
     username: &str,
+    expires_in_seconds: i64,
     config: &env::Env,
 ) -> Result<String, AppError> {
     let expiration = Utc::now()
-        .checked_add_signed(Duration::seconds(config.jwt_expires_in_seconds))
+        .checked_add_signed(Duration::seconds(expires_in_seconds))
         .expect("valid timestamp")
         .timestamp();
 
@@ -66,7 +79,7 @@ pub fn create_jwt(
     encode(
         &header,
         &claims,
-        &EncodingKey::from_secret(config.jwt_secret.as_ref()),
+        &EncodingKey::from_secret(config.auth.jwt_secret.as_ref()),
     )
     .map_err(AppError::JwtError)
 }
@@ -78,7 +91,7 @@ pub fn validate_jwt(token: &str, config: &env::Env) -> Result<Claims, AppError>
 
     decode::<Claims>(
         token,
-        &DecodingKey::from_secret(config.jwt_secret.as_ref()),
+        &DecodingKey::from_secret(config.auth.jwt_secret.as_ref()),
         &validation,
     )
     .map(|data| data.claims)
@@ -0,0 +1,49 @@
+//! Fixtures shared by the integration test crates.
+
+// 테스트 파일마다 사용하는 도우미가 다름
+#![allow(dead_code)]
+
+use admin_server::{
+    config::env::{AuthConfig, Env},
+    dto::{user::CreateUserRequest, user_type::CreateUserTypeRequest},
+    repositories::{UserRepository, UserTypeRepository},
+    services,
+};
+
+pub fn test_env() -> Env {
+    Env {
+        auth: AuthConfig {
+            jwt_secret: "test-secret".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+pub async fn create_user_type(repo: &dyn UserTypeRepository, name: &str) -> i64 {
+    services::user_type::create_user_type(
+        repo,
+        CreateUserTypeRequest {
+            name: name.to_string(),
+            description: None,
+        },
+    )
+    .await
+    .unwrap()
+    .id
+}
+
+// 비밀번호는 password123
+pub async fn create_user(repo: &dyn UserRepository, username: &str, user_type_id: i64) -> i64 {
+    services::user::create_user(
+        repo,
+        CreateUserRequest {
+            username: username.to_string(),
+            password: "password123".to_string(),
+            user_type_id,
+            is_active: None,
+        },
+    )
+    .await
+    .unwrap()
+}
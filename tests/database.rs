@@ -0,0 +1,1487 @@
+//! Runs the migrations and the main service flows against the backend selected by the
+//! cargo feature (`sqlite` by default, `postgres` with `--no-default-features --features postgres`).
+//!
+//! `TEST_DATABASE_URL` selects the database. For SQLite a temporary file is used when it is
+//! unset; for PostgreSQL it must point at a disposable database (its `public` schema is
+//! recreated), e.g. the instance started by `scripts/test-postgres.sh`.
+
+mod common;
+
+use actix_web::{http::header, test, web, App};
+use admin_server::{
+    config::{
+        db::{self, DbPool},
+        env::{Env, SchedulerConfig},
+    },
+    dto::{
+        common::ListQueryParams,
+        health::HealthStatus,
+        permission::CreatePermissionRequest,
+        setup::SetupRequest,
+        snapshot::{
+            ConfigSnapshot, MenuSnapshot, PermissionSnapshot, SnapshotAction, SnapshotApplyParams,
+            SnapshotEntity, UserTypeSnapshot,
+        },
+        transfer::{ExportQueryParams, ImportQueryParams, TransferFormat},
+        user::CreateUserRequest,
+        user_type::UpdateUserTypeRequest,
+    },
+    errors::AppError,
+    handlers,
+    middleware::auth::authentication_middleware::Authentication,
+    repositories::{
+        self, AccessReviewRepository, JobRepository, NewAccessReview, NewAccessReviewItem,
+        NewJobRun, PermissionRepository, SqlxRepository, UserPermissionChange, UserRepository,
+    },
+    services::{
+        self,
+        scheduler::{Job, Scheduler, TRIGGER_MANUAL, TRIGGER_SCHEDULE},
+        transfer::UploadedFile,
+    },
+    util::{self, create_jwt},
+};
+use chrono::DurationRound;
+use common::create_user;
+use futures_util::StreamExt;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::sync::{Mutex, MutexGuard};
+
+// PostgreSQL에서는 테스트마다 같은 `public` 스키마를 다시 만들므로 테스트를 하나씩 실행
+static DB_LOCK: Mutex<()> = Mutex::const_new(());
+
+async fn setup_pool() -> Option<(MutexGuard<'static, ()>, DbPool)> {
+    setup_database().await.map(|(guard, _, pool)| (guard, pool))
+}
+
+// 마이그레이션을 적용한 테스트 DB (서버와 같은 방식으로 풀을 만들 수 있도록 접속 URL도 반환)
+async fn setup_database() -> Option<(MutexGuard<'static, ()>, String, DbPool)> {
+    let guard = DB_LOCK.lock().await;
+
+    let url = match std::env::var("TEST_DATABASE_URL") {
+        Ok(url) => url,
+        Err(_) if cfg!(feature = "postgres") => {
+            eprintln!("TEST_DATABASE_URL is not set; skipping PostgreSQL test");
+            return None;
+        }
+        Err(_) => {
+            let path = std::env::temp_dir().join(format!(
+                "admin_server_test_{}_{}.db",
+                std::process::id(),
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+            ));
+            format!("sqlite://{}?mode=rwc", path.display())
+        }
+    };
+
+    let pool = db::create_pool(&url, &db::DbOptions::default())
+        .await
+        .expect("failed to connect");
+    if cfg!(feature = "postgres") {
+        sqlx::raw_sql("DROP SCHEMA public CASCADE; CREATE SCHEMA public;")
+            .execute(&pool)
+            .await
+            .expect("failed to reset schema");
+    }
+    db::migrate_db(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .expect("failed to run migrations");
+    Some((guard, url, pool))
+}
+
+fn test_env() -> Env {
+    let mut env = common::test_env();
+    env.auth.setup_token = Some("test-setup-token".to_string());
+    env
+}
+
+#[actix_web::test]
+async fn backend_end_to_end() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let repo = SqlxRepository::new(db::DbPools::single(pool.clone()));
+
+    let report = db::verify_schema(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .unwrap();
+    assert!(report.is_ok(), "{:?}", report);
+
+    // 준비 상태 검사 (DB 연결, 마이그레이션)
+    let maintenance = services::maintenance::MaintenanceState::default();
+    let supervisor = services::lifecycle::TaskSupervisor::default();
+    let pools = db::DbPools::single(pool.clone());
+    let health = services::health::readiness(&pools, &test_env(), &maintenance, &supervisor).await;
+    let status = |name: &str| {
+        health
+            .checks
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.status)
+    };
+    assert_eq!(status("database"), Some(HealthStatus::Up));
+    assert_eq!(status("migrations"), Some(HealthStatus::Up));
+    let guard = maintenance.enter().unwrap();
+    let health = services::health::readiness(&pools, &test_env(), &maintenance, &supervisor).await;
+    assert_eq!(health.status, HealthStatus::Down);
+    drop(guard);
+    // 종료가 시작되면 준비 상태 Down
+    let stopping = services::lifecycle::TaskSupervisor::default();
+    stopping.begin_shutdown();
+    let health = services::health::readiness(&pools, &test_env(), &maintenance, &stopping).await;
+    assert_eq!(health.status, HealthStatus::Down);
+    assert!(health.checks.iter().any(|c| c.name == "shutdown"));
+
+    // 최초 실행 설정
+    let setup_state = services::setup::SetupState::initialize(&pool, &test_env())
+        .await
+        .unwrap();
+    assert!(setup_state.is_required());
+    let root = services::setup::complete_setup(
+        &pool,
+        &setup_state,
+        SetupRequest {
+            setup_token: "test-setup-token".to_string(),
+            username: "root".to_string(),
+            password: "password123".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(!setup_state.is_required());
+
+    // 사용자 생성 및 검색 (대소문자 구분 없음)
+    let alice = || CreateUserRequest {
+        username: "Alice".to_string(),
+        password: "password123".to_string(),
+        user_type_id: 2,
+        is_active: None,
+    };
+    let id = services::user::create_user(&repo, alice()).await.unwrap();
+    assert_ne!(id, root.id);
+    let duplicate = services::user::create_user(&repo, alice()).await;
+    assert!(matches!(duplicate, Err(AppError::DatabaseError(_))));
+
+    let query = web_query("q=ali&sort_by=username");
+    let users = services::user::get_user_array(&repo, &query).await.unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, id);
+
+    // 동적 UPDATE 쿼리
+    let updated = services::user_type::update_user_type(
+        &repo,
+        2,
+        UpdateUserTypeRequest {
+            name: None,
+            description: Some("updated".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(updated.description, "updated");
+
+    // 사용 중인 사용자 종류는 삭제할 수 없음
+    let in_use = services::user_type::delete_user_type(&repo, &repo, 2).await;
+    assert!(matches!(in_use, Err(AppError::Conflict(_))), "{:?}", in_use);
+
+    services::permission::create_permission(
+        &repo,
+        CreatePermissionRequest {
+            code: "report:read".to_string(),
+            description: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    // 런타임 설정 저장/갱신/삭제 (upsert)
+    let settings = services::settings::Settings::load(&repo, &test_env())
+        .await
+        .unwrap();
+    let key = services::settings::JWT_EXPIRES_IN_SECONDS;
+    for value in [600, 1200] {
+        settings
+            .update(&repo, key.key, serde_json::json!(value), root.id)
+            .await
+            .unwrap();
+    }
+    let reloaded = services::settings::Settings::load(&repo, &test_env())
+        .await
+        .unwrap();
+    assert_eq!(reloaded.get(&key), 1200);
+    settings.reset(&repo, key.key).await.unwrap();
+    assert_eq!(settings.get(&key), 3600);
+
+    // 장기 미사용 계정 경고/비활성화/재활성화
+    let dormant = UserRepository::find_by_id(&repo, id)
+        .await
+        .unwrap()
+        .unwrap();
+    repo.mark_dormancy_warned(dormant.id).await.unwrap();
+    assert!(repo.deactivate(dormant.id, "Dormant: test").await.unwrap());
+    assert!(!repo.deactivate(dormant.id, "Dormant: test").await.unwrap());
+    assert!(repo
+        .list_active()
+        .await
+        .unwrap()
+        .iter()
+        .all(|u| u.id != dormant.id));
+    let deactivated = UserRepository::find_by_id(&repo, dormant.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(deactivated.dormancy_warned_at.is_some() && deactivated.deactivated_at.is_some());
+    assert_eq!(
+        deactivated.deactivated_reason.as_deref(),
+        Some("Dormant: test")
+    );
+    let reactivated = repo.reactivate(dormant.id).await.unwrap().unwrap();
+    assert!(reactivated.is_active && reactivated.reactivated_at.is_some());
+    assert!(reactivated.deactivated_reason.is_none() && reactivated.dormancy_warned_at.is_none());
+    assert!(repo.reactivate(dormant.id).await.unwrap().is_none());
+
+    // 예약 작업 잠금: 유효한 잠금은 다른 인스턴스가 가져갈 수 없고 만료되면 인수 가능
+    let now = chrono::Utc::now().naive_utc();
+    let until = now + chrono::Duration::minutes(5);
+    assert!(repo
+        .try_lock("backup", "a", now, until, None)
+        .await
+        .unwrap());
+    assert!(!repo
+        .try_lock("backup", "b", now, until, None)
+        .await
+        .unwrap());
+    repo.unlock("backup", "b").await.unwrap();
+    assert!(!repo
+        .try_lock("backup", "b", now, until, None)
+        .await
+        .unwrap());
+    let expired = until + chrono::Duration::seconds(1);
+    assert!(repo
+        .try_lock(
+            "backup",
+            "b",
+            expired,
+            expired + chrono::Duration::minutes(5),
+            None
+        )
+        .await
+        .unwrap());
+    repo.unlock("backup", "b").await.unwrap();
+    assert!(repo
+        .try_lock("backup", "a", now, until, None)
+        .await
+        .unwrap());
+
+    let run = repo
+        .start_run(&NewJobRun {
+            job_name: "backup",
+            trigger_type: "manual",
+            started_by: Some(root.id),
+            instance_id: "a",
+        })
+        .await
+        .unwrap();
+    assert_eq!(run.status, "running");
+    let run = repo
+        .finish_run(run.id, "succeeded", Some("done"))
+        .await
+        .unwrap();
+    assert_eq!(run.message.as_deref(), Some("done"));
+    assert!(run.finished_at.is_some());
+    assert_eq!(repo.list_runs("backup", 10).await.unwrap().len(), 1);
+    assert_eq!(
+        repo.delete_runs_before(now - chrono::Duration::days(1))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(repo.delete_runs_before(expired).await.unwrap(), 1);
+
+    // 접근 권한 검토: 항목 생성/결정/종료와 증적 내보내기 조인 쿼리
+    let review = repo
+        .create_review(
+            NewAccessReview {
+                name: "Q4".to_string(),
+                user_types: r#"["SuperAdmin"]"#.to_string(),
+                created_by: "root".to_string(),
+                due_at: None,
+            },
+            vec![NewAccessReviewItem {
+                user_id: root.id,
+                username: "root".to_string(),
+                user_type_id: root.user_type_id,
+                user_type_name: "SuperAdmin".to_string(),
+                permissions: r#"["*"]"#.to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+    let items = repo.list_items(review.id).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].decision, "pending");
+    let item = repo
+        .decide_item(review.id, items[0].id, "keep", Some("ok"), "auditor")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.decided_by.as_deref(), Some("auditor"));
+    assert!(repo
+        .decide_item(review.id + 1, items[0].id, "keep", None, "auditor")
+        .await
+        .unwrap()
+        .is_none());
+    repo.mark_item_applied(item.id).await.unwrap();
+    let closed = repo
+        .close_review(review.id, "auditor")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(closed.status, "closed");
+    assert!(repo
+        .close_review(review.id, "auditor")
+        .await
+        .unwrap()
+        .is_none());
+    // 종료된 캠페인과 이미 적용된 항목의 결정은 바꿀 수 없음
+    for item in &items {
+        assert!(repo
+            .decide_item(review.id, item.id, "revoke", None, "auditor")
+            .await
+            .unwrap()
+            .is_none());
+    }
+    assert_eq!(repo.list_reviews().await.unwrap().len(), 1);
+    let evidence: Vec<_> =
+        services::access_review::export_review(&pool, &repo, review.id, TransferFormat::Jsonl)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+    let evidence = String::from_utf8(
+        evidence
+            .into_iter()
+            .flat_map(|chunk| chunk.unwrap().to_vec())
+            .collect(),
+    )
+    .unwrap();
+    assert!(evidence.contains(r#""review_name":"Q4""#), "{}", evidence);
+    assert!(evidence.contains(r#""decision":"keep""#), "{}", evidence);
+
+    // 사용자별 권한 예외: 덮어쓰기(upsert), 삭제, 변경 이력
+    let report_read = PermissionRepository::find_by_code(&repo, "report:read")
+        .await
+        .unwrap()
+        .unwrap();
+    let change = UserPermissionChange {
+        user_id: root.id,
+        username: "root",
+        permission_id: report_read.id.unwrap(),
+        permission_code: "report:read",
+        changed_by: "root",
+    };
+    let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::days(1);
+    repo.set_user_override(&change, "allow", None, None)
+        .await
+        .unwrap();
+    let user_permission = repo
+        .set_user_override(&change, "deny", Some("test"), Some(expires_at))
+        .await
+        .unwrap();
+    assert_eq!(user_permission.permission_code, "report:read");
+    assert_eq!(user_permission.effect, "deny");
+    let overrides = repo.user_overrides(root.id).await.unwrap();
+    assert_eq!(overrides.len(), 1);
+    assert!(repo.remove_user_override(&change).await.unwrap());
+    assert!(!repo.remove_user_override(&change).await.unwrap());
+    let history = repo.user_override_history(root.id).await.unwrap();
+    let actions: Vec<_> = history
+        .iter()
+        .map(|a| (a.action.as_str(), a.effect.as_deref()))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            ("remove", Some("deny")),
+            ("set", Some("deny")),
+            ("set", Some("allow"))
+        ]
+    );
+
+    // 보관 기간이 지난 감사 이력 정리 (권한 변경 이력, 종료된 캠페인과 항목)
+    let later = chrono::Utc::now().naive_utc() + chrono::Duration::days(1);
+    assert_eq!(repo.delete_override_history_before(later).await.unwrap(), 3);
+    assert_eq!(repo.delete_closed_before(later).await.unwrap(), 1);
+    assert!(repo.list_reviews().await.unwrap().is_empty());
+    assert!(repo.list_items(review.id).await.unwrap().is_empty());
+
+    // 스냅샷은 같은 DB에 다시 적용해도 변경 사항이 없어야 함
+    let snapshot = services::snapshot::export_snapshot(&pool).await.unwrap();
+    assert!(snapshot.permissions.iter().any(|p| p.code == "report:read"));
+    let permissions = PermissionRepository::list_all(&repo).await.unwrap();
+    assert_eq!(permissions.len(), snapshot.permissions.len());
+    let plan =
+        services::snapshot::plan_snapshot(&pool, &SnapshotApplyParams { prune: true }, &snapshot)
+            .await
+            .unwrap();
+    assert!(plan.changes.is_empty(), "{:?}", plan.changes);
+
+    // 모든 마이그레이션 되돌리기 후 다시 적용
+    db::revert_migrations(&pool, db::DEFAULT_MIGRATION_DIR, 0)
+        .await
+        .unwrap();
+    let report = db::verify_schema(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .unwrap();
+    assert!(report.applied.is_empty(), "{:?}", report);
+    assert_eq!(report.missing_tables.len(), db::REQUIRED_TABLES.len());
+
+    // 디렉터리가 없으면 바이너리에 포함된 마이그레이션으로 다시 적용
+    db::migrate_db(&pool, "./no-such-migration-dir")
+        .await
+        .unwrap();
+    let report = db::verify_schema(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .unwrap();
+    assert!(report.is_ok(), "{:?}", report);
+
+    // 종료 시 WAL 체크포인트 후 연결 종료
+    db::close_pools(&db::DbPools::single(pool.clone())).await;
+    assert!(pool.is_closed());
+}
+
+#[actix_web::test]
+async fn setup_endpoint_creates_single_super_admin() {
+    let Some((_guard, url, pool)) = setup_database().await else {
+        return;
+    };
+    let pools = db::create_pools(&url, &db::DbOptions::default())
+        .await
+        .unwrap();
+    let repo = Arc::new(SqlxRepository::new(pools.clone()));
+    let env = test_env();
+    let setup_state = web::Data::new(
+        services::setup::SetupState::initialize(&pool, &env)
+            .await
+            .unwrap(),
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .app_data(web::Data::new(pools))
+            .app_data(setup_state.clone())
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(Authentication)
+            .service(web::scope("/api/v1").service(handlers::setup::route())),
+    )
+    .await;
+    let status = || test::TestRequest::get().uri("/api/v1/setup").to_request();
+    let setup = |token: &str, password: &str| {
+        test::TestRequest::post()
+            .uri("/api/v1/setup")
+            .set_json(serde_json::json!({
+                "setup_token": token,
+                "username": "root",
+                "password": password,
+            }))
+            .to_request()
+    };
+
+    // 계정이 없으면 인증 없이 설정 상태를 조회할 수 있음
+    let res = test::call_service(&app, status()).await;
+    assert_eq!(res.status(), 200);
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["setup_required"], true);
+
+    // 잘못된 토큰이나 유효하지 않은 요청은 계정을 만들지 않음
+    let res = test::call_service(&app, setup("wrong-token", "password123")).await;
+    assert_eq!(res.status(), 401);
+    let res = test::call_service(&app, setup("test-setup-token", "short")).await;
+    assert_eq!(res.status(), 400);
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM admin_user")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 0);
+
+    // 첫 요청만 SuperAdmin을 만들고 이후 요청은 거부
+    let res = test::call_service(&app, setup("test-setup-token", "password123")).await;
+    assert_eq!(res.status(), 201);
+    let created: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(created["username"], "root");
+    assert_eq!(created["user_type_id"], 1);
+    assert!(created.get("password_hash").is_none());
+    let res = test::call_service(&app, setup("test-setup-token", "password123")).await;
+    assert_eq!(res.status(), 404);
+    let res = test::call_service(&app, status()).await;
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["setup_required"], false);
+
+    // 모든 사용자를 삭제해도 재시작 후 설정 모드로 돌아가지 않음
+    sqlx::query("DELETE FROM admin_user")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let restarted = services::setup::SetupState::initialize(&pool, &env)
+        .await
+        .unwrap();
+    assert!(!restarted.is_required());
+}
+
+#[actix_web::test]
+async fn concurrent_setup_has_single_winner() {
+    // SQLite는 단일 쓰기 연결로 쓰기가 직렬화되므로 PostgreSQL에서만 확인
+    if !cfg!(feature = "postgres") {
+        return;
+    }
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let setup_state = services::setup::SetupState::initialize(&pool, &test_env())
+        .await
+        .unwrap();
+
+    // 다른 인스턴스의 설정 요청이 커밋되기 전에 이 인스턴스도 설정 완료 여부를 확인한 상황
+    let mut other = pool.begin().await.unwrap();
+    let other_id: i64 = sqlx::query_scalar(
+        "INSERT INTO admin_user (username, password_hash, user_type_id, is_active) VALUES ('other_root', 'x', 1, TRUE) RETURNING id",
+    )
+    .fetch_one(&mut *other)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO setup_state (id, completed_by) VALUES (1, $1)")
+        .bind(other_id)
+        .execute(&mut *other)
+        .await
+        .unwrap();
+
+    let (result, ()) = futures_util::future::join(
+        services::setup::complete_setup(
+            &pool,
+            &setup_state,
+            SetupRequest {
+                setup_token: "test-setup-token".to_string(),
+                username: "root".to_string(),
+                password: "password123".to_string(),
+            },
+        ),
+        async {
+            // 이 인스턴스가 setup_state 행 잠금을 기다리기 시작하면 다른 인스턴스를 커밋
+            for _ in 0..500 {
+                let waiting: bool =
+                    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_locks WHERE NOT granted)")
+                        .fetch_one(&pool)
+                        .await
+                        .unwrap();
+                if waiting {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            other.commit().await.unwrap();
+        },
+    )
+    .await;
+    assert!(result.is_err(), "{:?}", result);
+    let admins: Vec<String> = sqlx::query_scalar("SELECT username FROM admin_user")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(admins, ["other_root"]);
+}
+
+#[actix_web::test]
+async fn import_export_round_trip() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let repo = SqlxRepository::new(db::DbPools::single(pool.clone()));
+    let import = |format, dry_run| ImportQueryParams {
+        format: Some(format),
+        dry_run,
+    };
+    let upload = |data: &str| UploadedFile {
+        filename: None,
+        data: data.as_bytes().to_vec(),
+    };
+    let export_permissions = |format| {
+        let pool = pool.clone();
+        async move {
+            let stream =
+                services::permission::export_permissions(&pool, &ExportQueryParams { format })
+                    .await
+                    .unwrap();
+            let chunks: Vec<_> = stream.collect().await;
+            chunks
+                .into_iter()
+                .map(|chunk| String::from_utf8(chunk.unwrap().to_vec()).unwrap())
+                .collect::<String>()
+        }
+    };
+
+    // 행 단위 오류는 행 번호와 함께 보고되고 나머지 행은 가져옴
+    let csv =
+        "code,description\nimport:read,Read\n,Empty code\nimport:read,Duplicate\nimport:write,\n";
+    let report = services::permission::import_permissions(
+        &pool,
+        &import(TransferFormat::Csv, true),
+        &upload(csv),
+    )
+    .await
+    .unwrap();
+    assert_eq!((report.total, report.imported, report.failed), (4, 2, 2));
+    let failed_rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+    assert_eq!(failed_rows, vec![2, 3]);
+    // dry run은 롤백
+    assert!(PermissionRepository::find_by_code(&repo, "import:read")
+        .await
+        .unwrap()
+        .is_none());
+
+    let report = services::permission::import_permissions(
+        &pool,
+        &import(TransferFormat::Csv, false),
+        &upload(csv),
+    )
+    .await
+    .unwrap();
+    assert_eq!((report.imported, report.failed), (2, 2));
+
+    // 내보낸 JSONL을 다시 가져오면 같은 데이터가 복원됨 (기본 권한은 그대로 두므로 제외)
+    let exported: String = export_permissions(TransferFormat::Jsonl)
+        .await
+        .lines()
+        .filter(|line| line.contains(r#""code":"import:"#))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let pairs = |jsonl: &str| {
+        let mut pairs: Vec<(String, Option<String>)> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .filter(|v| v["code"].as_str().unwrap().starts_with("import:"))
+            .map(|v| {
+                (
+                    v["code"].as_str().unwrap().to_string(),
+                    v["description"].as_str().map(str::to_string),
+                )
+            })
+            .collect();
+        pairs.sort();
+        pairs
+    };
+    assert_eq!(
+        pairs(&exported),
+        vec![
+            ("import:read".to_string(), Some("Read".to_string())),
+            ("import:write".to_string(), None),
+        ]
+    );
+    sqlx::query("DELETE FROM permission WHERE code LIKE 'import:%'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let report = services::permission::import_permissions(
+        &pool,
+        &import(TransferFormat::Jsonl, false),
+        &upload(&exported),
+    )
+    .await
+    .unwrap();
+    assert_eq!(report.failed, 0, "{:?}", report.errors);
+    assert_eq!(
+        pairs(&export_permissions(TransferFormat::Jsonl).await),
+        pairs(&exported)
+    );
+
+    // CSV 내보내기는 헤더와 함께 기존 컬럼 순서를 유지
+    let exported = export_permissions(TransferFormat::Csv).await;
+    assert!(exported.starts_with("id,code,description"), "{}", exported);
+    assert!(exported.contains(",import:read,Read,"));
+
+    // 사용자 가져오기: 유효하지 않은 행만 실패하고 비밀번호는 해시되어 저장
+    let jsonl = concat!(
+        r#"{"username":"imported","password":"password123","user_type_id":2}"#,
+        "\n",
+        r#"{"username":"short","password":"pw","user_type_id":2}"#,
+        "\n",
+        "not json\n",
+        r#"{"username":"inactive","password":"password123","user_type_id":2,"is_active":false}"#,
+        "\n",
+    );
+    let report = services::user::import_user_array(
+        &pool,
+        &import(TransferFormat::Jsonl, false),
+        &upload(jsonl),
+    )
+    .await
+    .unwrap();
+    assert_eq!((report.total, report.imported, report.failed), (4, 2, 2));
+    let failed_rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+    assert_eq!(failed_rows, vec![2, 3]);
+    let imported = UserRepository::find_by_username(&repo, "imported")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(imported.password_hash, "password123");
+    assert!(
+        util::verify_password("password123", &imported.password_hash)
+            .await
+            .unwrap()
+    );
+    let inactive = UserRepository::find_by_username(&repo, "inactive")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!inactive.is_active);
+
+    // 내보내기는 목록 API의 필터를 따르고 비밀번호 해시는 포함하지 않음
+    let stream = services::user::export_user_array(
+        &pool,
+        &web_query("q=import"),
+        &ExportQueryParams {
+            format: TransferFormat::Csv,
+        },
+    )
+    .await
+    .unwrap();
+    let chunks: Vec<_> = stream.collect().await;
+    let exported: String = chunks
+        .into_iter()
+        .map(|chunk| String::from_utf8(chunk.unwrap().to_vec()).unwrap())
+        .collect();
+    let lines: Vec<&str> = exported.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", exported);
+    assert!(lines[1].contains(",imported,2,true,"));
+    assert!(!exported.contains("password"));
+}
+
+#[actix_web::test]
+async fn import_export_endpoints() {
+    let Some((_guard, url, _pool)) = setup_database().await else {
+        return;
+    };
+    let pools = db::create_pools(&url, &db::DbOptions::default())
+        .await
+        .unwrap();
+    let repo = Arc::new(SqlxRepository::new(pools.clone()));
+    let env = test_env();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .app_data(web::Data::new(pools))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(Authentication)
+            .service(handlers::menu::route())
+            .service(handlers::permission::route())
+            .service(handlers::user::route())
+            .service(handlers::user_type::route()),
+    )
+    .await;
+    let id = create_user(repo.as_ref(), "importer", 1).await;
+    let token = create_jwt(id, 1, "importer", 3600, &env).unwrap();
+    let upload = |uri: &str, field: &str, filename: &str, data: &str| {
+        let body = format!(
+            "--boundary\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n{}\r\n--boundary--\r\n",
+            field, filename, data
+        );
+        test::TestRequest::post()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .insert_header((
+                header::CONTENT_TYPE,
+                "multipart/form-data; boundary=boundary",
+            ))
+            .set_payload(body)
+            .to_request()
+    };
+    let export = |uri: &str| {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    // 형식은 파일 확장자로 판단하고, dry run은 행 단위 결과만 보고한 뒤 롤백
+    let jsonl = concat!(
+        r#"{"name":"Auditor","description":"Reads audit logs"}"#,
+        "\n",
+        r#"{"name":""}"#,
+        "\n",
+        r#"{"name":"Operator"}"#,
+        "\n",
+    );
+    let res = test::call_service(
+        &app,
+        upload(
+            "/user-types/import?dry_run=true",
+            "file",
+            "types.jsonl",
+            jsonl,
+        ),
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+    let report: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(
+        (&report["total"], &report["imported"], &report["failed"]),
+        (&3.into(), &2.into(), &1.into())
+    );
+    assert_eq!(report["errors"][0]["row"], 2);
+    let res = test::call_service(&app, export("/user-types/export?format=jsonl")).await;
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(!body.contains("Auditor"), "{}", body);
+
+    // 실제 가져오기 후 내보내기는 첨부 파일로 스트리밍
+    let res = test::call_service(
+        &app,
+        upload("/user-types/import", "file", "types.jsonl", jsonl),
+    )
+    .await;
+    let report: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        (&report["imported"], &report["failed"]),
+        (&2.into(), &1.into())
+    );
+    let res = test::call_service(&app, export("/user-types/export?format=jsonl")).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(
+        res.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/x-ndjson"
+    );
+    let disposition = res.headers().get(header::CONTENT_DISPOSITION).unwrap();
+    assert!(disposition
+        .to_str()
+        .unwrap()
+        .contains(r#"filename="user_types.jsonl""#));
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(body.contains(r#""name":"Auditor""#), "{}", body);
+    assert!(body.contains(r#""name":"Operator""#), "{}", body);
+
+    // 존재하지 않는 부모를 가리키는 메뉴 행만 실패
+    let csv = "name,path,parent_id\nReports,/reports,\nOrphan,/orphan,999\n";
+    let res = test::call_service(&app, upload("/menu/import", "file", "menus.csv", csv)).await;
+    assert_eq!(res.status(), 200);
+    let report: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        (&report["imported"], &report["failed"]),
+        (&1.into(), &1.into())
+    );
+    assert_eq!(report["errors"][0]["row"], 2);
+    let res = test::call_service(&app, export("/menu/export")).await;
+    let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+    assert!(body.contains(",Reports,/reports,"), "{}", body);
+    assert!(!body.contains("Orphan"), "{}", body);
+
+    // `file` 필드가 없으면 거부
+    let res = test::call_service(&app, upload("/menu/import", "other", "menus.csv", csv)).await;
+    assert_eq!(res.status(), 400);
+
+    // 전체 권한이 없는 사용자는 가져오기/내보내기 모두 거부
+    let viewer_id = create_user(repo.as_ref(), "viewer", 2).await;
+    let viewer_token = create_jwt(viewer_id, 2, "viewer", 3600, &env).unwrap();
+    for scope in ["/user", "/permission", "/user-types", "/menu"] {
+        let req = test::TestRequest::get()
+            .uri(&format!("{}/export", scope))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", viewer_token)))
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            403,
+            "{}",
+            scope
+        );
+        let req = test::TestRequest::post()
+            .uri(&format!("{}/import", scope))
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", viewer_token)))
+            .insert_header((
+                header::CONTENT_TYPE,
+                "multipart/form-data; boundary=boundary",
+            ))
+            .set_payload(format!(
+                "--boundary\r\nContent-Disposition: form-data; name=\"file\"; filename=\"rows.csv\"\r\n\r\n{}\r\n--boundary--\r\n",
+                "username,password,user_type_id\nescalated,password123,1"
+            ))
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            403,
+            "{}",
+            scope
+        );
+    }
+    assert!(UserRepository::find_by_username(repo.as_ref(), "escalated")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[actix_web::test]
+async fn upgrade_removes_seeded_admin() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    // 0001만 적용되어 기본 admin 계정이 남아 있는 기존 설치본
+    let seeded = |pool: DbPool, other: Option<&'static str>| async move {
+        db::revert_migrations(&pool, db::DEFAULT_MIGRATION_DIR, 1)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM admin_user")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO admin_user (username, password_hash, user_type_id) VALUES ('admin', '$2b$12$5ONiULm9P4oefANo/NYCguK4W4NdKYgoAbKFl9X5iqaMZeS7IfWqy', 1)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        if let Some(username) = other {
+            sqlx::query(
+                "INSERT INTO admin_user (username, password_hash, user_type_id) VALUES ($1, 'x', 1)",
+            )
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        if cfg!(feature = "sqlite") {
+            // 시드가 들어 있던 이전 버전의 0001 체크섬으로 기록된 상태
+            sqlx::query("UPDATE _sqlx_migrations SET checksum = $1 WHERE version = 1")
+                .bind(SEEDED_SCHEMA_CHECKSUM.as_slice())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        db::migrate_db(&pool, db::DEFAULT_MIGRATION_DIR)
+            .await
+            .unwrap();
+        let users: Vec<String> = sqlx::query_scalar("SELECT username FROM admin_user")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        let setup_state = services::setup::SetupState::initialize(&pool, &test_env())
+            .await
+            .unwrap();
+        let report = db::verify_schema(&pool, db::DEFAULT_MIGRATION_DIR)
+            .await
+            .unwrap();
+        assert!(report.is_ok(), "{:?}", report);
+        (users, setup_state.is_required())
+    };
+
+    // 기본 계정만 있으면 삭제되고 설정 모드로 시작
+    let (users, required) = seeded(pool.clone(), None).await;
+    assert!(users.is_empty() && required, "{:?}", users);
+    // 다른 사용자가 있으면 기본 계정만 삭제되고 설정 완료로 간주
+    let (users, required) = seeded(pool.clone(), Some("ops")).await;
+    assert_eq!(users, ["ops"]);
+    assert!(!required);
+
+    // 알려진 이전 버전이 아닌 체크섬은 그대로 두고 마이그레이션을 거부
+    sqlx::query("UPDATE _sqlx_migrations SET checksum = $1 WHERE version = 1")
+        .bind([0u8; 48].as_slice())
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(db::migrate_db(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .is_err());
+    let report = db::verify_schema(&pool, db::DEFAULT_MIGRATION_DIR)
+        .await
+        .unwrap();
+    assert_eq!(report.checksum_mismatches, [1]);
+}
+
+#[actix_web::test]
+async fn fresh_install_has_no_users() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    // 0001만 적용된 상태에서도 계정이 없어야 함 (기본 계정은 /setup으로만 생성)
+    db::revert_migrations(&pool, db::DEFAULT_MIGRATION_DIR, 1)
+        .await
+        .unwrap();
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM admin_user")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 0);
+}
+
+// 기본 admin 계정 시드가 들어 있던 sqlite 0001_initial_schema의 SHA-384 체크섬
+const SEEDED_SCHEMA_CHECKSUM: [u8; 48] = [
+    0x82, 0xcd, 0x6d, 0xda, 0xbd, 0x2d, 0x07, 0x26, 0x2f, 0x47, 0x33, 0xfb, 0x76, 0xda, 0x15, 0x45,
+    0x5c, 0x2d, 0xbc, 0xa8, 0xea, 0x20, 0xb0, 0xdb, 0xf5, 0x72, 0xb6, 0x67, 0x99, 0x5a, 0x7d, 0x02,
+    0x8c, 0x1d, 0x1a, 0x74, 0x1b, 0x7c, 0x2f, 0xab, 0x20, 0x49, 0x61, 0xba, 0x17, 0x63, 0xab, 0x38,
+];
+
+#[actix_web::test]
+async fn snapshot_validation_apply_and_prune() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let menu = |path: &str, parent_path: Option<&str>| MenuSnapshot {
+        path: path.to_string(),
+        name: path.trim_start_matches('/').to_string(),
+        icon: None,
+        parent_path: parent_path.map(str::to_string),
+        display_order: 0,
+        is_visible: true,
+    };
+    let base = services::snapshot::export_snapshot(&pool).await.unwrap();
+    let mut desired = base.clone();
+    desired.permissions.push(PermissionSnapshot {
+        code: "report:read".to_string(),
+        description: Some("Read reports".to_string()),
+    });
+    // 자식 메뉴가 부모보다 먼저 나와도 부모부터 생성
+    desired.menus.push(menu("/admin/reports", Some("/admin")));
+    desired.menus.push(menu("/admin", None));
+    desired.user_types.push(UserTypeSnapshot {
+        name: "ReportViewer".to_string(),
+        description: None,
+        permissions: vec!["report:read".to_string()],
+        menus: vec!["/admin/reports".to_string()],
+    });
+    let params = |prune| SnapshotApplyParams { prune };
+
+    // 스냅샷 내부 참조 오류는 DB를 건드리기 전에 거부
+    type Edit = fn(&mut ConfigSnapshot);
+    let invalid: Vec<(Edit, &str)> = vec![
+        (|s| s.version = 2, "Unsupported snapshot version"),
+        (
+            |s| s.permissions.push(s.permissions[0].clone()),
+            "Duplicate permission code",
+        ),
+        (
+            |s| s.menus[0].parent_path = Some("/missing".to_string()),
+            "unknown parent",
+        ),
+        (
+            |s| {
+                let admin = s.menus.iter_mut().find(|m| m.path == "/admin").unwrap();
+                admin.parent_path = Some("/admin/reports".to_string());
+            },
+            "cyclic parent reference",
+        ),
+        (
+            |s| s.user_types[0].permissions.push("missing:code".to_string()),
+            "unknown permission",
+        ),
+        (
+            |s| s.user_types[0].menus.push("/missing".to_string()),
+            "unknown menu",
+        ),
+    ];
+    for (mutate, expected) in invalid {
+        let mut snapshot = desired.clone();
+        mutate(&mut snapshot);
+        match services::snapshot::plan_snapshot(&pool, &params(false), &snapshot).await {
+            Err(AppError::BadRequest(message)) => {
+                assert!(message.contains(expected), "{}", message)
+            }
+            other => panic!(
+                "expected `{}`, got {:?}",
+                expected,
+                other.map(|p| p.changes)
+            ),
+        }
+    }
+
+    // 계획은 롤백되고 적용은 커밋되며, 같은 스냅샷을 다시 적용하면 변경 없음
+    let plan = services::snapshot::plan_snapshot(&pool, &params(false), &desired)
+        .await
+        .unwrap();
+    assert!(!plan.applied);
+    let created: Vec<(SnapshotEntity, &str)> = plan
+        .changes
+        .iter()
+        .filter(|c| c.action == SnapshotAction::Create)
+        .map(|c| (c.entity, c.key.as_str()))
+        .collect();
+    assert!(
+        created
+            .iter()
+            .position(|c| *c == (SnapshotEntity::MenuItem, "/admin"))
+            < created
+                .iter()
+                .position(|c| *c == (SnapshotEntity::MenuItem, "/admin/reports")),
+        "{:?}",
+        created
+    );
+    assert!(created.contains(&(SnapshotEntity::UserType, "ReportViewer")));
+    assert_eq!(
+        services::snapshot::export_snapshot(&pool)
+            .await
+            .unwrap()
+            .menus,
+        base.menus
+    );
+
+    let applied = services::snapshot::apply_snapshot(&pool, "admin", &params(false), &desired)
+        .await
+        .unwrap();
+    assert!(applied.applied);
+    assert_eq!(applied.changes.len(), plan.changes.len());
+    let plan = services::snapshot::plan_snapshot(&pool, &params(true), &desired)
+        .await
+        .unwrap();
+    assert!(plan.changes.is_empty(), "{:?}", plan.changes);
+
+    // prune 없이 적용하면 스냅샷에 없는 항목은 그대로 유지
+    let plan = services::snapshot::plan_snapshot(&pool, &params(false), &base)
+        .await
+        .unwrap();
+    assert!(plan.changes.is_empty(), "{:?}", plan.changes);
+
+    // prune은 스냅샷에 없는 역할/권한/메뉴를 삭제
+    let pruned = services::snapshot::apply_snapshot(&pool, "admin", &params(true), &base)
+        .await
+        .unwrap();
+    let mut deleted: Vec<(SnapshotEntity, &str)> = pruned
+        .changes
+        .iter()
+        .filter(|c| c.action == SnapshotAction::Delete)
+        .map(|c| (c.entity, c.key.as_str()))
+        .collect();
+    deleted.retain(|(entity, _)| {
+        matches!(
+            entity,
+            SnapshotEntity::Permission | SnapshotEntity::UserType | SnapshotEntity::MenuItem
+        )
+    });
+    // 메뉴 삭제 시 자식 메뉴의 parent_id를 먼저 비우므로 삭제 순서는 무관
+    deleted.sort_by_key(|(_, key)| *key);
+    assert_eq!(
+        deleted,
+        [
+            (SnapshotEntity::MenuItem, "/admin"),
+            (SnapshotEntity::MenuItem, "/admin/reports"),
+            (SnapshotEntity::UserType, "ReportViewer"),
+            (SnapshotEntity::Permission, "report:read"),
+        ]
+    );
+    let current = services::snapshot::export_snapshot(&pool).await.unwrap();
+    assert_eq!(
+        (current.permissions, current.user_types, current.menus),
+        (
+            base.permissions.clone(),
+            base.user_types.clone(),
+            base.menus.clone()
+        )
+    );
+
+    // 사용자가 배정된 역할은 prune으로 삭제할 수 없고 전체 적용이 롤백됨
+    services::snapshot::apply_snapshot(&pool, "admin", &params(false), &desired)
+        .await
+        .unwrap();
+    let viewer_type: i64 =
+        sqlx::query_scalar("SELECT id FROM user_type WHERE name = 'ReportViewer'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    sqlx::query(
+        "INSERT INTO admin_user (username, password_hash, user_type_id) VALUES ('viewer', 'x', $1)",
+    )
+    .bind(viewer_type)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let result = services::snapshot::apply_snapshot(&pool, "admin", &params(true), &base).await;
+    assert!(
+        matches!(result, Err(AppError::Conflict(_))),
+        "{:?}",
+        result.map(|p| p.changes)
+    );
+    let current = services::snapshot::export_snapshot(&pool).await.unwrap();
+    assert!(current.permissions.iter().any(|p| p.code == "report:read"));
+}
+
+#[actix_web::test]
+async fn snapshot_endpoints_require_super_admin() {
+    let Some((_guard, url, pool)) = setup_database().await else {
+        return;
+    };
+    // 서버와 동일하게 읽기 전용(query_only) 풀과 쓰기 풀을 분리
+    let pools = db::create_pools(&url, &db::DbOptions::default())
+        .await
+        .unwrap();
+    let repo = Arc::new(SqlxRepository::new(pools.clone()));
+    let env = test_env();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .app_data(web::Data::new(pools))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(Authentication)
+            .service(handlers::snapshot::route(1024 * 1024)),
+    )
+    .await;
+    let token = |user_type_id: i64, username: &'static str| {
+        let (repo, env) = (repo.clone(), env.clone());
+        async move {
+            let id = create_user(repo.as_ref(), username, user_type_id).await;
+            create_jwt(id, user_type_id, username, 3600, &env).unwrap()
+        }
+    };
+    let admin = token(1, "admin").await;
+    let viewer = token(2, "viewer").await;
+    let snapshot = services::snapshot::export_snapshot(&pool).await.unwrap();
+    let post = |uri: &str, token: &str| {
+        test::TestRequest::post()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .set_json(&snapshot)
+            .to_request()
+    };
+
+    // 전체 권한이 없으면 계획/적용 모두 거부
+    for uri in ["/snapshot/plan", "/snapshot/apply"] {
+        let res = test::call_service(&app, post(uri, &viewer)).await;
+        assert_eq!(res.status(), 403, "{}", uri);
+    }
+    for uri in ["/snapshot/plan", "/snapshot/apply"] {
+        let res = test::call_service(&app, post(uri, &admin)).await;
+        assert_eq!(res.status(), 200, "{}", uri);
+    }
+
+    // 변경이 있는 계획도 실행 후 롤백되며 DB는 그대로
+    let mut changed = snapshot.clone();
+    changed.permissions.push(PermissionSnapshot {
+        code: "report:plan".to_string(),
+        description: None,
+    });
+    let req = test::TestRequest::post()
+        .uri("/snapshot/plan")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", admin)))
+        .set_json(&changed)
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    let plan: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(plan["applied"], false);
+    assert_eq!(plan["changes"].as_array().unwrap().len(), 1, "{}", plan);
+    assert!(
+        PermissionRepository::find_by_code(repo.as_ref(), "report:plan")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[actix_web::test]
+async fn scheduled_slot_runs_once_across_instances() {
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let repo = Arc::new(SqlxRepository::new(db::DbPools::single(pool)));
+    let count = Arc::new(AtomicUsize::new(0));
+    let scheduler = || {
+        let counter = count.clone();
+        let job = Job::new("count", "Counts its runs", move || {
+            let counter = counter.clone();
+            async move {
+                // 다른 인스턴스가 실행 중에 같은 slot을 시도하도록 잠시 대기
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                Ok(format!(
+                    "run {}",
+                    counter.fetch_add(1, Ordering::SeqCst) + 1
+                ))
+            }
+        });
+        let config = SchedulerConfig {
+            schedules: Default::default(),
+            ..Default::default()
+        };
+        Scheduler::new(repo.clone(), &config, vec![job]).unwrap()
+    };
+    let (a, b) = (scheduler(), scheduler());
+
+    // 같은 DB를 쓰는 두 인스턴스가 같은 slot에 깨어나도 한 번만 실행
+    let slot = chrono::Utc::now()
+        .duration_trunc(chrono::Duration::minutes(1))
+        .unwrap();
+    let (first, second) = futures_util::future::join(
+        a.run_scheduled("count", slot),
+        b.run_scheduled("count", slot),
+    )
+    .await;
+    assert_eq!(
+        [first.unwrap().is_some(), second.unwrap().is_some()]
+            .iter()
+            .filter(|ran| **ran)
+            .count(),
+        1
+    );
+    // 실행이 끝나 잠금이 풀린 뒤 늦게 깨어난 인스턴스도 같은 slot은 건너뜀
+    assert!(b.run_scheduled("count", slot).await.unwrap().is_none());
+    let earlier = slot - chrono::Duration::minutes(1);
+    assert!(a.run_scheduled("count", earlier).await.unwrap().is_none());
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    // 수동 실행은 slot과 무관하고, 다음 slot은 다시 실행
+    assert!(b
+        .run_now("count", TRIGGER_MANUAL, None)
+        .await
+        .unwrap()
+        .is_some());
+    let next = slot + chrono::Duration::minutes(1);
+    let run = b.run_scheduled("count", next).await.unwrap().unwrap();
+    assert_eq!(
+        (run.status.as_str(), run.trigger_type.as_str()),
+        ("succeeded", TRIGGER_SCHEDULE)
+    );
+    assert!(a.run_scheduled("count", next).await.unwrap().is_none());
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(feature = "sqlite")]
+#[actix_web::test]
+async fn backup_prune_verify_and_restore() {
+    use admin_server::services::{
+        backup,
+        maintenance::MaintenanceState,
+        settings::{Settings, JWT_EXPIRES_IN_SECONDS},
+    };
+    use sqlx::{sqlite::SqliteConnectOptions, ConnectOptions};
+
+    let Some((_guard, pool)) = setup_pool().await else {
+        return;
+    };
+    let repo = SqlxRepository::new(db::DbPools::single(pool.clone()));
+    let dir = std::env::temp_dir().join(format!(
+        "admin_server_backups_{}_{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let mut env = test_env();
+    env.backup.dir = dir.to_string_lossy().into_owned();
+    env.backup.compress = true;
+    let maintenance = MaintenanceState::default();
+    let setup_state = services::setup::SetupState::initialize(&pool, &env)
+        .await
+        .unwrap();
+    let settings = Settings::load(&repo, &env).await.unwrap();
+    let default_expiry = settings.get(&JWT_EXPIRES_IN_SECONDS);
+    let restore = |env: Env, name: String| {
+        let (pool, maintenance, settings, setup_state) =
+            (&pool, &maintenance, &settings, &setup_state);
+        async move {
+            backup::restore_backup(pool, &env, maintenance, settings, setup_state, &name).await
+        }
+    };
+    let usernames = || async {
+        sqlx::query_scalar::<_, String>("SELECT username FROM admin_user ORDER BY username")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+    };
+
+    // 압축 백업 -> 데이터/설정 변경 -> 복원하면 백업 시점으로 돌아가고 캐시도 갱신
+    let initial = backup::create_backup(&pool, &env).await.unwrap();
+    assert!(initial.compressed && initial.name.ends_with(".db.gz"));
+    let root = services::setup::complete_setup(
+        &pool,
+        &setup_state,
+        SetupRequest {
+            setup_token: "test-setup-token".to_string(),
+            username: "root".to_string(),
+            password: "password123".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    settings
+        .update(
+            &repo,
+            JWT_EXPIRES_IN_SECONDS.key,
+            serde_json::json!(default_expiry + 60),
+            root.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(usernames().await, ["root"]);
+    assert!(!setup_state.is_required());
+
+    let restored = restore(env.clone(), initial.name.clone()).await.unwrap();
+    assert_eq!(restored.restored, initial.name);
+    assert!(usernames().await.is_empty());
+    assert_eq!(settings.get(&JWT_EXPIRES_IN_SECONDS), default_expiry);
+    assert!(setup_state.is_required());
+    assert!(!maintenance.is_active());
+
+    // 복원 직전 상태는 안전 백업으로 남아 있어 되돌릴 수 있음
+    restore(env.clone(), restored.safety_backup.name.clone())
+        .await
+        .unwrap();
+    assert_eq!(usernames().await, ["root"]);
+    assert_eq!(settings.get(&JWT_EXPIRES_IN_SECONDS), default_expiry + 60);
+    assert!(!setup_state.is_required());
+
+    // 보관 개수를 넘는 오래된 백업은 새 백업을 만들 때 삭제
+    env.backup.retention = 2;
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let latest = backup::create_backup(&pool, &env).await.unwrap();
+    let names: Vec<String> = backup::list_backups(&env)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    assert_eq!(names.len(), 2, "{:?}", names);
+    assert_eq!(names[0], latest.name);
+    assert!(!names.contains(&initial.name));
+
+    // 손상된 백업은 현재 DB를 건드리지 않고 거부
+    for (name, content) in [
+        ("backup-20000101T000000000Z.db", &b"not a database"[..]),
+        ("backup-20000101T000000001Z.db.gz", &b"not gzip"[..]),
+    ] {
+        std::fs::write(dir.join(name), content).unwrap();
+        let result = restore(env.clone(), name.to_string()).await;
+        assert!(
+            matches!(result, Err(AppError::BadRequest(_))),
+            "{:?}",
+            result
+        );
+    }
+    assert_eq!(usernames().await, ["root"]);
+    let missing = restore(env.clone(), "../outside.db".to_string()).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    // 다른 스키마 버전으로 만든 백업은 거부
+    env.backup.compress = false;
+    env.backup.retention = 7;
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let older_schema = backup::create_backup(&pool, &env).await.unwrap();
+    let mut conn = SqliteConnectOptions::new()
+        .filename(dir.join(&older_schema.name))
+        .connect()
+        .await
+        .unwrap();
+    sqlx::query(
+        "DELETE FROM _sqlx_migrations WHERE version = (SELECT MAX(version) FROM _sqlx_migrations)",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    drop(conn);
+    let result = restore(env.clone(), older_schema.name.clone()).await;
+    assert!(
+        matches!(&result, Err(AppError::BadRequest(message)) if message.contains("schema")),
+        "{:?}",
+        result
+    );
+    assert_eq!(usernames().await, ["root"]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+fn web_query(query: &str) -> ListQueryParams {
+    web::Query::<ListQueryParams>::from_query(query)
+        .unwrap()
+        .into_inner()
+}
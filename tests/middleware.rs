@@ -0,0 +1,649 @@
+//! HTTP-level behaviour of the middleware, exercised against minimal apps.
+
+mod common;
+
+use actix_web::{http::header, test, web, App, HttpResponse};
+use admin_server::{
+    config::env::{Env, RateLimitConfig, RateLimitKey, RouteRateLimit, SecurityConfig},
+    errors::AppError,
+    handlers::extractors,
+    middleware::{
+        auth::{authenticated_user::AuthenticatedUser, authentication_middleware::Authentication},
+        cors::{cors, origin_allowed, validate_origin},
+        metrics::RequestMetrics,
+        rate_limit::RateLimit,
+        request_id::RequestId,
+        security_headers::SecurityHeaders,
+    },
+    repositories::{self, InMemoryRepository, UserRepository},
+    services::{
+        metrics::METRICS,
+        rate_limit::{route_for, RateLimiter},
+        settings::{Settings, CORS_ALLOWED_ORIGINS, RATE_LIMIT_ENABLED, RATE_LIMIT_ROUTES},
+    },
+    util::create_jwt,
+};
+use common::{create_user, create_user_type};
+use serde_json::{json, Value};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+fn test_env(allowed_origins: &[&str]) -> Env {
+    let mut env = common::test_env();
+    env.cors.allowed_origins = allowed_origins.iter().map(|s| s.to_string()).collect();
+    env
+}
+
+fn preflight(origin: &str, method: &str) -> test::TestRequest {
+    test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/v1/user")
+        .insert_header((header::ORIGIN, origin))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, method))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization"))
+}
+
+#[actix_web::test]
+async fn request_id_is_propagated_to_responses_and_errors() {
+    let app = test::init_service(
+        App::new()
+            .wrap(RequestId)
+            .route("/ok", web::get().to(HttpResponse::Ok))
+            .route(
+                "/missing",
+                web::get().to(|| async { Err::<HttpResponse, _>(AppError::not_found("gone")) }),
+            ),
+    )
+    .await;
+
+    // 클라이언트가 보낸 ID는 그대로 사용하고 오류 본문에도 포함
+    let req = test::TestRequest::get()
+        .uri("/missing")
+        .insert_header(("X-Request-Id", "abc-123"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers().get("x-request-id").unwrap(), "abc-123");
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["request_id"], "abc-123");
+
+    // 형식에 맞지 않으면 새로 생성
+    let req = test::TestRequest::get()
+        .uri("/ok")
+        .insert_header(("X-Request-Id", "not valid!"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let id = res.headers().get("x-request-id").unwrap().to_str().unwrap();
+    assert_ne!(id, "not valid!");
+    assert_eq!(id.len(), 36);
+}
+
+#[actix_web::test]
+async fn origin_patterns() {
+    let patterns = [
+        "https://*.example.com".to_string(),
+        "http://localhost:*".to_string(),
+    ];
+    for allowed in [
+        "https://app.example.com",
+        "https://a.b.example.com",
+        "HTTPS://App.Example.com",
+        "http://localhost:3000",
+        "http://localhost:5173",
+        "http://localhost",
+    ] {
+        assert!(origin_allowed(&patterns, allowed, false), "{}", allowed);
+    }
+    for denied in [
+        "https://example.com",
+        "https://evilexample.com",
+        "https://example.com.evil.org",
+        "http://app.example.com",
+        "https://app.example.com:8443",
+        "http://127.0.0.1:3000",
+    ] {
+        assert!(!origin_allowed(&patterns, denied, false), "{}", denied);
+    }
+
+    // "*" 는 자격 증명을 허용하지 않을 때만 적용
+    let any = ["*".to_string()];
+    assert!(origin_allowed(&any, "https://anything.org", false));
+    assert!(!origin_allowed(&any, "https://anything.org", true));
+
+    for invalid in [
+        "example.com",
+        "ftp://example.com",
+        "https://example.com/",
+        "https://*.com",
+        "https://exa mple.com",
+        "https://example.com:99999",
+    ] {
+        assert!(validate_origin(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[actix_web::test]
+async fn cors_preflight_and_rejected_requests() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let env = test_env(&["https://*.example.com"]);
+    let settings = web::Data::new(Settings::load(repo.as_ref(), &env).await.unwrap());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(Authentication)
+            .wrap(cors(&env.cors, settings.clone()))
+            .route("/api/v1/user", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    // 허용된 출처의 preflight는 인증 없이 응답
+    let res = test::call_service(
+        &app,
+        preflight("https://app.example.com", "PUT").to_request(),
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+    let headers = res.headers();
+    assert_eq!(
+        headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+        "https://app.example.com"
+    );
+    let methods = headers
+        .get(header::ACCESS_CONTROL_ALLOW_METHODS)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(methods.contains("PUT"));
+    assert_eq!(headers.get(header::ACCESS_CONTROL_MAX_AGE).unwrap(), "3600");
+    assert!(headers
+        .get(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+        .is_none());
+
+    // 허용되지 않은 출처/메서드는 거부
+    let res = test::call_service(&app, preflight("https://evil.org", "GET").to_request()).await;
+    assert_eq!(res.status(), 400);
+    assert!(res
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .is_none());
+    let res = test::call_service(
+        &app,
+        preflight("https://app.example.com", "TRACE").to_request(),
+    )
+    .await;
+    assert_eq!(res.status(), 400);
+
+    // 인증 실패 응답에도 CORS 헤더가 있어야 브라우저에서 401을 읽을 수 있음
+    let req = test::TestRequest::get()
+        .uri("/api/v1/user")
+        .insert_header((header::ORIGIN, "https://app.example.com"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 401);
+    assert_eq!(
+        res.headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(
+        res.headers()
+            .get(header::ACCESS_CONTROL_EXPOSE_HEADERS)
+            .unwrap(),
+        "x-request-id"
+    );
+
+    // 런타임 설정 변경은 재시작 없이 바로 적용
+    settings
+        .update(
+            repo.as_ref(),
+            CORS_ALLOWED_ORIGINS.key,
+            json!(["https://admin.other.org"]),
+            1,
+        )
+        .await
+        .unwrap();
+    let res = test::call_service(
+        &app,
+        preflight("https://admin.other.org", "GET").to_request(),
+    )
+    .await;
+    assert_eq!(res.status(), 200);
+    let res = test::call_service(
+        &app,
+        preflight("https://app.example.com", "GET").to_request(),
+    )
+    .await;
+    assert_eq!(res.status(), 400);
+    let invalid = settings
+        .update(
+            repo.as_ref(),
+            CORS_ALLOWED_ORIGINS.key,
+            json!(["https://*.com"]),
+            1,
+        )
+        .await;
+    assert!(matches!(invalid, Err(AppError::BadRequest(_))));
+}
+
+#[actix_web::test]
+async fn inactive_users_tokens_are_rejected() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let env = test_env(&[]);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(Authentication)
+            .route(
+                "/api/v1/user",
+                web::get().to(|user: AuthenticatedUser| async move {
+                    HttpResponse::Ok().json(json!({"type": user.user_type_id}))
+                }),
+            )
+            .route("/api/v1/health", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+    let admin = create_user_type(repo.as_ref(), "Admin").await;
+    let viewer = create_user_type(repo.as_ref(), "Viewer").await;
+    let id = create_user(repo.as_ref(), "alice", viewer).await;
+    // 발급 당시에는 사용자 종류가 Admin이었던 토큰
+    let token = create_jwt(id, admin, "alice", 3600, &env).unwrap();
+    let get = |uri: &str| {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    // 사용자 종류는 토큰이 아닌 현재 값
+    let res = test::call_service(&app, get("/api/v1/user")).await;
+    assert_eq!(res.status(), 200);
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["type"], viewer);
+
+    // 휴면 등으로 비활성화되면 기존 토큰도 거부
+    assert!(repo.deactivate(id, "Dormant: test").await.unwrap());
+    let res = test::call_service(&app, get("/api/v1/user")).await;
+    assert_eq!(res.status(), 401);
+    // 인증을 건너뛰는 경로는 그대로 허용
+    assert_eq!(
+        test::call_service(&app, get("/api/v1/health"))
+            .await
+            .status(),
+        200
+    );
+    // 없는 사용자의 토큰도 거부
+    let token = create_jwt(id + 100, admin, "ghost", 3600, &env).unwrap();
+    let req = test::TestRequest::get()
+        .uri("/api/v1/user")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+
+    repo.reactivate(id).await.unwrap();
+    assert_eq!(
+        test::call_service(&app, get("/api/v1/user")).await.status(),
+        200
+    );
+}
+
+#[actix_web::test]
+async fn request_metrics_bound_method_labels() {
+    let app = test::init_service(
+        App::new()
+            .wrap(RequestMetrics)
+            .route("/probe", web::to(HttpResponse::Ok)),
+    )
+    .await;
+    for method in ["GET", "PROPFIND", "X-RANDOM-1", "X-RANDOM-2"] {
+        let req = test::TestRequest::default()
+            .method(method.parse().unwrap())
+            .uri("/probe")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    // 표준이 아닌 메서드는 모두 OTHER 하나로 집계
+    let count = |method: &str| {
+        METRICS
+            .http_requests
+            .with_label_values(&[method, "/probe", "200"])
+            .get()
+    };
+    assert_eq!(count("GET"), 1);
+    assert_eq!(count("OTHER"), 3);
+    assert_eq!(count("PROPFIND"), 0);
+}
+
+#[actix_web::test]
+async fn rate_limit_buckets_refill() {
+    let routes = vec![RouteRateLimit {
+        path: "/api/v1/*".to_string(),
+        methods: vec!["GET".to_string()],
+        requests: 2,
+        window_seconds: 10,
+        key: RateLimitKey::Ip,
+    }];
+    assert!(route_for(&routes, "GET", "/api/v1/user").is_some());
+    assert!(route_for(&routes, "POST", "/api/v1/user").is_none());
+    assert!(route_for(&routes, "GET", "/metrics").is_none());
+
+    // 용량만큼 연속 허용 후 거부, 5초(토큰 1개)가 지나면 다시 허용
+    let limiter = RateLimiter::default();
+    let route = &routes[0];
+    let start = Instant::now();
+    assert!(limiter.check(route, "a", start).allowed);
+    let second = limiter.check(route, "a", start);
+    assert!(second.allowed);
+    assert_eq!(second.remaining, 0);
+    assert_eq!(second.reset_seconds, 10);
+    let denied = limiter.check(route, "a", start);
+    assert!(!denied.allowed);
+    assert_eq!(denied.retry_after_seconds, 5);
+    assert!(limiter.check(route, "b", start).allowed);
+    assert!(
+        limiter
+            .check(route, "a", start + Duration::from_secs(5))
+            .allowed
+    );
+    assert!(
+        !limiter
+            .check(route, "a", start + Duration::from_secs(5))
+            .allowed
+    );
+
+    // 한도만 바뀐 규칙은 소진된 버킷을 이어 쓰고, 경로가 다른 규칙은 별도 버킷 사용
+    let looser = RouteRateLimit {
+        requests: 5,
+        ..route.clone()
+    };
+    assert!(
+        !limiter
+            .check(&looser, "a", start + Duration::from_secs(5))
+            .allowed
+    );
+    let other = RouteRateLimit {
+        path: "/api/v1/user".to_string(),
+        ..looser
+    };
+    assert_eq!(limiter.check(&other, "a", start).remaining, 4);
+}
+
+#[actix_web::test]
+async fn rate_limit_rejects_with_retry_after() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let mut env = test_env(&[]);
+    env.rate_limit = RateLimitConfig {
+        routes: vec![
+            RouteRateLimit {
+                path: "/api/v1/auth/login".to_string(),
+                methods: vec!["POST".to_string()],
+                requests: 2,
+                window_seconds: 60,
+                key: RateLimitKey::Ip,
+            },
+            RouteRateLimit {
+                path: "/api/v1/*".to_string(),
+                methods: Vec::new(),
+                requests: 1,
+                window_seconds: 60,
+                key: RateLimitKey::Principal,
+            },
+        ],
+        ..Default::default()
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .app_data(web::Data::new(RateLimiter::default()))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(RateLimit)
+            .wrap(Authentication)
+            .wrap(RequestId)
+            .route("/api/v1/auth/login", web::post().to(HttpResponse::Ok))
+            .route("/api/v1/user", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+    let login = || {
+        test::TestRequest::post()
+            .uri("/api/v1/auth/login")
+            .peer_addr("10.0.0.1:40000".parse().unwrap())
+            .to_request()
+    };
+
+    let res = test::call_service(&app, login()).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "2");
+    assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "1");
+    assert_eq!(res.headers().get("ratelimit-policy").unwrap(), "2;w=60");
+    test::call_service(&app, login()).await;
+
+    // 429 응답도 AppError 형식 (요청 ID 포함)
+    let res = test::call_service(&app, login()).await;
+    assert_eq!(res.status(), 429);
+    assert_eq!(res.headers().get(header::RETRY_AFTER).unwrap(), "30");
+    assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+    let body: Value = test::read_body_json(res).await;
+    assert!(body["request_id"].is_string());
+
+    // 사용자별 버킷: 다른 사용자는 영향을 받지 않음
+    let user_type = create_user_type(repo.as_ref(), "Admin").await;
+    let (first, second) = (
+        create_user(repo.as_ref(), "first", user_type).await,
+        create_user(repo.as_ref(), "second", user_type).await,
+    );
+    let get_user = |user_id: i64| {
+        let token = create_jwt(user_id, 1, "user", 3600, &env).unwrap();
+        test::TestRequest::get()
+            .uri("/api/v1/user")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request()
+    };
+    assert_eq!(
+        test::call_service(&app, get_user(first)).await.status(),
+        200
+    );
+    assert_eq!(
+        test::call_service(&app, get_user(first)).await.status(),
+        429
+    );
+    assert_eq!(
+        test::call_service(&app, get_user(second)).await.status(),
+        200
+    );
+}
+
+#[actix_web::test]
+async fn rate_limit_reads_runtime_settings() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let mut env = test_env(&[]);
+    env.metrics.token = Some("metrics-secret".to_string());
+    let settings = web::Data::new(Settings::load(repo.as_ref(), &env).await.unwrap());
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(env.clone()))
+            .app_data(settings.clone())
+            .app_data(web::Data::new(RateLimiter::default()))
+            .configure(|cfg| repositories::register(cfg, repo.clone()))
+            .wrap(RateLimit)
+            .route("/metrics", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+    let metrics = |api_key: Option<&str>| {
+        let mut req = test::TestRequest::get()
+            .uri("/metrics")
+            .peer_addr("10.0.0.1:40000".parse().unwrap());
+        if let Some(key) = api_key {
+            req = req.insert_header(("x-api-key", key));
+        }
+        req.to_request()
+    };
+
+    // 기본 규칙에는 /metrics가 없으므로 제한 없음
+    let res = test::call_service(&app, metrics(None)).await;
+    assert_eq!(res.status(), 200);
+    assert!(res.headers().get("ratelimit-limit").is_none());
+
+    // 관리 API로 규칙을 바꾸면 재시작 없이 다음 요청부터 적용 (검증된 API 키별 버킷)
+    let invalid = settings
+        .update(
+            repo.as_ref(),
+            RATE_LIMIT_ROUTES.key,
+            json!([{ "path": "/metrics", "requests": 0, "window_seconds": 60, "key": "api_key" }]),
+            1,
+        )
+        .await;
+    assert!(matches!(invalid, Err(AppError::BadRequest(_))));
+    settings
+        .update(
+            repo.as_ref(),
+            RATE_LIMIT_ROUTES.key,
+            json!([{ "path": "/metrics", "methods": ["GET"], "requests": 1, "window_seconds": 60, "key": "api_key" }]),
+            1,
+        )
+        .await
+        .unwrap();
+    let res = test::call_service(&app, metrics(Some("metrics-secret"))).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "1");
+    assert_eq!(
+        test::call_service(&app, metrics(Some("metrics-secret")))
+            .await
+            .status(),
+        429
+    );
+    // 검증되지 않은 키는 바꿔 보내도 키가 없는 요청과 같은 클라이언트 IP 버킷
+    assert_eq!(
+        test::call_service(&app, metrics(Some("key-a")))
+            .await
+            .status(),
+        200
+    );
+    for key in [Some("key-b"), Some("key-c"), None] {
+        assert_eq!(test::call_service(&app, metrics(key)).await.status(), 429);
+    }
+
+    // 비활성화하면 제한과 헤더 모두 없음
+    settings
+        .update(repo.as_ref(), RATE_LIMIT_ENABLED.key, json!(false), 1)
+        .await
+        .unwrap();
+    let res = test::call_service(&app, metrics(Some("key-a"))).await;
+    assert_eq!(res.status(), 200);
+    assert!(res.headers().get("ratelimit-limit").is_none());
+}
+
+#[derive(serde::Deserialize)]
+struct Item {
+    #[allow(dead_code)]
+    name: String,
+}
+
+#[actix_web::test]
+async fn security_headers_and_extractor_errors() {
+    let config = SecurityConfig {
+        json_limit_bytes: 32,
+        ..Default::default()
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(extractors::json_config(config.json_limit_bytes))
+            .app_data(extractors::path_config())
+            .app_data(extractors::query_config())
+            .wrap(RequestId)
+            .wrap(SecurityHeaders::new(&config))
+            .route(
+                "/items/{id}",
+                web::post().to(|_: web::Path<i64>, _: web::Json<Item>| HttpResponse::Ok()),
+            )
+            .route(
+                "/items",
+                web::get()
+                    .to(|_: web::Query<std::collections::HashMap<String, i64>>| HttpResponse::Ok()),
+            )
+            .route("/swagger-ui/index.html", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+    let post = |uri: &str, body: &str| {
+        test::TestRequest::post()
+            .uri(uri)
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(body.to_string())
+            .to_request()
+    };
+
+    let res = test::call_service(&app, post("/items/1", r#"{"name":"a"}"#)).await;
+    assert_eq!(res.status(), 200);
+    let headers = res.headers();
+    assert_eq!(
+        headers.get(header::X_CONTENT_TYPE_OPTIONS).unwrap(),
+        "nosniff"
+    );
+    assert_eq!(headers.get(header::X_FRAME_OPTIONS).unwrap(), "DENY");
+    assert_eq!(headers.get(header::REFERRER_POLICY).unwrap(), "no-referrer");
+    assert_eq!(
+        headers.get(header::STRICT_TRANSPORT_SECURITY).unwrap(),
+        "max-age=31536000; includeSubDomains"
+    );
+    assert_eq!(
+        headers.get(header::CONTENT_SECURITY_POLICY).unwrap(),
+        "default-src 'none'; frame-ancestors 'none'"
+    );
+
+    // Swagger UI는 별도 CSP
+    let req = test::TestRequest::get()
+        .uri("/swagger-ui/index.html")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let csp = res.headers().get(header::CONTENT_SECURITY_POLICY).unwrap();
+    assert!(csp
+        .to_str()
+        .unwrap()
+        .contains("script-src 'self' 'unsafe-inline'"));
+
+    // 추출기 오류도 ErrorResponse 형식 (보안 헤더, 요청 ID 포함)
+    let cases = [
+        (post("/items/1", "{"), 400, "Invalid JSON body"),
+        (
+            post("/items/abc", r#"{"name":"a"}"#),
+            400,
+            "Invalid path parameter",
+        ),
+        (
+            post("/items/1", &format!(r#"{{"name":"{}"}}"#, "a".repeat(64))),
+            413,
+            "must not exceed 32 bytes",
+        ),
+        (
+            test::TestRequest::post()
+                .uri("/items/1")
+                .insert_header((header::CONTENT_TYPE, "text/plain"))
+                .set_payload("x")
+                .to_request(),
+            415,
+            "application/json",
+        ),
+        (
+            test::TestRequest::get().uri("/items?page=x").to_request(),
+            400,
+            "Invalid query string",
+        ),
+    ];
+    for (req, status, message) in cases {
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), status);
+        assert_eq!(res.headers().get(header::X_FRAME_OPTIONS).unwrap(), "DENY");
+        let body: Value = test::read_body_json(res).await;
+        assert_eq!(body["code"], status);
+        assert!(
+            body["message"].as_str().unwrap().contains(message),
+            "{}",
+            body
+        );
+        assert!(body["request_id"].is_string());
+    }
+}
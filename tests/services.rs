@@ -0,0 +1,1142 @@
+//! Exercises the service layer against `InMemoryRepository`, without a database or an
+//! actix-web runtime.
+
+mod common;
+
+use actix_web::web;
+use admin_server::{
+    config::env::{ConfigArgs, DormancyConfig, Env, SchedulerConfig},
+    dto::{
+        access_review::{
+            AccessReviewDecision, CreateAccessReviewRequest, DecideAccessReviewItemRequest,
+        },
+        auth::LoginRequest,
+        authz::{AuthzCheckRequest, AuthzDecision, AuthzRule, GrantSource},
+        common::ListQueryParams,
+        dormancy::DormancyStatus,
+        menu::CreateMenuRequest,
+        permission::{PermissionEffect, SetUserPermissionRequest},
+        user::{CreateUserRequest, ResetPasswordRequest},
+    },
+    errors::AppError,
+    middleware::auth::authenticated_user::AuthenticatedUser,
+    repositories::{
+        AccessReviewRepository, InMemoryRepository, JobRepository, PermissionRepository,
+        UserRepository,
+    },
+    services::{
+        self,
+        auth::LoginAttempts,
+        lifecycle::TaskSupervisor,
+        metrics::METRICS,
+        scheduler::{Job, JobSchedule, Scheduler, TRIGGER_MANUAL},
+        settings::{Settings, JWT_EXPIRES_IN_SECONDS, LOGIN_LOCKOUT_THRESHOLD},
+    },
+};
+use common::{create_user, create_user_type, test_env};
+use serde_json::json;
+use std::{
+    collections::HashSet,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+fn list_query(query: &str) -> ListQueryParams {
+    web::Query::<ListQueryParams>::from_query(query)
+        .unwrap()
+        .into_inner()
+}
+
+#[tokio::test]
+async fn user_and_user_type_rules() {
+    let repo = InMemoryRepository::new();
+    let admin = create_user_type(&repo, "Admin").await;
+    let viewer = create_user_type(&repo, "Viewer").await;
+
+    create_user(&repo, "carol", viewer).await;
+    create_user(&repo, "Alice", admin).await;
+    create_user(&repo, "bob", viewer).await;
+
+    // 사용자 이름 중복 / 존재하지 않는 사용자 종류
+    let duplicate = services::user::create_user(
+        &repo,
+        CreateUserRequest {
+            username: "bob".to_string(),
+            password: "password123".to_string(),
+            user_type_id: viewer,
+            is_active: None,
+        },
+    )
+    .await;
+    assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+    let missing_type = services::user::create_user(
+        &repo,
+        CreateUserRequest {
+            username: "dave".to_string(),
+            password: "password123".to_string(),
+            user_type_id: 999,
+            is_active: None,
+        },
+    )
+    .await;
+    assert!(matches!(missing_type, Err(AppError::BadRequest(_))));
+
+    // 검색은 대소문자를 구분하지 않고, 정렬/페이지네이션 적용
+    let users = services::user::get_user_array(&repo, &list_query("q=A"))
+        .await
+        .unwrap();
+    let names: Vec<_> = users.iter().map(|u| u.username.as_str()).collect();
+    assert_eq!(names, ["carol", "Alice"]);
+    let users =
+        services::user::get_user_array(&repo, &list_query("sort_by=username&order=desc&limit=2"))
+            .await
+            .unwrap();
+    let names: Vec<_> = users.iter().map(|u| u.username.as_str()).collect();
+    assert_eq!(names, ["carol", "bob"]);
+
+    // 사용 중인 사용자 종류는 삭제 불가, 비어 있으면 삭제 가능
+    let in_use = services::user_type::delete_user_type(&repo, &repo, viewer).await;
+    assert!(matches!(in_use, Err(AppError::Conflict(_))));
+    services::user::assign_user_type(&repo, "carol", admin)
+        .await
+        .unwrap();
+    services::user::assign_user_type(&repo, "bob", admin)
+        .await
+        .unwrap();
+    services::user_type::delete_user_type(&repo, &repo, viewer)
+        .await
+        .unwrap();
+    let deleted = services::user_type::get_user_type_by_id(&repo, viewer).await;
+    assert!(matches!(deleted, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn login_and_password_reset() {
+    let repo = InMemoryRepository::new();
+    let admin = create_user_type(&repo, "Admin").await;
+    let id = create_user(&repo, "alice", admin).await;
+    let env = test_env();
+    let settings = Settings::load(&repo, &env).await.unwrap();
+    let attempts = LoginAttempts::default();
+    let login = |password: &str| {
+        services::auth::login(
+            &repo,
+            &env,
+            &settings,
+            &attempts,
+            LoginRequest {
+                username: "alice".to_string(),
+                password: password.to_string(),
+            },
+        )
+    };
+
+    let wrong = login("wrong-password").await;
+    assert!(matches!(wrong, Err(AppError::Unauthorized(_))));
+    login("password123").await.unwrap();
+
+    services::user::reset_password(
+        &repo,
+        "alice",
+        ResetPasswordRequest {
+            password: "new-password".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    let old = login("password123").await;
+    assert!(matches!(old, Err(AppError::Unauthorized(_))));
+    login("new-password").await.unwrap();
+
+    let user = services::user::get_user_by_id(&repo, id).await.unwrap();
+    assert!(user.last_login_at.is_some());
+}
+
+#[tokio::test]
+async fn runtime_settings_and_lockout() {
+    let repo = InMemoryRepository::new();
+    let admin = create_user_type(&repo, "Admin").await;
+    let id = create_user(&repo, "alice", admin).await;
+    let env = test_env();
+    let settings = Settings::load(&repo, &env).await.unwrap();
+    let attempts = LoginAttempts::default();
+    let login = |password: &str| {
+        services::auth::login(
+            &repo,
+            &env,
+            &settings,
+            &attempts,
+            LoginRequest {
+                username: "alice".to_string(),
+                password: password.to_string(),
+            },
+        )
+    };
+
+    // 기본값은 설정 파일 값, 스키마에 맞지 않는 값은 거부
+    assert_eq!(settings.get(&JWT_EXPIRES_IN_SECONDS), 3600);
+    for invalid in [json!("7200"), json!(10), json!(1.5)] {
+        let result = settings
+            .update(&repo, JWT_EXPIRES_IN_SECONDS.key, invalid, id)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+    let unknown = settings.update(&repo, "no.such.key", json!(1), id).await;
+    assert!(matches!(unknown, Err(AppError::NotFound(_))));
+
+    // 변경 즉시 반영되고, 초기화하면 기본값으로 복귀
+    let updated = settings
+        .update(&repo, LOGIN_LOCKOUT_THRESHOLD.key, json!(2), id)
+        .await
+        .unwrap();
+    assert!(updated.overridden);
+    assert_eq!(updated.updated_by, Some(id));
+    assert_eq!(settings.get(&LOGIN_LOCKOUT_THRESHOLD), 2);
+
+    // 2회 실패 후 올바른 비밀번호로도 로그인 불가 (결과별 메트릭 기록)
+    let login_count = |result: &str| METRICS.logins.with_label_values(&[result]).get();
+    let (failures, lockouts) = (login_count("failure"), login_count("locked"));
+    for _ in 0..2 {
+        assert!(login("wrong-password").await.is_err());
+    }
+    let locked = login("password123").await;
+    assert!(
+        matches!(locked, Err(AppError::Unauthorized(ref m)) if m.contains("locked")),
+        "{:?}",
+        locked.err()
+    );
+    assert!(login_count("failure") >= failures + 2);
+    assert!(login_count("locked") > lockouts);
+
+    // 잠금 비활성화(0) 시 바로 로그인 가능
+    settings
+        .update(&repo, LOGIN_LOCKOUT_THRESHOLD.key, json!(0), id)
+        .await
+        .unwrap();
+    login("password123").await.unwrap();
+
+    let reset = settings
+        .reset(&repo, LOGIN_LOCKOUT_THRESHOLD.key)
+        .await
+        .unwrap();
+    assert!(!reset.overridden);
+    assert_eq!(settings.get(&LOGIN_LOCKOUT_THRESHOLD), 5);
+
+    // 다른 인스턴스가 저장한 값도 reload 후 반영
+    let other = Settings::load(&repo, &env).await.unwrap();
+    other
+        .update(&repo, JWT_EXPIRES_IN_SECONDS.key, json!(120), id)
+        .await
+        .unwrap();
+    settings.reload(&repo).await.unwrap();
+    assert_eq!(settings.get(&JWT_EXPIRES_IN_SECONDS), 120);
+}
+
+#[tokio::test]
+async fn runtime_settings_reload_periodically() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let env = test_env();
+    let settings = Arc::new(Settings::load(repo.as_ref(), &env).await.unwrap());
+    let other = Settings::load(repo.as_ref(), &env).await.unwrap();
+    let supervisor = TaskSupervisor::default();
+    services::settings::spawn_reload_job(&supervisor, settings.clone(), repo.clone(), 1);
+
+    // 다른 인스턴스의 변경이 다음 주기에 반영
+    other
+        .update(repo.as_ref(), LOGIN_LOCKOUT_THRESHOLD.key, json!(3), 1)
+        .await
+        .unwrap();
+    assert_eq!(settings.get(&LOGIN_LOCKOUT_THRESHOLD), 5);
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(settings.get(&LOGIN_LOCKOUT_THRESHOLD), 3);
+
+    supervisor.shutdown(Duration::from_secs(1)).await;
+}
+
+#[tokio::test]
+async fn permissions_and_menus() {
+    let repo = InMemoryRepository::new();
+    let admin = create_user_type(&repo, "Admin").await;
+    let permission = PermissionRepository::create(&repo, "user:read", None)
+        .await
+        .unwrap();
+    repo.grant_permission(admin, permission.id.unwrap());
+
+    let codes = repo.codes_for_user_type(admin).await.unwrap();
+    assert!(codes.contains("user:read"));
+
+    let menu = |name: &str, parent_id: Option<i64>| CreateMenuRequest {
+        name: name.to_string(),
+        path: format!("/{}", name),
+        icon: None,
+        parent_id,
+        display_order: None,
+        is_visible: None,
+    };
+    let root = services::menu::create_menu(&repo, menu("settings", None))
+        .await
+        .unwrap();
+    services::menu::create_menu(&repo, menu("users", Some(root.id)))
+        .await
+        .unwrap();
+    let orphan = services::menu::create_menu(&repo, menu("orphan", Some(999))).await;
+    assert!(matches!(orphan, Err(AppError::BadRequest(_))));
+
+    let tree = services::menu::get_menu_array(&repo).await.unwrap();
+    let count = tree
+        .iter()
+        .map(|m| 1 + m.children.as_deref().map_or(0, Vec::len))
+        .sum::<usize>();
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn supervisor_stops_background_tasks() {
+    let supervisor = TaskSupervisor::default();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let flag = stopped.clone();
+    supervisor.spawn("cooperative", move |mut shutdown| async move {
+        shutdown.recv().await;
+        flag.store(true, Ordering::SeqCst);
+    });
+    // 종료 신호를 무시하는 작업은 제한 시간 후 중단
+    let finished = Arc::new(AtomicBool::new(false));
+    let flag = finished.clone();
+    supervisor.spawn("stuck", move |_| async move {
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        flag.store(true, Ordering::SeqCst);
+    });
+
+    assert!(!supervisor.is_shutting_down());
+    let signal = supervisor.signal();
+    assert!(!signal.is_set());
+    supervisor.shutdown(Duration::from_millis(100)).await;
+
+    assert!(supervisor.is_shutting_down());
+    assert!(signal.is_set());
+    assert!(stopped.load(Ordering::SeqCst));
+    assert!(!finished.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn scheduler_locks_and_records_runs() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let jobs = || {
+        let counter = counter.clone();
+        vec![
+            Job::new("count", "Counts its runs", move || {
+                let counter = counter.clone();
+                async move {
+                    let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    Ok(format!("run {}", n))
+                }
+            })
+            .with_schedule(Some(JobSchedule::Every(Duration::from_secs(3600)))),
+            Job::new("broken", "Always fails", || async {
+                Err(AppError::bad_request("nothing to do"))
+            }),
+        ]
+    };
+
+    // 설정의 일정은 작업 기본 일정보다 우선하고, 없는 작업 이름은 거부
+    let config = SchedulerConfig {
+        schedules: [("broken".to_string(), "0 0 4 * * *".to_string())].into(),
+        ..Default::default()
+    };
+    let unknown = Scheduler::new(repo.clone(), &SchedulerConfig::default(), jobs());
+    assert!(unknown.is_err());
+    let scheduler = Arc::new(Scheduler::new(repo.clone(), &config, jobs()).unwrap());
+    let listed = scheduler.list().await.unwrap();
+    assert_eq!(listed[0].schedule.as_deref(), Some("every 3600s"));
+    assert_eq!(listed[1].schedule.as_deref(), Some("0 0 4 * * *"));
+    assert!(listed
+        .iter()
+        .all(|j| j.next_run_at.is_some() && j.last_run.is_none()));
+    // 고정 간격 일정은 인스턴스와 무관하게 같은 시각(간격의 배수)으로 정렬
+    assert_eq!(listed[0].next_run_at.unwrap().timestamp() % 3600, 0);
+
+    // 실행 결과와 메시지를 이력에 기록
+    let run = scheduler
+        .run_now("count", TRIGGER_MANUAL, Some(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        (run.status.as_str(), run.message.as_deref()),
+        ("succeeded", Some("run 1"))
+    );
+    assert_eq!(run.instance_id, scheduler.instance_id());
+    assert!(run.finished_at.is_some());
+    let failed = scheduler
+        .run_now("broken", TRIGGER_MANUAL, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(failed.status, "failed");
+    assert_eq!(
+        failed.message.as_deref(),
+        Some("Bad request: nothing to do")
+    );
+    assert!(matches!(
+        scheduler.runs("missing", None).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    // 다른 인스턴스가 잠금을 가진 동안에는 실행하지 않음
+    let now = chrono::Utc::now().naive_utc();
+    let until = now + chrono::Duration::minutes(5);
+    assert!(repo
+        .try_lock("count", "other", now, until, None)
+        .await
+        .unwrap());
+    assert!(scheduler
+        .run_now("count", TRIGGER_MANUAL, None)
+        .await
+        .unwrap()
+        .is_none());
+    let supervisor = TaskSupervisor::default();
+    assert!(matches!(
+        scheduler.trigger(&supervisor, "count", 1).await,
+        Err(AppError::Conflict(_))
+    ));
+    repo.unlock("count", "other").await.unwrap();
+
+    // 수동 실행은 running 기록을 먼저 반환하고 백그라운드에서 완료
+    let started = scheduler.trigger(&supervisor, "count", 1).await.unwrap();
+    assert_eq!(
+        (started.status.as_str(), started.trigger_type.as_str()),
+        ("running", "manual")
+    );
+    supervisor.shutdown(Duration::from_secs(5)).await;
+    let runs = scheduler.runs("count", None).await.unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].id, started.id);
+    assert_eq!(runs[0].status, "succeeded");
+    assert_eq!(runs[0].started_by, Some(1));
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    // 실행이 끝나면 잠금 해제
+    assert!(repo
+        .try_lock("count", "other", now, until, None)
+        .await
+        .unwrap());
+    repo.unlock("count", "other").await.unwrap();
+
+    // 예약 실행은 이미 처리된 slot을 다시 실행하지 않음
+    let slot = chrono::Utc::now();
+    assert!(scheduler
+        .run_scheduled("count", slot)
+        .await
+        .unwrap()
+        .is_some());
+    assert!(scheduler
+        .run_scheduled("count", slot)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn scheduler_renews_lock_while_running() {
+    let repo = Arc::new(InMemoryRepository::new());
+    let jobs = vec![
+        Job::new("slow", "Runs longer than the lock TTL", || async {
+            tokio::time::sleep(Duration::from_millis(2500)).await;
+            Ok("done".to_string())
+        }),
+        Job::new("stuck", "Never finishes", || async {
+            std::future::pending::<()>().await;
+            Ok("unreachable".to_string())
+        }),
+    ];
+    let config = SchedulerConfig {
+        lock_ttl_seconds: 1,
+        schedules: Default::default(),
+        ..Default::default()
+    };
+    let scheduler = Arc::new(Scheduler::new(repo.clone(), &config, jobs).unwrap());
+    let other_instance = |name: &'static str| {
+        let repo = repo.clone();
+        async move {
+            let now = chrono::Utc::now().naive_utc();
+            repo.try_lock(name, "other", now, now + chrono::Duration::minutes(5), None)
+                .await
+                .unwrap()
+        }
+    };
+
+    // lock_ttl보다 오래 걸리는 실행 중에도 잠금이 연장되어 다른 인스턴스가 가져가지 못함
+    let running = tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_now("slow", TRIGGER_MANUAL, None).await }
+    });
+    tokio::time::sleep(Duration::from_millis(1600)).await;
+    assert!(!other_instance("slow").await);
+    let run = running.await.unwrap().unwrap().unwrap();
+    assert_eq!(run.status, "succeeded");
+    assert!(other_instance("slow").await);
+
+    // 잠금을 잃은 실행은 중단되고 실패로 기록
+    let running = tokio::spawn({
+        let scheduler = scheduler.clone();
+        async move { scheduler.run_now("stuck", TRIGGER_MANUAL, None).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    repo.unlock("stuck", scheduler.instance_id()).await.unwrap();
+    assert!(other_instance("stuck").await);
+    let run = tokio::time::timeout(Duration::from_secs(3), running)
+        .await
+        .expect("the run should stop once its lock is lost")
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(run.status, "failed");
+    assert!(
+        run.message.as_deref().unwrap().contains("lost its lock"),
+        "{:?}",
+        run.message
+    );
+    // 다른 인스턴스의 잠금은 그대로 유지
+    assert!(!other_instance("stuck").await);
+}
+
+#[tokio::test]
+async fn dormancy_is_scheduled_by_default() {
+    // DORMANCY_ENABLED만 설정해도 기본 일정으로 실행
+    let env = Env::from_sources(
+        &ConfigArgs {
+            config_file: None,
+            profile: "default".to_string(),
+            overrides: Vec::new(),
+        },
+        [("DORMANCY_ENABLED".to_string(), "true".to_string())],
+    )
+    .unwrap();
+    let repo = Arc::new(InMemoryRepository::new());
+    let jobs = vec![
+        services::scheduler::prune_job_runs_job(repo.clone(), env.scheduler.history_retention_days),
+        services::scheduler::prune_audit_history_job(
+            repo.clone(),
+            repo.clone(),
+            env.scheduler.audit_retention_days,
+        ),
+        services::dormancy::dormancy_job(repo.clone(), repo.clone(), env.dormancy.clone()),
+    ];
+    let scheduler = Scheduler::new(repo.clone(), &env.scheduler, jobs).unwrap();
+    let job = scheduler
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|job| job.name == "deactivate_dormant_accounts")
+        .unwrap();
+    assert_eq!(job.schedule.as_deref(), Some("0 0 4 * * *"));
+
+    let run = scheduler
+        .run_scheduled(job.name.as_str(), job.next_run_at.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        (run.status.as_str(), run.message.as_deref()),
+        (
+            "succeeded",
+            Some("Warned 0 accounts, deactivated 0 accounts")
+        )
+    );
+
+    // 감사 이력은 기본적으로 영구 보관
+    let job = scheduler
+        .list()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|job| job.name == "prune_audit_history")
+        .unwrap();
+    let run = scheduler
+        .run_scheduled(job.name.as_str(), job.next_run_at.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        run.message.as_deref(),
+        Some("Audit history is retained indefinitely")
+    );
+}
+
+#[tokio::test]
+async fn dormant_accounts_are_warned_then_deactivated() {
+    let repo = InMemoryRepository::new();
+    let super_admin = create_user_type(&repo, "SuperAdmin").await;
+    let editor = create_user_type(&repo, "Editor").await;
+    let root = create_user(&repo, "root", super_admin).await;
+    let alice = create_user(&repo, "alice", editor).await;
+    let bob = create_user(&repo, "bob", editor).await;
+    create_user(&repo, "svc-sync", editor).await;
+    let config = DormancyConfig {
+        enabled: true,
+        exempt_usernames: vec!["svc-sync".to_string()],
+        ..Default::default()
+    };
+    let days = |n: i64| chrono::Utc::now().naive_utc() + chrono::Duration::days(n);
+
+    // 경고 기간 전에는 대상 없음, 이후에는 제외 계정을 뺀 모든 계정이 경고 대상
+    let report = services::dormancy::report(&repo, &config, days(30))
+        .await
+        .unwrap();
+    assert!(report.accounts.is_empty());
+    let report = services::dormancy::report(&repo, &config, days(70))
+        .await
+        .unwrap();
+    let names: Vec<_> = report
+        .accounts
+        .iter()
+        .map(|a| a.username.as_str())
+        .collect();
+    assert_eq!(names.len(), 3);
+    assert!(!names.contains(&"svc-sync"));
+    assert!(report
+        .accounts
+        .iter()
+        .all(|a| a.status == DormancyStatus::Warning && a.inactive_days == 70));
+
+    // 경고는 한 번만 기록하고, 로그인하면 초기화
+    let outcome = services::dormancy::enforce(&repo, &repo, &config, days(70))
+        .await
+        .unwrap();
+    assert_eq!(outcome.warned.len(), 3);
+    let outcome = services::dormancy::enforce(&repo, &repo, &config, days(71))
+        .await
+        .unwrap();
+    assert!(outcome.warned.is_empty());
+    repo.touch_last_login(bob).await.unwrap();
+    let bob_user = UserRepository::find_by_id(&repo, bob)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(bob_user.dormancy_warned_at.is_none());
+
+    // 비활성화 기간이 지나면 사유를 기록하고 비활성화 (마지막 SuperAdmin은 유지),
+    // 경고가 초기화된 계정은 먼저 다시 경고
+    let outcome = services::dormancy::enforce(&repo, &repo, &config, days(95))
+        .await
+        .unwrap();
+    assert_eq!(outcome.deactivated, ["alice"]);
+    assert_eq!(outcome.warned, ["bob"]);
+    assert_eq!(outcome.kept, ["root"]);
+
+    // 경고가 유예 기간보다 최근이면 비활성화를 미루고, 유예 기간이 지나면 비활성화
+    let strict = DormancyConfig {
+        warning_grace_days: 200,
+        ..config.clone()
+    };
+    let report = services::dormancy::report(&repo, &strict, days(95))
+        .await
+        .unwrap();
+    let bob_account = report
+        .accounts
+        .iter()
+        .find(|a| a.username == "bob")
+        .unwrap();
+    assert_eq!(bob_account.status, DormancyStatus::DeactivationDue);
+    assert!(bob_account.deactivates_at > chrono::Utc::now() + chrono::Duration::days(199));
+    let outcome = services::dormancy::enforce(&repo, &repo, &strict, days(95))
+        .await
+        .unwrap();
+    assert!(outcome.deactivated.is_empty() && outcome.warned.is_empty());
+    let outcome = services::dormancy::enforce(&repo, &repo, &config, days(95))
+        .await
+        .unwrap();
+    assert_eq!(outcome.deactivated, ["bob"]);
+    let alice_user = UserRepository::find_by_id(&repo, alice)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!alice_user.is_active);
+    assert!(alice_user
+        .deactivated_reason
+        .as_deref()
+        .unwrap()
+        .starts_with("Dormant: no login for 95 days"));
+    assert!(
+        UserRepository::find_by_id(&repo, root)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_active
+    );
+
+    // 재활성화하면 사유가 지워지고 미사용 기간을 다시 계산
+    let reactivated = services::dormancy::reactivate(&repo, alice).await.unwrap();
+    assert!(reactivated.is_active && reactivated.deactivated_reason.is_none());
+    assert_eq!(
+        services::dormancy::last_activity(&reactivated),
+        reactivated.reactivated_at.unwrap()
+    );
+    assert!(matches!(
+        services::dormancy::reactivate(&repo, alice).await,
+        Err(AppError::Conflict(_))
+    ));
+    assert!(matches!(
+        services::dormancy::reactivate(&repo, 999).await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn access_review_snapshots_and_revokes_on_close() {
+    let repo = InMemoryRepository::new();
+    let auditor = create_user_type(&repo, "Auditor").await;
+    let editor = create_user_type(&repo, "Editor").await;
+    let viewer = create_user_type(&repo, "Viewer").await;
+    let root_type = create_user_type(&repo, "Root").await;
+    let permission = PermissionRepository::create(&repo, "menu:write", None)
+        .await
+        .unwrap();
+    repo.grant_permission(editor, permission.id.unwrap());
+    let wildcard = PermissionRepository::create(&repo, "*", None)
+        .await
+        .unwrap();
+    repo.grant_permission(root_type, wildcard.id.unwrap());
+    let reviewer_id = create_user(&repo, "reviewer", auditor).await;
+    let alice = create_user(&repo, "alice", editor).await;
+    let bob = create_user(&repo, "bob", editor).await;
+    let dave = create_user(&repo, "dave", editor).await;
+    create_user(&repo, "carol", viewer).await;
+    create_user(&repo, "root", root_type).await;
+    let reviewer = AuthenticatedUser {
+        id: reviewer_id,
+        user_type_id: auditor,
+        username: "reviewer".to_string(),
+        permissions: Rc::new(HashSet::from([
+            services::access_review::ACCESS_REVIEW_PERMISSION.to_string(),
+        ])),
+    };
+    services::access_review::ensure_reviewer(&reviewer).unwrap();
+
+    // 사용자별 예외: bob은 menu:write 거부, report:export 추가 허용
+    PermissionRepository::create(&repo, "report:export", None)
+        .await
+        .unwrap();
+    let admin = AuthenticatedUser {
+        permissions: Rc::new(HashSet::from(["*".to_string()])),
+        ..reviewer.clone()
+    };
+    for (code, effect) in [
+        ("menu:write", PermissionEffect::Deny),
+        ("report:export", PermissionEffect::Allow),
+    ] {
+        services::permission::set_user_permission(
+            &repo,
+            &repo,
+            &admin,
+            bob,
+            code,
+            SetUserPermissionRequest {
+                effect,
+                reason: None,
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // 선택한 사용자 종류의 활성 사용자만 사용자별 예외를 반영한 권한과 함께 스냅샷
+    let review = services::access_review::start_review(
+        &repo,
+        &repo,
+        &repo,
+        &repo,
+        &reviewer,
+        CreateAccessReviewRequest {
+            name: "Q4".to_string(),
+            user_type_ids: vec![editor, auditor, root_type],
+            due_at: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(review.review.user_types, ["Editor", "Auditor", "Root"]);
+    let names: Vec<_> = review.items.iter().map(|i| i.username.as_str()).collect();
+    assert_eq!(names, ["alice", "bob", "dave", "reviewer", "root"]);
+    assert_eq!(review.items[0].permissions, ["menu:write"]);
+    assert_eq!(
+        review.items[1].permissions,
+        ["!menu:write", "report:export"]
+    );
+    assert_eq!(review.review.pending, 5);
+
+    let id = review.review.id;
+    let item = |name: &str| review.items.iter().find(|i| i.username == name).unwrap().id;
+    let decide = |item_id, decision| {
+        services::access_review::decide(
+            &repo,
+            &reviewer,
+            id,
+            item_id,
+            DecideAccessReviewItemRequest {
+                decision,
+                comment: None,
+            },
+        )
+    };
+
+    // 자기 자신은 검토할 수 없고, 미결 항목이 있으면 종료 불가
+    assert!(matches!(
+        decide(item("reviewer"), AccessReviewDecision::Keep).await,
+        Err(AppError::Forbidden(_))
+    ));
+    decide(item("alice"), AccessReviewDecision::Keep)
+        .await
+        .unwrap();
+    decide(item("bob"), AccessReviewDecision::Revoke)
+        .await
+        .unwrap();
+    decide(item("dave"), AccessReviewDecision::Revoke)
+        .await
+        .unwrap();
+    // 전체 권한은 사용자별 거부로 회수할 수 없으므로 유지만 가능
+    assert!(matches!(
+        decide(item("root"), AccessReviewDecision::Revoke).await,
+        Err(AppError::BadRequest(_))
+    ));
+    decide(item("root"), AccessReviewDecision::Keep)
+        .await
+        .unwrap();
+    assert!(matches!(
+        services::access_review::close_review(&repo, &repo, &repo, &reviewer, id).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    let other = AuthenticatedUser {
+        id: alice,
+        username: "alice".to_string(),
+        ..reviewer.clone()
+    };
+    services::access_review::decide(
+        &repo,
+        &other,
+        id,
+        item("reviewer"),
+        DecideAccessReviewItemRequest {
+            decision: AccessReviewDecision::Keep,
+            comment: Some("Owns the audit".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+
+    // 회수를 적용하던 중 실패한 종료 요청: 캠페인은 열린 채로 남아 다시 종료할 수 있고,
+    // 이미 적용된 회수 결정은 바꿀 수 없음
+    repo.mark_item_applied(item("bob")).await.unwrap();
+    let interrupted = services::access_review::get_review(&repo, id)
+        .await
+        .unwrap();
+    assert_eq!(interrupted.review.status, "open");
+    assert!(matches!(
+        decide(item("bob"), AccessReviewDecision::Keep).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    // 종료 시 회수 대상의 검토된 권한을 사용자별 거부로 막고 이력과 적용 시각 기록
+    let closed = services::access_review::close_review(&repo, &repo, &repo, &reviewer, id)
+        .await
+        .unwrap();
+    assert_eq!(closed.review.status, "closed");
+    assert_eq!((closed.review.kept, closed.review.revoked), (3, 2));
+    assert!(closed
+        .items
+        .iter()
+        .filter(|i| i.decision == "revoke")
+        .all(|i| i.applied_at.is_some()));
+    let overrides = repo.user_overrides(dave).await.unwrap();
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        (
+            overrides[0].permission_code.as_str(),
+            overrides[0].effect.as_str()
+        ),
+        ("menu:write", "deny")
+    );
+    assert!(overrides[0]
+        .reason
+        .as_deref()
+        .unwrap()
+        .starts_with("Access review `Q4`"));
+    let history = repo.user_override_history(dave).await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].changed_by, "reviewer");
+    for user_id in [alice, dave] {
+        assert!(
+            UserRepository::find_by_id(&repo, user_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .is_active
+        );
+    }
+    assert!(repo.user_overrides(alice).await.unwrap().is_empty());
+    assert!(matches!(
+        decide(item("alice"), AccessReviewDecision::Revoke).await,
+        Err(AppError::Conflict(_))
+    ));
+}
+
+#[tokio::test]
+async fn effective_permissions_and_authz_check_explain_grants() {
+    let repo = InMemoryRepository::new();
+    let super_admin = create_user_type(&repo, "SuperAdmin").await;
+    let editor = create_user_type(&repo, "Editor").await;
+    let wildcard = PermissionRepository::create(&repo, "*", None)
+        .await
+        .unwrap();
+    let menu_write = PermissionRepository::create(&repo, "menu:write", None)
+        .await
+        .unwrap();
+    PermissionRepository::create(&repo, "user:delete", None)
+        .await
+        .unwrap();
+    repo.grant_permission(super_admin, wildcard.id.unwrap());
+    repo.grant_permission(editor, menu_write.id.unwrap());
+    let root = create_user(&repo, "root", super_admin).await;
+    let jane = create_user(&repo, "jane", editor).await;
+
+    // "*"는 자신과 정의된 모든 권한으로 펼쳐서 출처를 표시
+    let effective = services::authz::effective_permissions(&repo, &repo, &repo, root)
+        .await
+        .unwrap();
+    let codes: Vec<_> = effective
+        .permissions
+        .iter()
+        .map(|p| p.code.as_str())
+        .collect();
+    assert_eq!(codes, ["*", "menu:write", "user:delete"]);
+    assert_eq!(effective.permissions[0].grants[0].source, GrantSource::Role);
+    assert_eq!(
+        effective.permissions[1].grants[0].source,
+        GrantSource::Wildcard
+    );
+
+    let check = |user_id, permission: &str| {
+        services::authz::check(
+            &repo,
+            &repo,
+            &repo,
+            AuthzCheckRequest {
+                user_id,
+                permission: permission.to_string(),
+            },
+        )
+    };
+    let allowed = check(jane, "menu:write").await.unwrap();
+    assert_eq!(
+        (allowed.decision, allowed.rule),
+        (AuthzDecision::Allow, AuthzRule::Role)
+    );
+    let denied = check(jane, "user:delete").await.unwrap();
+    assert_eq!(
+        (denied.decision, denied.rule),
+        (AuthzDecision::Deny, AuthzRule::NoGrant)
+    );
+    assert!(denied.explanation.contains("`Editor`"));
+    let wildcard = check(root, "report:read").await.unwrap();
+    assert_eq!(
+        (wildcard.decision, wildcard.rule),
+        (AuthzDecision::Allow, AuthzRule::Wildcard)
+    );
+    repo.deactivate(jane, "test").await.unwrap();
+    let inactive = check(jane, "menu:write").await.unwrap();
+    assert_eq!(
+        (inactive.decision, inactive.rule),
+        (AuthzDecision::Deny, AuthzRule::InactiveAccount)
+    );
+    assert!(matches!(
+        check(999, "menu:write").await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+// 인증 미들웨어와 같은 방식으로 계산한 권한 집합
+async fn merged_permissions(
+    repo: &InMemoryRepository,
+    user_id: i64,
+    user_type_id: i64,
+) -> HashSet<String> {
+    let codes = repo.codes_for_user_type(user_type_id).await.unwrap();
+    let overrides = repo.user_overrides(user_id).await.unwrap();
+    services::authz::merge_permissions(codes, &overrides, chrono::Utc::now().naive_utc())
+}
+
+#[tokio::test]
+async fn user_permission_overrides_are_merged_and_audited() {
+    let repo = InMemoryRepository::new();
+    let super_admin = create_user_type(&repo, "SuperAdmin").await;
+    let editor = create_user_type(&repo, "Editor").await;
+    let wildcard = PermissionRepository::create(&repo, "*", None)
+        .await
+        .unwrap();
+    let menu_write = PermissionRepository::create(&repo, "menu:write", None)
+        .await
+        .unwrap();
+    PermissionRepository::create(&repo, "report:export", None)
+        .await
+        .unwrap();
+    repo.grant_permission(super_admin, wildcard.id.unwrap());
+    repo.grant_permission(editor, menu_write.id.unwrap());
+    let root = create_user(&repo, "root", super_admin).await;
+    let jane = create_user(&repo, "jane", editor).await;
+    let admin = AuthenticatedUser {
+        id: root,
+        user_type_id: super_admin,
+        username: "root".to_string(),
+        permissions: Rc::new(HashSet::from(["*".to_string()])),
+    };
+    let set = |user_id, code: &'static str, effect, expires_at| {
+        services::permission::set_user_permission(
+            &repo,
+            &repo,
+            &admin,
+            user_id,
+            code,
+            SetUserPermissionRequest {
+                effect,
+                reason: Some("test".to_string()),
+                expires_at,
+            },
+        )
+    };
+
+    // 허용 예외는 사용자 종류에 없는 권한을 추가하고, 다시 설정하면 덮어씀
+    set(jane, "report:export", PermissionEffect::Allow, None)
+        .await
+        .unwrap();
+    let permissions = merged_permissions(&repo, jane, editor).await;
+    assert!(services::authz::is_granted(&permissions, "report:export"));
+    assert!(services::authz::is_granted(&permissions, "menu:write"));
+
+    // 거부 예외는 사용자 종류와 "*"보다 우선
+    set(jane, "menu:write", PermissionEffect::Deny, None)
+        .await
+        .unwrap();
+    set(root, "report:export", PermissionEffect::Deny, None)
+        .await
+        .unwrap();
+    assert!(!services::authz::is_granted(
+        &merged_permissions(&repo, jane, editor).await,
+        "menu:write"
+    ));
+    let root_permissions = merged_permissions(&repo, root, super_admin).await;
+    assert!(!services::authz::is_granted(
+        &root_permissions,
+        "report:export"
+    ));
+    assert!(services::authz::is_granted(
+        &root_permissions,
+        "user:delete"
+    ));
+    assert!(services::authz::is_super_admin(&root_permissions));
+
+    // 전체 권한("*")은 사용자별 예외로 부여하거나 거부할 수 없음
+    for effect in [PermissionEffect::Allow, PermissionEffect::Deny] {
+        assert!(matches!(
+            set(jane, "*", effect, None).await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+    assert!(!services::authz::is_super_admin(
+        &merged_permissions(&repo, jane, editor).await
+    ));
+    assert!(!services::authz::is_super_admin(&HashSet::from([
+        "*".to_string(),
+        "!*".to_string()
+    ])));
+    let check = services::authz::check(
+        &repo,
+        &repo,
+        &repo,
+        AuthzCheckRequest {
+            user_id: jane,
+            permission: "menu:write".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        (check.decision, check.rule),
+        (AuthzDecision::Deny, AuthzRule::DirectDeny)
+    );
+    let effective = services::authz::effective_permissions(&repo, &repo, &repo, jane)
+        .await
+        .unwrap();
+    let codes: Vec<_> = effective
+        .permissions
+        .iter()
+        .map(|p| p.code.as_str())
+        .collect();
+    assert_eq!(codes, ["report:export"]);
+    assert_eq!(
+        effective.permissions[0].grants[0].source,
+        GrantSource::Direct
+    );
+    assert_eq!(effective.denied[0].code, "menu:write");
+
+    // 만료된 예외는 무시하고, 과거 만료 시각은 거부
+    let past = chrono::Utc::now() - chrono::Duration::seconds(1);
+    assert!(matches!(
+        set(jane, "menu:write", PermissionEffect::Deny, Some(past)).await,
+        Err(AppError::BadRequest(_))
+    ));
+    let soon = chrono::Utc::now() + chrono::Duration::seconds(1);
+    set(jane, "menu:write", PermissionEffect::Deny, Some(soon))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert!(services::authz::is_granted(
+        &merged_permissions(&repo, jane, editor).await,
+        "menu:write"
+    ));
+    let listed = services::permission::list_user_permissions(&repo, &repo, jane)
+        .await
+        .unwrap();
+    assert!(listed
+        .iter()
+        .any(|p| p.permission_code == "menu:write" && p.expired));
+
+    // 모든 변경은 이력에 남음
+    services::permission::remove_user_permission(&repo, &repo, &admin, jane, "report:export")
+        .await
+        .unwrap();
+    assert!(matches!(
+        services::permission::remove_user_permission(&repo, &repo, &admin, jane, "report:export")
+            .await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(matches!(
+        set(jane, "no:such", PermissionEffect::Allow, None).await,
+        Err(AppError::NotFound(_))
+    ));
+    let history = services::permission::user_permission_history(&repo, &repo, jane)
+        .await
+        .unwrap();
+    let actions: Vec<_> = history
+        .iter()
+        .map(|a| (a.action.as_str(), a.permission_code.as_str()))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            ("remove", "report:export"),
+            ("set", "menu:write"),
+            ("set", "menu:write"),
+            ("set", "report:export"),
+        ]
+    );
+    assert!(history.iter().all(|a| a.changed_by == "root"));
+}
@@ -0,0 +1,127 @@
+//! OTLP trace export (`otel` feature) against an in-process collector:
+//! `cargo test --features otel --test telemetry`.
+#![cfg(feature = "otel")]
+
+use actix_web::{test, web, App, HttpResponse};
+use admin_server::{
+    config::env::TelemetryConfig, middleware::request_id::RequestId, services::telemetry, util,
+};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+};
+use tracing_subscriber::{layer::SubscriberExt, EnvFilter};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+// OTLP/HTTP 요청 본문(protobuf)을 그대로 모아 두는 최소 수집기
+fn start_collector() -> (String, Arc<Mutex<Vec<u8>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let sink = sink.clone();
+            std::thread::spawn(move || serve(stream.unwrap(), sink));
+        }
+    });
+    (endpoint, received)
+}
+
+// keep-alive 연결에서 요청을 반복해서 읽고 빈 200 응답을 보냄
+fn serve(mut stream: TcpStream, sink: Arc<Mutex<Vec<u8>>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut content_length = None;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+        let mut body = vec![0; content_length.unwrap_or(0)];
+        reader.read_exact(&mut body).unwrap();
+        sink.lock().unwrap().extend_from_slice(&body);
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+            .unwrap();
+    }
+}
+
+// protobuf는 문자열/바이트 필드를 그대로 담으므로 디코딩 없이 포함 여부만 확인
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn hex_decode(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn exports_request_query_and_bcrypt_spans() {
+    let (endpoint, received) = start_collector();
+    let provider = telemetry::init_tracer_provider(&TelemetryConfig {
+        otlp_endpoint: Some(endpoint),
+        service_name: "admin_server_test".to_string(),
+        ..Default::default()
+    })
+    .unwrap()
+    .unwrap();
+    let subscriber =
+        tracing_subscriber::registry().with(telemetry::layer(&provider, EnvFilter::new("info")));
+    // SQLite 쿼리 이벤트는 sqlx 작업 스레드에서 기록되므로 전역으로 설정
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
+    #[cfg(feature = "sqlite")]
+    let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+    let app = test::init_service(App::new().wrap(RequestId).route(
+        "/hash",
+        web::post().to(move || {
+            #[cfg(feature = "sqlite")]
+            let pool = pool.clone();
+            async move {
+                #[cfg(feature = "sqlite")]
+                sqlx::query("SELECT 1").execute(&pool).await.unwrap();
+                util::hash_password("password123").await.unwrap();
+                HttpResponse::Ok().finish()
+            }
+        }),
+    ))
+    .await;
+
+    // 호출한 서비스의 추적 컨텍스트를 이어받음
+    let req = test::TestRequest::post()
+        .uri("/hash")
+        .insert_header((
+            "traceparent",
+            format!("00-{}-00f067aa0ba902b7-01", TRACE_ID),
+        ))
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+    provider.force_flush().unwrap();
+
+    let body = received.lock().unwrap().clone();
+    assert!(contains(&body, b"admin_server_test"));
+    assert!(contains(&body, b"POST /hash"));
+    assert!(contains(&body, b"bcrypt.hash"));
+    #[cfg(feature = "sqlite")]
+    assert!(contains(&body, b"SELECT"));
+    assert!(contains(&body, &hex_decode(TRACE_ID)));
+
+    provider.shutdown().unwrap();
+}
@@ -0,0 +1,180 @@
+//! TLS termination: certificate loading and hot reload, ALPN (HTTP/2) negotiation against a
+//! real listener, and the HTTP to HTTPS redirect. Uses the `localhost` certificates in
+//! `tests/fixtures/tls`, signed by the test CA in `ca.crt`.
+
+use actix_web::{http::header, test, web, App, HttpResponse, HttpServer};
+use admin_server::{
+    config::env::TlsConfig, handlers::https_redirect, services::tls::CertificateStore,
+};
+use rustls::{
+    crypto::ring,
+    pki_types::{pem::PemObject, CertificateDer},
+    ClientConfig, ClientConnection, RootCertStore,
+};
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+const FIXTURES: &str = "tests/fixtures/tls";
+
+fn fixture_cert(name: &str) -> CertificateDer<'static> {
+    CertificateDer::from_pem_file(Path::new(FIXTURES).join(format!("{}.crt", name))).unwrap()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("admin_server_tls_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// 인증서/키를 복사하고 수정 시각을 `age_seconds` 만큼 앞당김 (같은 초에 여러 번 바꿔도 구분되도록)
+fn install(dir: &Path, cert: &str, key: &str, age_seconds: u64) {
+    for (source, target) in [
+        (format!("{}.crt", cert), "server.crt"),
+        (format!("{}.key", key), "server.key"),
+    ] {
+        let target = dir.join(target);
+        fs::copy(Path::new(FIXTURES).join(source), &target).unwrap();
+        File::options()
+            .write(true)
+            .open(&target)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(age_seconds))
+            .unwrap();
+    }
+}
+
+fn tls_config(dir: &Path) -> TlsConfig {
+    TlsConfig {
+        cert_path: Some(dir.join("server.crt").to_string_lossy().into_owned()),
+        key_path: Some(dir.join("server.key").to_string_lossy().into_owned()),
+        ..Default::default()
+    }
+}
+
+// 테스트 CA를 신뢰하는 클라이언트로 핸드셰이크한 연결
+fn handshake(addr: SocketAddr, alpn: &[&[u8]]) -> (ClientConnection, TcpStream) {
+    let mut roots = RootCertStore::empty();
+    roots.add(fixture_cert("ca")).unwrap();
+    let mut config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = alpn.iter().map(|p| p.to_vec()).collect();
+
+    let mut conn =
+        ClientConnection::new(Arc::new(config), "localhost".try_into().unwrap()).unwrap();
+    let mut sock = TcpStream::connect(addr).unwrap();
+    while conn.is_handshaking() {
+        conn.complete_io(&mut sock).unwrap();
+    }
+    (conn, sock)
+}
+
+#[actix_web::test]
+async fn certificates_reload_when_files_change() {
+    let dir = temp_dir("reload");
+    install(&dir, "first", "first", 60);
+    let store = CertificateStore::load(&tls_config(&dir)).unwrap();
+    assert_eq!(store.certificate().cert[0], fixture_cert("first"));
+    assert!(!store.reload_if_changed().unwrap());
+
+    // 새 인증서로 교체 (SEC1 형식 키)
+    install(&dir, "second", "second", 30);
+    assert!(store.reload_if_changed().unwrap());
+    assert_eq!(store.certificate().cert[0], fixture_cert("second"));
+
+    // 키가 맞지 않으면 오류, 기존 인증서 유지
+    install(&dir, "first", "second", 0);
+    assert!(store.reload_if_changed().is_err());
+    assert_eq!(store.certificate().cert[0], fixture_cert("second"));
+
+    let mismatched = temp_dir("mismatched");
+    install(&mismatched, "second", "first", 0);
+    assert!(CertificateStore::load(&tls_config(&mismatched)).is_err());
+}
+
+#[actix_web::test]
+async fn serves_http2_and_http1_over_tls() {
+    let dir = temp_dir("serve");
+    install(&dir, "first", "first", 60);
+    let store = Arc::new(CertificateStore::load(&tls_config(&dir)).unwrap());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = HttpServer::new(|| App::new().route("/ping", web::get().to(HttpResponse::Ok)))
+        .workers(1)
+        .listen_rustls_0_23(listener, store.server_config().unwrap())
+        .unwrap()
+        .run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    let (h2, served) = tokio::task::spawn_blocking(move || {
+        let (conn, _) = handshake(addr, &[b"h2", b"http/1.1"]);
+        (
+            conn.alpn_protocol().map(<[u8]>::to_vec),
+            conn.peer_certificates().unwrap()[0].clone(),
+        )
+    })
+    .await
+    .unwrap();
+    assert_eq!(h2.as_deref(), Some(&b"h2"[..]));
+    assert_eq!(served, fixture_cert("first"));
+
+    // 교체한 인증서는 재시작 없이 새 연결에 사용
+    install(&dir, "second", "second", 0);
+    assert!(store.reload_if_changed().unwrap());
+    let response = tokio::task::spawn_blocking(move || {
+        let (mut conn, mut sock) = handshake(addr, &[b"http/1.1"]);
+        assert_eq!(conn.peer_certificates().unwrap()[0], fixture_cert("second"));
+        let mut stream = rustls::Stream::new(&mut conn, &mut sock);
+        stream
+            .write_all(b"GET /ping HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        response
+    })
+    .await
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+    handle.stop(false).await;
+}
+
+#[actix_web::test]
+async fn http_requests_redirect_to_https() {
+    let app =
+        test::init_service(App::new().configure(|cfg| https_redirect::configure(cfg, 8443))).await;
+    let req = test::TestRequest::post()
+        .uri("/api/v1/user?page=2")
+        .insert_header((header::HOST, "admin.example.com:8080"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 308);
+    assert_eq!(
+        res.headers().get(header::LOCATION).unwrap(),
+        "https://admin.example.com:8443/api/v1/user?page=2"
+    );
+
+    // 기본 포트(443)는 생략
+    let app =
+        test::init_service(App::new().configure(|cfg| https_redirect::configure(cfg, 443))).await;
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header((header::HOST, "[::1]:80"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(
+        res.headers().get(header::LOCATION).unwrap(),
+        "https://[::1]/"
+    );
+}